@@ -0,0 +1,98 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Key tree benchmarks with 1M keys, run with `cargo bench --bench key_tree`.
+//!
+//! Each dataset is measured for:
+//! - a full build (rebuild after a key is deleted)
+//! - merging scan pages of 2,000 keys, the way keys arrive from SCAN
+//! - expanding a folder and reading the rows of a screen
+
+#[allow(dead_code)]
+#[path = "../src/helpers/key_tree.rs"]
+mod key_tree;
+
+use gpui::SharedString;
+use key_tree::KeyTree;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const KEY_COUNT: usize = 1_000_000;
+const SCAN_PAGE_SIZE: usize = 2_000;
+const SCREEN_ROWS: usize = 50;
+
+/// Keys in the order SCAN would return them (not sorted).
+fn dataset(name: &str) -> Vec<SharedString> {
+    (0..KEY_COUNT)
+        .map(|i| {
+            // spread the keys like the hash table of the server does
+            let n = i.wrapping_mul(2_654_435_761) % KEY_COUNT;
+            match name {
+                "flat" => format!("key{n}"),
+                "namespaced" => format!("user:{}:session:{n}", n % 10_000),
+                _ => format!(
+                    "{}:{}:{}:{n}",
+                    ["cache", "user", "order", "queue"][n % 4],
+                    n % 100,
+                    n % 7
+                ),
+            }
+            .into()
+        })
+        .collect()
+}
+
+fn report(dataset: &str, case: &str, elapsed: Duration) {
+    println!("{dataset:<12} {case:<32} {elapsed:>12.2?}");
+}
+
+fn main() {
+    for name in ["flat", "namespaced", "mixed"] {
+        let keys = dataset(name);
+
+        let start = Instant::now();
+        let mut tree = KeyTree::new(":", 5);
+        tree.extend(keys.iter().cloned());
+        tree.prepare();
+        report(name, "full build", start.elapsed());
+        black_box(tree.rows());
+
+        let start = Instant::now();
+        let mut tree = KeyTree::new(":", 5);
+        let mut slowest_page = Duration::ZERO;
+        for page in keys.chunks(SCAN_PAGE_SIZE) {
+            let page_start = Instant::now();
+            tree.extend(page.iter().cloned());
+            tree.prepare();
+            slowest_page = slowest_page.max(page_start.elapsed());
+        }
+        report(name, "merge scan pages", start.elapsed());
+        report(name, "slowest scan page", slowest_page);
+
+        let start = Instant::now();
+        let folder = tree.row(0).filter(|row| row.is_folder).map(|row| row.id);
+        if let Some(folder) = &folder {
+            tree.expand(folder);
+            tree.prepare();
+        }
+        report(name, "expand first folder", start.elapsed());
+
+        let start = Instant::now();
+        let rows = tree.rows();
+        for index in (rows / 2)..(rows / 2 + SCREEN_ROWS).min(rows) {
+            black_box(tree.row(index));
+        }
+        report(name, "rows of a screen", start.elapsed());
+    }
+}
@@ -64,6 +64,15 @@ pub enum CustomIconName {
     Binary,
     ALargeSmall,
     ListChecvronsDownUp,
+    FileDiff,
+    History,
+    FileUp,
+    FileDown,
+    Regex,
+    Funnel,
+    Bookmark,
+    Play,
+    Layers,
 }
 
 impl CustomIconName {
@@ -93,6 +102,15 @@ impl CustomIconName {
             CustomIconName::Binary => "icons/binary.svg",
             CustomIconName::ALargeSmall => "icons/a-large-small.svg",
             CustomIconName::ListChecvronsDownUp => "icons/list-chevrons-down-up.svg",
+            CustomIconName::FileDiff => "icons/file-diff.svg",
+            CustomIconName::History => "icons/history.svg",
+            CustomIconName::FileUp => "icons/file-up.svg",
+            CustomIconName::FileDown => "icons/file-down.svg",
+            CustomIconName::Regex => "icons/regex.svg",
+            CustomIconName::Funnel => "icons/funnel.svg",
+            CustomIconName::Bookmark => "icons/bookmark.svg",
+            CustomIconName::Play => "icons/play.svg",
+            CustomIconName::Layers => "icons/layers.svg",
         }
         .into()
     }
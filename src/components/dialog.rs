@@ -60,10 +60,14 @@ pub struct FormField {
     label: SharedString,
     /// Placeholder of the field.
     placeholder: SharedString,
+    /// Initial value of the field (the selected index for radio groups).
+    value: SharedString,
     /// Whether to focus the field when the dialog opens.
     focus: bool,
     /// Options of the field.
     options: Option<Vec<SharedString>>,
+    /// Whether the input accepts multiple lines.
+    multi_line: bool,
     /// Handler to validate the field.
     validate_handler: Option<ValidateHandler>,
 }
@@ -86,6 +90,16 @@ impl FormField {
         self.placeholder = placeholder;
         self
     }
+    /// Sets the initial value, for radio groups the index of the selected option.
+    pub fn with_value(mut self, value: SharedString) -> Self {
+        self.value = value;
+        self
+    }
+    /// Configures the input to accept multiple lines, e.g. a pasted list.
+    pub fn with_multi_line(mut self) -> Self {
+        self.multi_line = true;
+        self
+    }
     /// Configures the field as a RadioGroup with the provided options.
     pub fn with_options(mut self, options: Vec<SharedString>) -> Self {
         self.field_type = FormFieldType::RadioGroup;
@@ -129,11 +143,18 @@ pub fn open_add_form_dialog(params: FormDialog, window: &mut Window, cx: &mut Ap
         match field.field_type {
             FormFieldType::Input => {
                 let validator = field.validate_handler.clone();
+                let multi_line = field.multi_line;
                 let state = cx.new(|cx| {
-                    InputState::new(window, cx)
+                    let state = InputState::new(window, cx)
                         .clean_on_escape()
                         .placeholder(field.placeholder.clone())
-                        .validate(move |s, _| validator.as_ref().is_none_or(|v| v(s)))
+                        .default_value(field.value.clone())
+                        .validate(move |s, _| validator.as_ref().is_none_or(|v| v(s)));
+                    if multi_line {
+                        state.multi_line(true).auto_grow(3, 10)
+                    } else {
+                        state
+                    }
                 });
 
                 // Capture the first field marked for focus
@@ -143,7 +164,8 @@ pub fn open_add_form_dialog(params: FormDialog, window: &mut Window, cx: &mut Ap
                 states.push(FieldState::Input(state));
             }
             FormFieldType::RadioGroup => {
-                states.push(FieldState::Radio(Rc::new(Cell::new(0))));
+                let selected = field.value.parse::<usize>().unwrap_or_default();
+                states.push(FieldState::Radio(Rc::new(Cell::new(selected))));
             }
         }
     }
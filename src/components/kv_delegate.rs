@@ -13,9 +13,9 @@
 // limitations under the License.
 
 use crate::assets::CustomIconName;
-use crate::states::{RedisValue, ZedisGlobalStore, ZedisServerState, i18n_common};
+use crate::states::{RedisElement, RedisValue, ZedisGlobalStore, ZedisServerState, i18n_common};
 use crate::views::{KvTableColumn, KvTableColumnType};
-use gpui::{App, Edges, Entity, SharedString, Window, div, prelude::*, px};
+use gpui::{App, Edges, Entity, ObjectFit, SharedString, Window, div, img, prelude::*, px};
 use gpui_component::{
     ActiveTheme, Disableable, Icon, IconName, Sizable, StyledExt, WindowExt,
     button::{Button, ButtonVariants},
@@ -29,12 +29,37 @@ use std::{cell::Cell, collections::HashMap, rc::Rc, sync::Arc};
 
 pub const INDEX_COLUMN_NAME: &str = "#";
 
+/// Row being dragged by its row number, rendered as the drag preview.
+#[derive(Clone)]
+struct DraggedKvRow {
+    row_ix: usize,
+    label: SharedString,
+}
+
+impl Render for DraggedKvRow {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .bg(cx.theme().drop_target)
+            .child(Label::new(self.label.clone()).text_sm())
+    }
+}
+/// Height of image thumbnails rendered in table cells
+const CELL_IMAGE_HEIGHT: f32 = 20.0;
+
 /// Trait defining the data fetching and manipulation interface for Key-Value data.
 /// Implementers allow the `ZedisKvDelegate` to display and edit various Redis data types (Hash, Set, List, ZSet).
 pub trait ZedisKvFetcher: 'static {
     /// Retrieves a value for a specific cell in the table.
     fn get(&self, row_ix: usize, col_ix: usize) -> Option<SharedString>;
 
+    /// Retrieves the raw element of a cell, used to render decoded, binary and image values.
+    fn get_element(&self, _row_ix: usize, _col_ix: usize) -> Option<RedisElement> {
+        None
+    }
+
     /// Returns the total count of items available.
     fn count(&self) -> usize;
 
@@ -56,6 +81,19 @@ pub trait ZedisKvFetcher: 'static {
         0
     }
 
+    /// Returns the number of elements before the first row, used for the row numbers.
+    fn row_offset(&self) -> usize {
+        0
+    }
+
+    /// Returns whether rows can be reordered by dragging their row number.
+    fn can_reorder(&self) -> bool {
+        false
+    }
+
+    /// Moves the row at `from` to `to`.
+    fn reorder(&self, _from: usize, _to: usize, _cx: &mut App) {}
+
     /// Returns the column indices that are readonly.
     fn readonly_columns(&self) -> Vec<usize> {
         vec![]
@@ -77,7 +115,18 @@ pub trait ZedisKvFetcher: 'static {
     fn handle_add_value(&self, _window: &mut Window, _cx: &mut App);
 
     /// Updates values for a specific row.
-    fn handle_update_value(&self, _row_ix: usize, _values: Vec<SharedString>, _window: &mut Window, _cx: &mut App) {}
+    ///
+    /// `original_values` are the values of the inputs when the edit started, in the same order,
+    /// so columns the user did not change (e.g. a TTL countdown) can be skipped.
+    fn handle_update_value(
+        &self,
+        _row_ix: usize,
+        _values: Vec<SharedString>,
+        _original_values: Vec<SharedString>,
+        _window: &mut Window,
+        _cx: &mut App,
+    ) {
+    }
 
     /// Factory method to create a new instance.
     fn new(server_state: Entity<ZedisServerState>, value: RedisValue) -> Self;
@@ -96,10 +145,14 @@ pub struct ZedisKvDelegate<T: ZedisKvFetcher> {
     columns: Vec<Column>,
     /// Tracks which row is currently being edited (if any).
     editing_row: Cell<Option<usize>>,
+    /// Whether the rows can not be edited, reordered or removed (a replica is browsed).
+    readonly: bool,
     /// Input states for editable cells, keyed by column index.
     value_states: HashMap<usize, Entity<InputState>>,
     /// Flag to ensure focus is applied only once when entering edit mode.
     edit_focus_done: bool,
+    /// Values of the input fields when the edit started, keyed by column index.
+    original_values: HashMap<usize, SharedString>,
 }
 
 impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
@@ -148,7 +201,9 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
             fetcher: Arc::new(fetcher),
             processing: Rc::new(Cell::new(false)),
             editing_row: Cell::new(None),
+            readonly: false,
             edit_focus_done: false,
+            original_values: HashMap::new(),
         }
     }
 
@@ -164,6 +219,14 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
         self.processing = Rc::new(Cell::new(false));
     }
 
+    /// Sets whether the rows can not be edited, reordered or removed.
+    pub fn set_readonly(&mut self, readonly: bool) {
+        self.readonly = readonly;
+        if readonly {
+            self.editing_row.set(None);
+        }
+    }
+
     /// Exits edit mode and resets related state flags.
     fn reset_edit(&mut self) {
         self.edit_focus_done = false;
@@ -180,8 +243,10 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
 
         // Populate input fields with current values from fetcher
         let fetcher = self.fetcher();
+        self.original_values.clear();
         for (col_ix, state) in &self.value_states {
             if let Some(value) = fetcher.get(row_ix, *col_ix) {
+                self.original_values.insert(*col_ix, value.clone());
                 state.update(cx, |input, cx| input.set_value(value, window, cx));
             }
         }
@@ -195,17 +260,19 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
         self.reset_edit();
 
         // Collect values from input fields in sorted column order
-        let values: Vec<SharedString> = {
-            let mut col_indices: Vec<_> = self.value_states.keys().copied().collect();
-            col_indices.sort_unstable();
-
-            col_indices
-                .iter()
-                .filter_map(|&col_ix| self.value_states.get(&col_ix).map(|state| state.read(cx).value()))
-                .collect()
-        };
+        let mut col_indices: Vec<_> = self.value_states.keys().copied().collect();
+        col_indices.sort_unstable();
+        let values: Vec<SharedString> = col_indices
+            .iter()
+            .filter_map(|&col_ix| self.value_states.get(&col_ix).map(|state| state.read(cx).value()))
+            .collect();
+        let original_values: Vec<SharedString> = col_indices
+            .iter()
+            .map(|col_ix| self.original_values.remove(col_ix).unwrap_or_default())
+            .collect();
 
-        self.fetcher().handle_update_value(row_ix, values, window, cx);
+        self.fetcher()
+            .handle_update_value(row_ix, values, original_values, window, cx);
     }
 
     /// Renders action buttons (edit/save/cancel/delete) for a table row.
@@ -243,7 +310,7 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
                 .mr_2()
                 .icon(icon)
                 .tooltip(i18n_common(cx, "update_tooltip"))
-                .disabled(processing.get())
+                .disabled(self.readonly || processing.get())
                 .on_click(cx.listener(move |this, _, window, cx| {
                     if is_editing {
                         this.delegate_mut().handle_update_row(row_ix, window, cx);
@@ -280,10 +347,11 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
                 .ghost()
                 .icon(Icon::new(CustomIconName::FileXCorner))
                 .tooltip(i18n_common(cx, "remove_tooltip"))
-                .disabled(processing.get())
+                .disabled(self.readonly || processing.get())
                 .on_click(cx.listener(move |this, _, window, cx| {
                     let processing = this.delegate_mut().processing.clone();
                     let value = fetcher.get(row_ix, fetcher.primary_index()).unwrap_or_default();
+                    let row_offset = fetcher.row_offset();
                     let fetcher = fetcher.clone();
 
                     cx.stop_propagation();
@@ -292,7 +360,7 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
                         let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
                         let message = t!(
                             "common.remove_item_prompt",
-                            row = row_ix + 1,
+                            row = row_offset + row_ix + 1,
                             value = value,
                             locale = locale
                         );
@@ -368,9 +436,32 @@ impl<T: ZedisKvFetcher + 'static> TableDelegate for ZedisKvDelegate<T> {
         // Handle special column types
         if let Some(table_column) = self.table_columns.get(col_ix) {
             match table_column.column_type {
-                // Index column: Display row number (1-based)
+                // Index column: Display row number (1-based), dragging it reorders the rows
                 KvTableColumnType::Index => {
-                    return base.child(Label::new((row_ix + 1).to_string()).text_align(column.align).w_full());
+                    let label: SharedString = (self.fetcher.row_offset() + row_ix + 1).to_string().into();
+                    if self.readonly || !self.fetcher.can_reorder() {
+                        return base.child(Label::new(label).text_align(column.align).w_full());
+                    }
+                    let fetcher = self.fetcher.clone();
+                    return base
+                        .drag_over::<DraggedKvRow>(|style, _, _, cx| style.bg(cx.theme().drop_target))
+                        .on_drop(move |row: &DraggedKvRow, _, cx| {
+                            fetcher.reorder(row.row_ix, row_ix, cx);
+                        })
+                        .child(
+                            div()
+                                .id(("zedis-kv-row-drag", row_ix))
+                                .w_full()
+                                .cursor_grab()
+                                .on_drag(
+                                    DraggedKvRow {
+                                        row_ix,
+                                        label: label.clone(),
+                                    },
+                                    |row, _, _, cx| cx.new(|_| row.clone()),
+                                )
+                                .child(Label::new(label).text_align(column.align)),
+                        );
                 }
                 // Action column: Display edit/delete/cancel buttons
                 KvTableColumnType::Action => {
@@ -390,6 +481,27 @@ impl<T: ZedisKvFetcher + 'static> TableDelegate for ZedisKvDelegate<T> {
             return base.child(Input::new(value_state).small().cleanable(true));
         }
 
+        // Element: Render image thumbnail or decoded text with its format
+        if let Some(element) = self.fetcher.get_element(row_ix, col_ix) {
+            let format_label = element.format_label().map(|format| {
+                Label::new(format)
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .flex_none()
+            });
+            let content = if let Some(image) = element.image.clone() {
+                img(image)
+                    .h(px(CELL_IMAGE_HEIGHT))
+                    .object_fit(ObjectFit::Contain)
+                    .into_any_element()
+            } else {
+                Label::new(element.display_text())
+                    .text_align(column.align)
+                    .into_any_element()
+            };
+            return base.gap_1().children(format_label).child(content);
+        }
+
         // Default: Render value as label
         let value = self.fetcher.get(row_ix, col_ix).unwrap_or_else(|| "--".into());
         base.child(Label::new(value).text_align(column.align))
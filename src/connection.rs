@@ -15,7 +15,8 @@
 mod async_connection;
 mod config;
 mod manager;
+mod sentinel;
 
 pub use async_connection::RedisAsyncConn;
-pub use config::{QueryMode, RedisServer, get_servers, save_servers};
-pub use manager::{RedisClientDescription, get_connection_manager};
+pub use config::{KeyFilter, QueryMode, ReadPreference, RedisServer, get_servers, save_servers};
+pub use manager::{RedisClient, RedisClientDescription, ReplicaInfo, get_connection_manager};
@@ -14,17 +14,26 @@
 
 use super::config::RedisServer;
 use crate::error::Error;
+use ahash::AHashMap;
 use futures::future::try_join_all;
+use parking_lot::Mutex;
 use redis::{
-    Client, Cmd, FromRedisValue, Pipeline, RedisFuture, Value,
+    AsyncConnectionConfig, Client, Cmd, FromRedisValue, Pipeline, RedisFuture, Value,
     aio::{ConnectionLike, MultiplexedConnection},
     cluster_async::ClusterConnection,
     cmd,
 };
-use std::{sync::LazyLock, time::Duration};
+use std::{
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
+use tracing::debug;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
+pub(crate) const CONNECTION_TIMEOUT: Duration = Duration::from_secs(30);
+pub(crate) const RESPONSE_TIMEOUT: Duration = Duration::from_secs(60);
+
 static DELAY: LazyLock<Option<Duration>> = LazyLock::new(|| {
     let value = std::env::var("REDIS_DELAY").unwrap_or_default();
     humantime::parse_duration(&value).ok()
@@ -94,43 +103,143 @@ impl ConnectionLike for RedisAsyncConn {
     }
 }
 
+/// Node connections idle for longer are closed
+const NODE_IDLE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// Node connections idle for longer are pinged before they are reused
+const NODE_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const NODE_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+struct NodeConnection {
+    conn: MultiplexedConnection,
+    last_used: Instant,
+}
+
+/// Connections to the nodes of a server, reused by the commands sent to every master.
+///
+/// - One multiplexed connection per node, opened (and SELECTed) on first use
+/// - Connections idle for a while are pinged before they are reused, and reopened if the ping fails
+/// - Connections idle for too long, or failing with a connection error, are evicted
+pub(crate) struct NodeConnectionPool {
+    db: usize,
+    /// Whether the connections are to cluster replicas, READONLY is sent when they are opened
+    readonly: bool,
+    connections: Mutex<AHashMap<String, NodeConnection>>,
+}
+
+impl NodeConnectionPool {
+    pub fn new(db: usize) -> Self {
+        Self {
+            db,
+            readonly: false,
+            connections: Mutex::new(AHashMap::new()),
+        }
+    }
+    /// Pool of connections to cluster replicas, which only serve reads after READONLY.
+    pub fn readonly(mut self) -> Self {
+        self.readonly = true;
+        self
+    }
+    /// Reuse an open connection for the node, e.g. the main connection of a standalone server.
+    pub fn insert(&self, node: &RedisServer, conn: MultiplexedConnection) {
+        self.connections.lock().insert(
+            node_key(node),
+            NodeConnection {
+                conn,
+                last_used: Instant::now(),
+            },
+        );
+    }
+    /// Close the connection of the node, the next command opens a new one.
+    fn evict(&self, node: &RedisServer) {
+        self.connections.lock().remove(&node_key(node));
+    }
+    /// Get the connection of the node, opening it if needed.
+    async fn get(&self, node: &RedisServer) -> Result<MultiplexedConnection> {
+        let key = node_key(node);
+        let now = Instant::now();
+        let cached = {
+            let mut connections = self.connections.lock();
+            connections.retain(|_, item| now.duration_since(item.last_used) < NODE_IDLE_TIMEOUT);
+            connections.get_mut(&key).map(|item| {
+                let idle = now.duration_since(item.last_used);
+                item.last_used = now;
+                (item.conn.clone(), idle)
+            })
+        };
+        if let Some((mut conn, idle)) = cached {
+            if idle < NODE_HEALTH_CHECK_INTERVAL {
+                return Ok(conn);
+            }
+            let ping = async { Some(cmd("PING").query_async::<()>(&mut conn).await) };
+            let timeout = async {
+                smol::Timer::after(NODE_HEALTH_CHECK_TIMEOUT).await;
+                None
+            };
+            if let Some(Ok(())) = smol::future::or(ping, timeout).await {
+                return Ok(conn);
+            }
+            debug!(node = key, "node connection failed the health check");
+            self.evict(node);
+        }
+
+        debug!(node = key, db = self.db, "open node connection");
+        let client = open_client(node)?;
+        let cfg = AsyncConnectionConfig::default()
+            .set_connection_timeout(Some(CONNECTION_TIMEOUT))
+            .set_response_timeout(Some(RESPONSE_TIMEOUT));
+        let mut conn = client.get_multiplexed_async_connection_with_config(&cfg).await?;
+        if self.db != 0 {
+            let _: () = cmd("SELECT").arg(self.db).query_async(&mut conn).await?;
+        }
+        if self.readonly {
+            let _: () = cmd("READONLY").query_async(&mut conn).await?;
+        }
+        self.insert(node, conn.clone());
+        Ok(conn)
+    }
+    /// Run the command on the node, retrying once on a new connection if the connection failed.
+    async fn query<T: FromRedisValue>(&self, node: &RedisServer, command: &Cmd) -> Result<T> {
+        let mut conn = self.get(node).await?;
+        match command.query_async(&mut conn).await {
+            Ok(value) => Ok(value),
+            Err(e) if e.is_io_error() || e.is_connection_dropped() || e.is_timeout() => {
+                debug!(node = node_key(node), error = %e, "node connection failed, reconnect");
+                self.evict(node);
+                let mut conn = self.get(node).await?;
+                Ok(command.query_async(&mut conn).await?)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+fn node_key(node: &RedisServer) -> String {
+    format!("{}:{}", node.host, node.port)
+}
+
 /// Queries multiple Redis master nodes concurrently.
 ///
-/// This function establishes connections to all provided addresses in parallel
-/// and executes the corresponding commands.
+/// The connections of the nodes are reused from the pool, commands are retried
+/// once if their connection failed, so they must be safe to run twice.
 ///
 /// # Arguments
 ///
-/// * `addrs` - A vector of Redis connection strings (e.g., "redis://127.0.0.1").
+/// * `pool` - The connections of the nodes.
+/// * `addrs` - The configurations of the master nodes.
 /// * `cmds` - A vector of commands to execute. If there are fewer commands than addresses,
 ///   the first command is reused for the remaining addresses.
 pub(crate) async fn query_async_masters<T: FromRedisValue>(
+    pool: &NodeConnectionPool,
     addrs: Vec<RedisServer>,
-    db: usize,
     cmds: Vec<Cmd>,
 ) -> Result<Vec<T>> {
     let first_cmd = cmds.first().ok_or_else(|| Error::Invalid {
         message: "Commands are empty".to_string(),
     })?;
     let tasks = addrs.into_iter().enumerate().map(|(index, addr)| {
-        // Clone data to move ownership into the async block.
-        // let addr = addr.to_string();
         // Use the specific command for this index, or fallback to the first command.
-        let current_cmd = cmds.get(index).unwrap_or(first_cmd).clone();
-
-        async move {
-            // Establish a multiplexed async connection to the specific node.
-            let client = open_client(&addr)?;
-            let mut conn = client.get_multiplexed_async_connection().await?;
-            if db != 0 {
-                let _: () = cmd("SELECT").arg(db).query_async(&mut conn).await?;
-            }
-
-            // Execute the command asynchronously.
-            let value: T = current_cmd.query_async(&mut conn).await?;
-
-            Ok::<T, Error>(value)
-        }
+        let current_cmd = cmds.get(index).unwrap_or(first_cmd);
+        async move { pool.query(&addr, current_cmd).await }
     });
 
     let values = try_join_all(tasks).await?;
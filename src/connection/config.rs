@@ -33,6 +33,10 @@ pub enum QueryMode {
     All,
     Prefix,
     Exact,
+    /// Keys ending with the keyword
+    Suffix,
+    /// Keys matching the keyword as a regular expression, applied client-side to scanned keys
+    Regex,
 }
 
 impl fmt::Display for QueryMode {
@@ -40,6 +44,8 @@ impl fmt::Display for QueryMode {
         let s = match self {
             QueryMode::Prefix => "^",
             QueryMode::Exact => "=",
+            QueryMode::Suffix => "$",
+            QueryMode::Regex => "~",
             _ => "*",
         };
         write!(f, "{}", s)
@@ -53,11 +59,53 @@ impl FromStr for QueryMode {
         match s {
             "^" => Ok(QueryMode::Prefix),
             "=" => Ok(QueryMode::Exact),
+            "$" => Ok(QueryMode::Suffix),
+            "~" => Ok(QueryMode::Regex),
             _ => Ok(QueryMode::All),
         }
     }
 }
 
+/// Filters of the key search, combined with AND.
+///
+/// The type is passed to SCAN's TYPE option, the others are checked client-side for each scanned key.
+#[derive(Debug, Default, Deserialize, Clone, PartialEq, Serialize)]
+pub struct KeyFilter {
+    /// Key type (string, list, set, zset, hash, stream)
+    #[serde(default)]
+    pub key_type: Option<String>,
+    /// Only keys without TTL
+    #[serde(default)]
+    pub no_ttl: bool,
+    /// Only keys expiring within the seconds
+    #[serde(default)]
+    pub expires_within: Option<u64>,
+    /// Only keys using more memory than the bytes (MEMORY USAGE)
+    #[serde(default)]
+    pub min_memory: Option<u64>,
+}
+
+impl KeyFilter {
+    /// Returns true if no filter is set.
+    pub fn is_empty(&self) -> bool {
+        self == &KeyFilter::default()
+    }
+    /// Returns true if each scanned key has to be checked client-side.
+    pub fn needs_key_check(&self) -> bool {
+        self.no_ttl || self.expires_within.is_some() || self.min_memory.is_some()
+    }
+}
+
+/// Nodes the keys and values are read from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadPreference {
+    #[default]
+    Master,
+    /// Scan and load values from a replica when one is online, writes always go to the masters
+    PreferReplica,
+}
+
 #[derive(Debug, Default, Deserialize, Clone, Serialize)]
 pub struct RedisServer {
     pub id: String,
@@ -70,14 +118,41 @@ pub struct RedisServer {
     pub description: Option<String>,
     pub updated_at: Option<String>,
     pub query_mode: Option<String>,
+    pub key_filter: Option<KeyFilter>,
     pub soft_wrap: Option<bool>,
     pub tls: Option<bool>,
     pub insecure: Option<bool>,
     pub client_cert: Option<String>,
     pub client_key: Option<String>,
     pub root_cert: Option<String>,
+    /// Username of the sentinels, the data nodes use `username`
+    pub sentinel_username: Option<String>,
+    /// Password of the sentinels, the data nodes use `password`
+    pub sentinel_password: Option<String>,
+    /// Whether the sentinels use TLS, with the certificates of the data nodes
+    pub sentinel_tls: Option<bool>,
+    pub sentinel_insecure: Option<bool>,
+    pub read_preference: Option<ReadPreference>,
 }
 impl RedisServer {
+    /// Returns true if reads should go to a replica.
+    pub fn prefers_replica(&self) -> bool {
+        self.read_preference == Some(ReadPreference::PreferReplica)
+    }
+    /// Returns true if the sentinels have their own credentials or TLS settings.
+    pub fn has_sentinel_settings(&self) -> bool {
+        self.sentinel_username.is_some() || self.sentinel_password.is_some() || self.sentinel_tls.is_some()
+    }
+    /// Connection config of the sentinels, used for the sentinel handshake and SENTINEL commands.
+    pub fn sentinel_config(&self) -> RedisServer {
+        RedisServer {
+            username: self.sentinel_username.clone(),
+            password: self.sentinel_password.clone(),
+            tls: self.sentinel_tls,
+            insecure: self.sentinel_insecure,
+            ..self.clone()
+        }
+    }
     /// Generates the connection URL based on host, port, and optional password.
     pub fn get_connection_url(&self) -> String {
         let tls = self.tls.unwrap_or(false);
@@ -154,6 +229,9 @@ pub fn get_servers() -> Result<Vec<RedisServer>> {
         if let Some(password) = &server.password {
             server.password = Some(decrypt(password).unwrap_or(password.clone()));
         }
+        if let Some(password) = &server.sentinel_password {
+            server.sentinel_password = Some(decrypt(password).unwrap_or(password.clone()));
+        }
     }
     Ok(servers)
 }
@@ -164,6 +242,9 @@ pub async fn save_servers(mut servers: Vec<RedisServer>) -> Result<()> {
         if let Some(password) = &server.password {
             server.password = Some(encrypt(password)?);
         }
+        if let Some(password) = &server.sentinel_password {
+            server.sentinel_password = Some(encrypt(password)?);
+        }
     }
     let path = get_or_create_server_config()?;
     let value = toml::to_string(&RedisServers { servers }).map_err(|e| Error::Invalid { message: e.to_string() })?;
@@ -13,18 +13,22 @@
 // limitations under the License.
 
 use super::{
-    async_connection::{RedisAsyncConn, open_client, query_async_masters},
+    async_connection::{
+        CONNECTION_TIMEOUT, NodeConnectionPool, RESPONSE_TIMEOUT, RedisAsyncConn, open_client, query_async_masters,
+    },
     config::{RedisServer, get_config},
+    sentinel::{SentinelTopology, resolve_master, watch_switch_master},
 };
 use crate::error::Error;
+use crate::helpers::{escape_key, unescape_glob_pattern};
 use dashmap::DashMap;
+use futures::channel::mpsc::UnboundedSender;
 use gpui::SharedString;
 use redis::{AsyncConnectionConfig, Client, Cmd, FromRedisValue, InfoDict, Role, cluster, cmd};
 use semver::Version;
 use std::{
-    collections::{HashMap, HashSet},
-    sync::LazyLock,
-    time::Duration,
+    collections::HashSet,
+    sync::{Arc, LazyLock, atomic::AtomicBool},
 };
 use tracing::{debug, error, info};
 
@@ -64,7 +68,10 @@ struct RedisNode {
     server: RedisServer,
     // connection_url: String,
     role: NodeRole,
-    master_name: Option<String>,
+    /// Node id, cluster only
+    id: String,
+    /// Id of the master of a replica, cluster only
+    master_id: Option<String>,
 }
 
 impl RedisNode {
@@ -76,9 +83,12 @@ impl RedisNode {
 // Information parsed from `CLUSTER NODES` command
 #[derive(Debug, Clone)]
 pub struct ClusterNodeInfo {
+    pub id: String,
     pub ip: String,
     pub port: u16,
     pub role: NodeRole,
+    /// Id of the master of a replica
+    pub master_id: Option<String>,
 }
 
 /// Parses a Redis address string like "ip:port@cport" or just "ip:port".
@@ -136,14 +146,131 @@ fn parse_cluster_nodes(raw_data: &str) -> Result<Vec<ClusterNodeInfo>> {
             NodeRole::Unknown
         };
 
-        nodes.push(ClusterNodeInfo { ip, port, role });
+        let master_id = Some(parts[3]).filter(|id| *id != "-").map(String::from);
+
+        nodes.push(ClusterNodeInfo {
+            id: parts[0].to_string(),
+            ip,
+            port,
+            role,
+            master_id,
+        });
     }
 
     Ok(nodes)
 }
 
-const CONNECTION_TIMEOUT: Duration = Duration::from_secs(30);
-const RESPONSE_TIMEOUT: Duration = Duration::from_secs(60);
+/// Builds the client of the cluster, routing the read-only commands to replicas if asked.
+fn build_cluster_client(nodes: &[RedisNode], read_from_replicas: bool) -> Result<cluster::ClusterClient> {
+    let addrs: Vec<String> = nodes.iter().map(|n| n.server.get_connection_url()).collect();
+    let mut builder = cluster::ClusterClientBuilder::new(addrs);
+    let node = &nodes[0];
+    if let Some(certificates) = node.server.tls_certificates() {
+        builder = builder.certs(certificates);
+    }
+    if node.server.insecure.unwrap_or(false) {
+        builder = builder.danger_accept_invalid_hostnames(true);
+    }
+    if read_from_replicas {
+        builder = builder.read_from_replicas();
+    }
+    Ok(builder.build()?)
+}
+
+/// Replicas and connections used for reads with the read preference `PreferReplica`
+#[derive(Default)]
+struct ReadReplicas {
+    nodes: Vec<RedisNode>,
+    pool: Option<NodeConnectionPool>,
+    connection: Option<RedisAsyncConn>,
+}
+
+/// Picks the replicas to read from and opens their connections.
+///
+/// - Cluster: a replica of each master (the master if it has none) for SCAN, and a
+///   cluster connection routing the read-only commands to replicas (READONLY)
+/// - Standalone and sentinel: the online replica with the lowest lag (INFO replication)
+///   which can be connected to, the address reported by the master may not be reachable
+///   from the client (NAT, docker)
+///
+/// No replica is picked if none is usable, the reads then go to the masters.
+/// An error is returned if no replica can be connected to.
+async fn open_read_replicas(
+    server_type: &ServerType,
+    nodes: &[RedisNode],
+    master_nodes: &[RedisNode],
+    connection: &RedisAsyncConn,
+    db: usize,
+) -> Result<ReadReplicas> {
+    if *server_type == ServerType::Cluster {
+        let read_nodes: Vec<RedisNode> = master_nodes
+            .iter()
+            .map(|master| {
+                nodes
+                    .iter()
+                    .find(|node| node.role == NodeRole::Slave && node.master_id.as_ref() == Some(&master.id))
+                    .unwrap_or(master)
+                    .clone()
+            })
+            .collect();
+        if !read_nodes.iter().any(|node| node.role == NodeRole::Slave) {
+            return Ok(ReadReplicas::default());
+        }
+        let client = RClient::Cluster(build_cluster_client(nodes, true)?);
+        let connection = get_async_connection(&client, db).await?;
+        return Ok(ReadReplicas {
+            nodes: read_nodes,
+            pool: Some(NodeConnectionPool::new(db).readonly()),
+            connection: Some(connection),
+        });
+    }
+    let Some(master) = master_nodes.first() else {
+        return Ok(ReadReplicas::default());
+    };
+    let mut conn = connection.clone();
+    let info: String = cmd("INFO").arg("replication").query_async(&mut conn).await?;
+    let mut replicas: Vec<ReplicaInfo> = parse_replication_info(&info)
+        .into_iter()
+        .filter(|replica| replica.online)
+        .collect();
+    replicas.sort_by_key(|replica| replica.lag.unwrap_or(u64::MAX));
+    let mut last_error = None;
+    for replica in replicas {
+        let address = replica.host_port();
+        let mut server = master.server.clone();
+        server.host = replica.host;
+        server.port = replica.port;
+        let connection = match open_client(&server) {
+            Ok(client) => get_async_connection(&RClient::Single(client), db).await,
+            Err(e) => Err(e),
+        };
+        let connection = match connection {
+            Ok(connection) => connection,
+            Err(e) => {
+                error!(error = %e, replica = address, "connect to the replica failed");
+                last_error = Some(e);
+                continue;
+            }
+        };
+        let pool = NodeConnectionPool::new(db);
+        if let RedisAsyncConn::Single(conn) = &connection {
+            pool.insert(&server, conn.clone());
+        }
+        return Ok(ReadReplicas {
+            nodes: vec![RedisNode {
+                server,
+                role: NodeRole::Slave,
+                ..Default::default()
+            }],
+            pool: Some(pool),
+            connection: Some(connection),
+        });
+    }
+    match last_error {
+        Some(e) => Err(e),
+        None => Ok(ReadReplicas::default()),
+    }
+}
 
 /// Establishes an asynchronous connection based on the client type.
 async fn get_async_connection(client: &RClient, db: usize) -> Result<RedisAsyncConn> {
@@ -171,12 +298,27 @@ async fn get_async_connection(client: &RClient, db: usize) -> Result<RedisAsyncC
 // TODO 是否在client中保存connection
 #[derive(Clone)]
 pub struct RedisClient {
-    db: usize,
     server_type: ServerType,
     nodes: Vec<RedisNode>,
     master_nodes: Vec<RedisNode>,
     version: Version,
+    /// Client of the main connection, used to open dedicated connections
+    client: RClient,
+    db: usize,
     connection: RedisAsyncConn,
+    /// Connections to the master nodes, shared by the clones of the client
+    master_pool: Arc<NodeConnectionPool>,
+    /// Replica browsed instead of the master (read-only), sentinel servers only
+    browsed_replica: Option<RedisNode>,
+    /// Nodes the keys are scanned from with the read preference `PreferReplica`,
+    /// a replica of each master (the master if it has no replica)
+    read_replicas: Vec<RedisNode>,
+    /// Connections to the read replicas
+    replica_pool: Arc<NodeConnectionPool>,
+    /// Connection the values are read from with the read preference `PreferReplica`
+    read_connection: Option<RedisAsyncConn>,
+    /// Why the replicas could not be opened, the reads then go to the masters
+    read_replica_error: Option<String>,
 }
 #[derive(Debug, Clone, Default)]
 pub struct RedisClientDescription {
@@ -184,6 +326,59 @@ pub struct RedisClientDescription {
     pub master_nodes: SharedString,
     pub slave_nodes: SharedString,
 }
+
+/// A replica of the master and its replication lag
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplicaInfo {
+    pub host: String,
+    pub port: u16,
+    /// Whether the replica is connected to the master (`state=online`)
+    pub online: bool,
+    /// Seconds since the last acknowledgment of the replica
+    pub lag: Option<u64>,
+    /// Bytes of the replication stream not acknowledged by the replica yet
+    pub offset_lag: Option<u64>,
+}
+
+impl ReplicaInfo {
+    pub fn host_port(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+/// Parses the replicas of `INFO replication` of a master,
+/// e.g. `slave0:ip=10.0.0.2,port=6379,state=online,offset=1234,lag=0`.
+fn parse_replication_info(info: &str) -> Vec<ReplicaInfo> {
+    let mut master_offset = None;
+    let mut replicas = vec![];
+    for line in info.lines() {
+        let Some((name, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        if name == "master_repl_offset" {
+            master_offset = value.parse::<u64>().ok();
+            continue;
+        }
+        if name.strip_prefix("slave").is_none_or(|n| n.parse::<usize>().is_err()) {
+            continue;
+        }
+        let mut replica = ReplicaInfo::default();
+        let mut offset = None;
+        for field in value.split(',') {
+            match field.split_once('=') {
+                Some(("ip", ip)) => replica.host = ip.to_string(),
+                Some(("port", port)) => replica.port = port.parse().unwrap_or_default(),
+                Some(("state", state)) => replica.online = state == "online",
+                Some(("offset", value)) => offset = value.parse::<u64>().ok(),
+                Some(("lag", lag)) => replica.lag = lag.parse().ok(),
+                _ => {}
+            }
+        }
+        replica.offset_lag = offset.map(|offset| master_offset.unwrap_or(offset).saturating_sub(offset));
+        replicas.push(replica);
+    }
+    replicas
+}
 impl RedisClient {
     pub fn nodes(&self) -> (usize, usize) {
         (self.master_nodes.len(), self.nodes.len())
@@ -194,6 +389,45 @@ impl RedisClient {
     pub fn supports_db_selection(&self) -> bool {
         self.server_type != ServerType::Cluster
     }
+    /// Returns true if the server is a cluster, multi-key commands then require keys in the same slot.
+    pub fn is_cluster(&self) -> bool {
+        self.server_type == ServerType::Cluster
+    }
+    /// Returns true if the master is resolved by sentinels.
+    pub fn is_sentinel(&self) -> bool {
+        self.server_type == ServerType::Sentinel
+    }
+    /// Returns the address of the browsed replica, if any.
+    pub fn browsed_replica(&self) -> Option<String> {
+        self.browsed_replica.as_ref().map(|node| node.host_port())
+    }
+    /// The nodes the keys are read from, the masters or the browsed replica.
+    fn read_nodes(&self) -> &[RedisNode] {
+        match &self.browsed_replica {
+            Some(replica) => std::slice::from_ref(replica),
+            None => &self.master_nodes,
+        }
+    }
+    /// The nodes the keys are scanned from and their connections, the read replicas if any.
+    fn scan_nodes(&self) -> (&[RedisNode], &NodeConnectionPool) {
+        if self.browsed_replica.is_none() && !self.read_replicas.is_empty() {
+            (&self.read_replicas, &self.replica_pool)
+        } else {
+            (self.read_nodes(), &self.master_pool)
+        }
+    }
+    /// Returns why the replicas of the read preference `PreferReplica` could not be opened.
+    pub fn read_replica_error(&self) -> Option<String> {
+        self.read_replica_error.clone()
+    }
+    /// Returns the addresses of the replicas the reads are sent to, empty if they go to the masters.
+    pub fn read_replicas(&self) -> Vec<String> {
+        self.read_replicas
+            .iter()
+            .filter(|node| node.role == NodeRole::Slave)
+            .map(|node| node.host_port())
+            .collect()
+    }
 
     pub fn nodes_description(&self) -> RedisClientDescription {
         let master_nodes: Vec<String> = self.master_nodes.iter().map(|node| node.host_port()).collect();
@@ -215,6 +449,28 @@ impl RedisClient {
     pub fn connection(&self) -> RedisAsyncConn {
         self.connection.clone()
     }
+    /// Opens a new connection to the same nodes as `connection`, not shared with other tasks.
+    ///
+    /// Required by connection state like WATCH, which would be affected by
+    /// (or affect) the commands of other tasks on the shared connection.
+    pub async fn dedicated_connection(&self) -> Result<RedisAsyncConn> {
+        get_async_connection(&self.client, self.db).await
+    }
+    /// Returns the connection for reads (TYPE, value loads), to the replicas with the
+    /// read preference `PreferReplica`. Writes must use `connection`.
+    pub fn read_connection(&self) -> RedisAsyncConn {
+        self.read_connection.as_ref().unwrap_or(&self.connection).clone()
+    }
+    /// Returns the connection the value of a key is read from: the master if the key
+    /// has been written by this client (a replica may not have the write yet),
+    /// else the connection for reads.
+    pub fn value_connection(&self, written: bool) -> RedisAsyncConn {
+        if written {
+            self.connection()
+        } else {
+            self.read_connection()
+        }
+    }
     /// Checks if the client version is at least the given version.
     /// # Arguments
     /// * `version` - The version to check.
@@ -224,16 +480,43 @@ impl RedisClient {
         self.version >= Version::parse(version).unwrap_or(Version::new(0, 0, 0))
     }
 
-    /// Executes commands on all master nodes concurrently.
+    /// Executes commands on all master nodes (or the browsed replica) concurrently.
+    ///
+    /// The connections of the nodes are pooled, commands must be safe to retry.
     /// # Arguments
     /// * `cmds` - A vector of commands to execute.
     /// # Returns
     /// * `Vec<T>` - A vector of results from the commands.
     pub async fn query_async_masters<T: FromRedisValue>(&self, cmds: Vec<Cmd>) -> Result<Vec<T>> {
-        let addrs: Vec<_> = self.master_nodes.iter().map(|item| item.server.clone()).collect();
-        let values = query_async_masters(addrs, self.db, cmds).await?;
+        let addrs: Vec<_> = self.read_nodes().iter().map(|item| item.server.clone()).collect();
+        let values = query_async_masters(&self.master_pool, addrs, cmds).await?;
         Ok(values)
     }
+    /// Lists the replicas of the masters with their lag (INFO replication of the masters).
+    ///
+    /// Replicas known by the sentinels or the cluster but not connected to a master are listed offline.
+    pub async fn replicas(&self) -> Result<Vec<ReplicaInfo>> {
+        let addrs: Vec<_> = self.master_nodes.iter().map(|node| node.server.clone()).collect();
+        if addrs.is_empty() {
+            return Ok(vec![]);
+        }
+        let infos: Vec<String> =
+            query_async_masters(&self.master_pool, addrs, vec![cmd("INFO").arg("replication").clone()]).await?;
+        let mut replicas: Vec<ReplicaInfo> = infos.iter().flat_map(|info| parse_replication_info(info)).collect();
+        for node in self.nodes.iter().filter(|node| node.role == NodeRole::Slave) {
+            if !replicas
+                .iter()
+                .any(|replica| replica.host == node.server.host && replica.port == node.server.port)
+            {
+                replicas.push(ReplicaInfo {
+                    host: node.server.host.clone(),
+                    port: node.server.port,
+                    ..Default::default()
+                });
+            }
+        }
+        Ok(replicas)
+    }
     /// Calculates the total DB size across all masters.
     /// # Returns
     /// * `u64` - The total DB size.
@@ -247,56 +530,74 @@ impl RedisClient {
         let _: () = cmd("PING").query_async(&mut conn).await?;
         Ok(())
     }
-    /// Returns the number of master nodes.
+    /// Returns the number of master nodes (1 when browsing a replica).
     /// # Returns
     /// * `usize` - The number of master nodes.
     pub fn count_masters(&self) -> Result<usize> {
-        Ok(self.master_nodes.len())
+        Ok(self.scan_nodes().0.len())
     }
     /// Initiates a SCAN operation across all masters.
     /// # Arguments
     /// * `pattern` - The pattern to match keys.
     /// * `count` - The count of keys to return.
+    /// * `key_type` - Only return keys of this type (SCAN TYPE, Redis 6.0+).
     /// # Returns
     /// * `(Vec<u64>, Vec<SharedString>)` - A tuple containing the new cursors and the keys.
-    pub async fn first_scan(&self, pattern: &str, count: u64) -> Result<(Vec<u64>, Vec<SharedString>)> {
+    pub async fn first_scan(
+        &self,
+        pattern: &str,
+        count: u64,
+        key_type: Option<&str>,
+    ) -> Result<(Vec<u64>, Vec<SharedString>)> {
         let master_count = self.count_masters()?;
         let cursors = vec![0; master_count];
 
-        let (cursors, keys) = self.scan(cursors, pattern, count).await?;
+        let (cursors, keys) = self.scan(cursors, pattern, count, key_type).await?;
         Ok((cursors, keys))
     }
     /// Continues a SCAN operation.
+    ///
+    /// Keys are returned in their escaped text form (see `escape_key`),
+    /// so binary keys keep their original bytes.
     /// # Arguments
     /// * `cursors` - A vector of cursors for each master.
     /// * `pattern` - The pattern to match keys.
     /// * `count` - The count of keys to return.
+    /// * `key_type` - Only return keys of this type (SCAN TYPE, Redis 6.0+).
     /// # Returns
     /// * `(Vec<u64>, Vec<SharedString>)` - A tuple containing the new cursors and the keys.
-    pub async fn scan(&self, cursors: Vec<u64>, pattern: &str, count: u64) -> Result<(Vec<u64>, Vec<SharedString>)> {
-        debug!("scan, cursors: {cursors:?}, pattern: {pattern}, count: {count}");
+    pub async fn scan(
+        &self,
+        cursors: Vec<u64>,
+        pattern: &str,
+        count: u64,
+        key_type: Option<&str>,
+    ) -> Result<(Vec<u64>, Vec<SharedString>)> {
+        debug!("scan, cursors: {cursors:?}, pattern: {pattern}, count: {count}, type: {key_type:?}");
         let cmds: Vec<Cmd> = cursors
             .iter()
             .map(|cursor| {
-                cmd("SCAN")
+                let mut scan_cmd = cmd("SCAN");
+                scan_cmd
                     .cursor_arg(*cursor)
                     .arg("MATCH")
-                    .arg(pattern)
+                    .arg(unescape_glob_pattern(pattern))
                     .arg("COUNT")
-                    .arg(count)
-                    .clone()
+                    .arg(count);
+                if let Some(key_type) = key_type {
+                    scan_cmd.arg("TYPE").arg(key_type);
+                }
+                scan_cmd
             })
             .collect();
-        let values: Vec<(u64, Vec<Vec<u8>>)> = self.query_async_masters(cmds).await?;
+        let (nodes, pool) = self.scan_nodes();
+        let addrs: Vec<_> = nodes.iter().map(|item| item.server.clone()).collect();
+        let values: Vec<(u64, Vec<Vec<u8>>)> = query_async_masters(pool, addrs, cmds).await?;
         let mut cursors = Vec::with_capacity(values.len());
         let mut keys = Vec::with_capacity(values[0].1.len() * values.len());
         for (cursor, keys_in_node) in values {
             cursors.push(cursor);
-            keys.extend(
-                keys_in_node
-                    .iter()
-                    .map(|k| String::from_utf8_lossy(k).to_string().into()),
-            );
+            keys.extend(keys_in_node.iter().map(|k| escape_key(k).into()));
         }
         keys.sort_unstable();
         Ok((cursors, keys))
@@ -305,6 +606,10 @@ impl RedisClient {
 
 pub struct ConnectionManager {
     clients: DashMap<String, RedisClient>,
+    /// Sentinels of the sentinel servers, kept when the clients are removed
+    sentinels: DashMap<String, SentinelTopology>,
+    /// Address of the replica browsed for a server
+    browsed_replicas: DashMap<String, String>,
 }
 
 /// Detects the type of Redis server (Sentinel, Cluster, or Standalone).
@@ -339,20 +644,52 @@ impl ConnectionManager {
     pub fn new() -> Self {
         Self {
             clients: DashMap::new(),
+            sentinels: DashMap::new(),
+            browsed_replicas: DashMap::new(),
         }
     }
     /// Discovers Redis nodes and server type based on initial configuration.
     async fn get_redis_nodes(&self, name: &str) -> Result<(Vec<RedisNode>, ServerType)> {
         let config = get_config(name)?;
-        let mut client = open_client(&config)?;
+        // the master of a known sentinel server is resolved by any of its sentinels,
+        // as the configured sentinel may be down
+        let topology = self.sentinels.get(name).map(|item| item.clone());
+        if let Some(topology) = topology
+            && topology.sentinels.first() == Some(&(config.host.clone(), config.port))
+            && config
+                .master_name
+                .as_ref()
+                .is_none_or(|name| *name == topology.master_name)
+        {
+            return self
+                .resolve_sentinel_nodes(
+                    name,
+                    &config,
+                    &topology.config,
+                    Some(topology.master_name),
+                    &topology.sentinels,
+                )
+                .await;
+        }
+        self.sentinels.remove(name);
+        // the configured address of a server with sentinel settings is a sentinel,
+        // it is detected with the sentinel credentials
+        let has_sentinel_settings = config.has_sentinel_settings();
+        let mut sentinel_config = if has_sentinel_settings {
+            config.sentinel_config()
+        } else {
+            config.clone()
+        };
+        let mut client = open_client(&sentinel_config)?;
         // Attempt to connect and detect server type
         // Handles logic to retry without password if authentication fails
         let server_type = match detect_server_type(&client).await {
             Ok(server_type) => server_type,
             Err(e) => {
-                // Retry without password if auth failed and config might allow empty password
-                // or simply to handle sentinel cases which often have no auth
-                if config.password.is_none() || !e.to_string().contains("AuthenticationFailed") {
+                // Without sentinel settings, retry without password if auth failed and config might
+                // allow empty password, or simply to handle sentinel cases which often have no auth
+                if has_sentinel_settings || config.password.is_none() || !e.to_string().contains("AuthenticationFailed")
+                {
                     error!("detect server type failed: {e:?}, use standalone mode");
                     return Ok((
                         vec![RedisNode {
@@ -363,15 +700,18 @@ impl ConnectionManager {
                         ServerType::Standalone,
                     ));
                 }
-                let mut tmp_config = config.clone();
-                tmp_config.password = None;
-                client = open_client(&tmp_config)?;
+                sentinel_config.password = None;
+                client = open_client(&sentinel_config)?;
                 detect_server_type(&client).await.unwrap_or_else(|e| {
                     error!("detect server type failed: {e:?}, use standalone mode");
                     ServerType::Standalone
                 })
             }
         };
+        // not a sentinel, the data node credentials are used
+        if has_sentinel_settings && server_type != ServerType::Sentinel {
+            client = open_client(&config)?;
+        }
         match server_type {
             ServerType::Cluster => {
                 let mut conn = client.get_multiplexed_async_connection().await?;
@@ -388,60 +728,16 @@ impl ConnectionManager {
                         RedisNode {
                             server: tmp_config,
                             role: item.role.clone(),
-                            ..Default::default()
+                            id: item.id.clone(),
+                            master_id: item.master_id.clone(),
                         }
                     })
                     .collect();
                 Ok((nodes, server_type))
             }
             ServerType::Sentinel => {
-                let mut conn = client.get_multiplexed_async_connection().await?;
-                // Fetch masters from Sentinel
-                let masters_response: Vec<HashMap<String, String>> =
-                    cmd("SENTINEL").arg("MASTERS").query_async(&mut conn).await?;
-                let mut nodes = vec![];
-
-                for item in masters_response {
-                    let ip = item.get("ip").ok_or_else(|| Error::Invalid {
-                        message: "ip is not found".to_string(),
-                    })?;
-                    let port: u16 = item
-                        .get("port")
-                        .ok_or_else(|| Error::Invalid {
-                            message: "port is not found".to_string(),
-                        })?
-                        .parse()
-                        .map_err(|e| Error::Invalid {
-                            message: format!("Invalid port {e:?}"),
-                        })?;
-                    let name = item.get("name").ok_or_else(|| Error::Invalid {
-                        message: "master_name is not found".to_string(),
-                    })?;
-                    // Filter by master name if configured
-                    if let Some(master_name) = &config.master_name
-                        && name != master_name
-                    {
-                        continue;
-                    }
-                    let mut tmp_config = config.clone();
-                    tmp_config.host = ip.clone();
-                    tmp_config.port = port;
-
-                    nodes.push(RedisNode {
-                        server: tmp_config,
-                        role: NodeRole::Master,
-                        master_name: Some(name.clone()),
-                    });
-                }
-                // Check for ambiguous master configuration
-                let unique_masters: HashSet<_> = nodes.iter().filter_map(|n| n.master_name.as_ref()).collect();
-                if unique_masters.len() > 1 {
-                    return Err(Error::Invalid {
-                        message: "Multiple masters found in Sentinel, please specify master_name".into(),
-                    });
-                }
-
-                Ok((nodes, server_type))
+                self.resolve_sentinel_nodes(name, &config, &sentinel_config, config.master_name.clone(), &[])
+                    .await
             }
             _ => Ok((
                 vec![RedisNode {
@@ -453,8 +749,60 @@ impl ConnectionManager {
             )),
         }
     }
+    /// Resolves the master and the replicas of a sentinel server, and keeps its sentinels.
+    async fn resolve_sentinel_nodes(
+        &self,
+        name: &str,
+        config: &RedisServer,
+        sentinel_config: &RedisServer,
+        master_name: Option<String>,
+        known_sentinels: &[(String, u16)],
+    ) -> Result<(Vec<RedisNode>, ServerType)> {
+        let (resolved, topology) = resolve_master(sentinel_config, master_name, known_sentinels).await?;
+        let node = |(host, port): (String, u16), role: NodeRole| {
+            let mut server = config.clone();
+            server.host = host;
+            server.port = port;
+            RedisNode {
+                server,
+                role,
+                ..Default::default()
+            }
+        };
+        let mut nodes = vec![node(resolved.master, NodeRole::Master)];
+        nodes.extend(resolved.replicas.into_iter().map(|addr| node(addr, NodeRole::Slave)));
+        self.sentinels.insert(name.to_string(), topology);
+        Ok((nodes, ServerType::Sentinel))
+    }
+    /// Watches the failovers of a sentinel server, see `watch_switch_master`.
+    /// Returns at once if the server has no known sentinels.
+    pub async fn watch_sentinel(&self, server_id: &str, stop: Arc<AtomicBool>, tx: UnboundedSender<(String, u16)>) {
+        let Some(topology) = self.sentinels.get(server_id).map(|item| item.clone()) else {
+            return;
+        };
+        watch_switch_master(topology, stop, tx).await;
+    }
+    /// Browses a replica of a sentinel server instead of its master, or the master again with `None`.
+    /// The clients of the server are removed, the next one connects to the replica.
+    pub fn set_browsed_replica(&self, server_id: &str, addr: Option<String>) {
+        if let Some(addr) = addr {
+            self.browsed_replicas.insert(server_id.to_string(), addr);
+        } else {
+            self.browsed_replicas.remove(server_id);
+        }
+        self.remove_client(server_id);
+    }
+    /// Forgets everything known about the server (clients, sentinels, browsed replica),
+    /// after its config was changed or removed.
+    pub fn remove_server(&self, server_id: &str) {
+        self.sentinels.remove(server_id);
+        self.browsed_replicas.remove(server_id);
+        self.remove_client(server_id);
+    }
+    /// Removes the cached clients of every db of the server.
     pub fn remove_client(&self, name: &str) {
-        self.clients.remove(name);
+        self.clients
+            .retain(|key, _| key.rsplit_once(':').is_none_or(|(server_id, _)| server_id != name));
     }
     /// Retrieves or creates a RedisClient for the given configuration name.
     pub async fn get_client(&self, server_id: &str, db: usize) -> Result<RedisClient> {
@@ -463,21 +811,22 @@ impl ConnectionManager {
             return Ok(client.clone());
         }
         let (nodes, server_type) = self.get_redis_nodes(server_id).await?;
+        // the browsed replica may be gone after a failover, the master is used then
+        let browsed_addr = self.browsed_replicas.get(server_id).map(|addr| addr.clone());
+        let browsed_replica = browsed_addr.and_then(|addr| {
+            nodes
+                .iter()
+                .find(|node| node.role == NodeRole::Slave && node.host_port() == addr)
+                .cloned()
+        });
+        if browsed_replica.is_none() {
+            self.browsed_replicas.remove(server_id);
+        }
         let client = match server_type {
-            ServerType::Cluster => {
-                let addrs: Vec<String> = nodes.iter().map(|n| n.server.get_connection_url()).collect();
-                let mut builder = cluster::ClusterClientBuilder::new(addrs);
-                let node = &nodes[0];
-                if let Some(certificates) = node.server.tls_certificates() {
-                    builder = builder.certs(certificates);
-                }
-                if node.server.insecure.unwrap_or(false) {
-                    builder = builder.danger_accept_invalid_hostnames(true);
-                }
-                RClient::Cluster(builder.build()?)
-            }
+            ServerType::Cluster => RClient::Cluster(build_cluster_client(&nodes, false)?),
             _ => {
-                let client = open_client(&nodes[0].server.clone())?;
+                let node = browsed_replica.as_ref().unwrap_or(&nodes[0]);
+                let client = open_client(&node.server)?;
                 RClient::Single(client)
             }
         };
@@ -489,14 +838,47 @@ impl ConnectionManager {
         let master_nodes_description: Vec<String> = master_nodes.iter().map(|node| node.host_port()).collect();
         info!(master_nodes = ?master_nodes_description, "server master nodes");
         let connection = get_async_connection(&client, db).await?;
+        let master_pool = NodeConnectionPool::new(db);
+        // the only master of a standalone or sentinel server is the one of the main connection
+        if let RedisAsyncConn::Single(conn) = &connection
+            && browsed_replica.is_none()
+            && let [master] = master_nodes.as_slice()
+        {
+            master_pool.insert(&master.server, conn.clone());
+        }
+        // the replicas only serve reads, the masters are used if they can't be opened
+        let prefers_replica = nodes.first().is_some_and(|node| node.server.prefers_replica());
+        let mut read_replica_error = None;
+        let read_replicas = if prefers_replica && browsed_replica.is_none() {
+            open_read_replicas(&server_type, &nodes, &master_nodes, &connection, db)
+                .await
+                .unwrap_or_else(|e| {
+                    error!(error = %e, "open read replicas failed, read from the masters");
+                    read_replica_error = Some(e.to_string());
+                    ReadReplicas::default()
+                })
+        } else {
+            ReadReplicas::default()
+        };
+        if !read_replicas.nodes.is_empty() {
+            let read_nodes_description: Vec<String> = read_replicas.nodes.iter().map(|node| node.host_port()).collect();
+            info!(read_nodes = ?read_nodes_description, "server read nodes");
+        }
 
         let mut client = RedisClient {
-            db,
             server_type: server_type.clone(),
             nodes,
             master_nodes,
             version: Version::new(0, 0, 0),
+            client,
+            db,
             connection,
+            master_pool: Arc::new(master_pool),
+            browsed_replica,
+            read_replicas: read_replicas.nodes,
+            replica_pool: Arc::new(read_replicas.pool.unwrap_or_else(|| NodeConnectionPool::new(db))),
+            read_connection: read_replicas.connection,
+            read_replica_error,
         };
         let mut conn = client.connection.clone();
         client.version = match server_type {
@@ -526,11 +908,27 @@ impl ConnectionManager {
         self.clients.insert(key, client.clone());
         Ok(client)
     }
+    /// Drops the cached clients of the server and creates a new one,
+    /// discovering the nodes again (new masters after a failover or resharding).
+    pub async fn reconnect(&self, server_id: &str, db: usize) -> Result<RedisClient> {
+        self.remove_client(server_id);
+        self.get_client(server_id, db).await
+    }
     /// Shorthand to get an async connection directly.
     pub async fn get_connection(&self, server_id: &str, db: usize) -> Result<RedisAsyncConn> {
         let client = self.get_client(server_id, db).await?;
         Ok(client.connection.clone())
     }
+    /// Retrieves the connection for reads, see `RedisClient::read_connection`.
+    pub async fn get_read_connection(&self, server_id: &str, db: usize) -> Result<RedisAsyncConn> {
+        let client = self.get_client(server_id, db).await?;
+        Ok(client.read_connection())
+    }
+    /// Retrieves the connection the value of a key is read from, see `RedisClient::value_connection`.
+    pub async fn get_value_connection(&self, server_id: &str, db: usize, written: bool) -> Result<RedisAsyncConn> {
+        let client = self.get_client(server_id, db).await?;
+        Ok(client.value_connection(written))
+    }
 }
 
 /// Global accessor for the connection manager.
@@ -0,0 +1,299 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sentinel discovery and failover following.
+//!
+//! - The master is resolved with SENTINEL GET-MASTER-ADDR-BY-NAME, asking each known
+//!   sentinel in turn, so the server stays reachable when the configured sentinel is down
+//! - The other sentinels (SENTINEL SENTINELS) and the replicas (SENTINEL REPLICAS) are
+//!   listed at the same time, the sentinels are kept across reconnections
+//! - `+switch-master` events of the sentinels are watched, a failover then re-resolves the master
+
+use super::{
+    async_connection::{CONNECTION_TIMEOUT, RESPONSE_TIMEOUT, open_client},
+    config::RedisServer,
+};
+use crate::error::Error;
+use futures::{StreamExt, channel::mpsc::UnboundedSender};
+use redis::{AsyncConnectionConfig, aio::MultiplexedConnection, cmd};
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+use tracing::{debug, info};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Pub/Sub channel of the sentinels announcing a new master
+const SWITCH_MASTER_CHANNEL: &str = "+switch-master";
+/// Interval of the checks of the watch (stopped, reconnect to the sentinels)
+const WATCH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Sentinels monitoring the master of a server
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SentinelTopology {
+    /// Name of the master monitored by the sentinels
+    pub master_name: String,
+    /// Connection config of the sentinels, only the address differs between them
+    pub config: RedisServer,
+    /// Addresses of the sentinels, the configured one first
+    pub sentinels: Vec<(String, u16)>,
+}
+
+/// Master and replicas resolved by a sentinel
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SentinelMaster {
+    pub master: (String, u16),
+    /// Replicas which are not down or disconnected
+    pub replicas: Vec<(String, u16)>,
+}
+
+fn parse_addr(item: &HashMap<String, String>) -> Result<(String, u16)> {
+    let ip = item.get("ip").ok_or_else(|| Error::Invalid {
+        message: "ip is not found".to_string(),
+    })?;
+    let port = item
+        .get("port")
+        .ok_or_else(|| Error::Invalid {
+            message: "port is not found".to_string(),
+        })?
+        .parse()
+        .map_err(|e| Error::Invalid {
+            message: format!("Invalid port {e:?}"),
+        })?;
+    Ok((ip.clone(), port))
+}
+
+/// Whether the flags of a replica (SENTINEL REPLICAS) mark it as unusable
+fn is_replica_down(item: &HashMap<String, String>) -> bool {
+    item.get("flags").is_some_and(|flags| {
+        flags
+            .split(',')
+            .any(|flag| matches!(flag, "s_down" | "o_down" | "disconnected"))
+    })
+}
+
+fn with_addr(config: &RedisServer, (host, port): &(String, u16)) -> RedisServer {
+    let mut config = config.clone();
+    config.host = host.clone();
+    config.port = *port;
+    config
+}
+
+async fn connect(config: &RedisServer) -> Result<MultiplexedConnection> {
+    let client = open_client(config)?;
+    let cfg = AsyncConnectionConfig::default()
+        .set_connection_timeout(Some(CONNECTION_TIMEOUT))
+        .set_response_timeout(Some(RESPONSE_TIMEOUT));
+    Ok(client.get_multiplexed_async_connection_with_config(&cfg).await?)
+}
+
+/// Ask a sentinel for the master, by name or the only monitored master if no name is configured.
+/// Returns the name of the master, the master and its replicas, and the other sentinels.
+async fn query_sentinel(
+    config: &RedisServer,
+    master_name: Option<&str>,
+) -> Result<(String, SentinelMaster, Vec<(String, u16)>)> {
+    let mut conn = connect(config).await?;
+    let master_name = if let Some(master_name) = master_name {
+        master_name.to_string()
+    } else {
+        let masters: Vec<HashMap<String, String>> = cmd("SENTINEL").arg("MASTERS").query_async(&mut conn).await?;
+        let mut names = masters.iter().filter_map(|item| item.get("name"));
+        match (names.next(), names.next()) {
+            (Some(name), None) => name.clone(),
+            (None, _) => {
+                return Err(Error::Invalid {
+                    message: "No master is monitored by the sentinel".into(),
+                });
+            }
+            _ => {
+                return Err(Error::Invalid {
+                    message: "Multiple masters found in Sentinel, please specify master_name".into(),
+                });
+            }
+        }
+    };
+    let addr: Option<(String, String)> = cmd("SENTINEL")
+        .arg("GET-MASTER-ADDR-BY-NAME")
+        .arg(&master_name)
+        .query_async(&mut conn)
+        .await?;
+    let Some((host, port)) = addr else {
+        return Err(Error::Invalid {
+            message: format!("Master {master_name} is not monitored by the sentinel"),
+        });
+    };
+    let port = port.parse().map_err(|e| Error::Invalid {
+        message: format!("Invalid port {e:?}"),
+    })?;
+
+    // SENTINEL REPLICAS was added in Redis 5.0, SENTINEL SLAVES before
+    let replicas: Vec<HashMap<String, String>> = match cmd("SENTINEL")
+        .arg("REPLICAS")
+        .arg(&master_name)
+        .query_async(&mut conn)
+        .await
+    {
+        Ok(replicas) => replicas,
+        Err(_) => {
+            cmd("SENTINEL")
+                .arg("SLAVES")
+                .arg(&master_name)
+                .query_async(&mut conn)
+                .await?
+        }
+    };
+    let replicas = replicas
+        .iter()
+        .filter(|item| !is_replica_down(item))
+        .map(parse_addr)
+        .collect::<Result<Vec<_>>>()?;
+    let sentinels: Vec<HashMap<String, String>> = cmd("SENTINEL")
+        .arg("SENTINELS")
+        .arg(&master_name)
+        .query_async(&mut conn)
+        .await?;
+    let sentinels = sentinels.iter().map(parse_addr).collect::<Result<Vec<_>>>()?;
+
+    Ok((
+        master_name,
+        SentinelMaster {
+            master: (host, port),
+            replicas,
+        },
+        sentinels,
+    ))
+}
+
+/// Resolve the master, asking the configured sentinel first, then the known ones until one answers.
+///
+/// # Arguments
+/// * `config` - Connection config of the configured sentinel.
+/// * `master_name` - Name of the master, the only monitored master is used if `None`.
+/// * `known_sentinels` - Sentinels found by a previous resolution.
+pub(crate) async fn resolve_master(
+    config: &RedisServer,
+    master_name: Option<String>,
+    known_sentinels: &[(String, u16)],
+) -> Result<(SentinelMaster, SentinelTopology)> {
+    let mut candidates = vec![(config.host.clone(), config.port)];
+    for addr in known_sentinels {
+        if !candidates.contains(addr) {
+            candidates.push(addr.clone());
+        }
+    }
+    let mut last_error = None;
+    for addr in candidates.iter() {
+        match query_sentinel(&with_addr(config, addr), master_name.as_deref()).await {
+            Ok((master_name, master, sentinels)) => {
+                info!(sentinel = ?addr, master_name, master = ?master.master, "sentinel resolved master");
+                for addr in sentinels {
+                    if !candidates.contains(&addr) {
+                        candidates.push(addr);
+                    }
+                }
+                let topology = SentinelTopology {
+                    master_name,
+                    config: config.clone(),
+                    sentinels: candidates,
+                };
+                return Ok((master, topology));
+            }
+            Err(e) => {
+                debug!(sentinel = ?addr, error = %e, "sentinel failed to resolve master");
+                last_error = Some(e);
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| Error::Invalid {
+        message: "No sentinel is available".into(),
+    }))
+}
+
+/// Watch the `+switch-master` events of the sentinels, sending the new master address
+/// of the monitored master. A lost sentinel connection moves on to the next sentinel.
+/// Runs until `stop` is set or the receiver is dropped.
+pub(crate) async fn watch_switch_master(
+    topology: SentinelTopology,
+    stop: Arc<AtomicBool>,
+    tx: UnboundedSender<(String, u16)>,
+) {
+    let stopped = || stop.load(Ordering::Relaxed) || tx.is_closed();
+    loop {
+        for addr in topology.sentinels.iter() {
+            if stopped() {
+                return;
+            }
+            let subscribe = async {
+                let client = open_client(&with_addr(&topology.config, addr))?;
+                let mut pubsub = client.get_async_pubsub().await?;
+                pubsub.subscribe(SWITCH_MASTER_CHANNEL).await?;
+                Ok::<_, Error>(pubsub)
+            };
+            let timeout = async {
+                smol::Timer::after(CONNECTION_TIMEOUT).await;
+                Err(Error::Invalid {
+                    message: "Subscribe to the sentinel timed out".into(),
+                })
+            };
+            let pubsub = match smol::future::or(subscribe, timeout).await {
+                Ok(pubsub) => pubsub,
+                Err(e) => {
+                    debug!(sentinel = ?addr, error = %e, "subscribe to sentinel failed");
+                    continue;
+                }
+            };
+            debug!(sentinel = ?addr, "watching sentinel for failovers");
+            let mut messages = pubsub.into_on_message();
+            loop {
+                let next = async { Some(messages.next().await) };
+                let check = async {
+                    smol::Timer::after(WATCH_CHECK_INTERVAL).await;
+                    None
+                };
+                match smol::future::or(next, check).await {
+                    None if stopped() => return,
+                    None => continue,
+                    // the connection to the sentinel is lost
+                    Some(None) => break,
+                    Some(Some(msg)) => {
+                        let Ok(payload) = msg.get_payload::<String>() else {
+                            continue;
+                        };
+                        // <master name> <old ip> <old port> <new ip> <new port>
+                        let parts: Vec<&str> = payload.split_whitespace().collect();
+                        if let [name, _, _, ip, port] = parts.as_slice()
+                            && *name == topology.master_name
+                            && let Ok(port) = port.parse()
+                        {
+                            info!(master_name = name, ip, port, "sentinel switched master");
+                            if tx.unbounded_send((ip.to_string(), port)).is_err() {
+                                return;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        if stopped() {
+            return;
+        }
+        smol::Timer::after(WATCH_CHECK_INTERVAL).await;
+    }
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use redis::{ErrorKind, ServerErrorKind};
 use snafu::Snafu;
 
 #[derive(Debug, Snafu)]
@@ -30,6 +31,38 @@ pub enum Error {
     TomlSe { source: toml::ser::Error },
 }
 
+impl Error {
+    /// Whether the connection to the server is broken (closed, refused or timed out),
+    /// the cached client has to be reconnected.
+    pub fn is_connection_error(&self) -> bool {
+        let Error::Redis { source } = self else {
+            return false;
+        };
+        source.is_io_error() || source.is_connection_dropped() || source.is_connection_refusal() || source.is_timeout()
+    }
+    /// Whether the nodes of the server have changed (MOVED redirect, failover),
+    /// the topology has to be discovered again.
+    pub fn is_topology_error(&self) -> bool {
+        let Error::Redis { source } = self else {
+            return false;
+        };
+        matches!(
+            source.kind(),
+            ErrorKind::ClusterConnectionNotFound
+                | ErrorKind::Server(
+                    ServerErrorKind::Moved
+                        | ServerErrorKind::ClusterDown
+                        | ServerErrorKind::MasterDown
+                        | ServerErrorKind::ReadOnly
+                )
+        )
+    }
+    /// Whether a write was rejected by a replica (READONLY).
+    pub fn is_readonly_error(&self) -> bool {
+        matches!(self, Error::Redis { source } if source.kind() == ErrorKind::Server(ServerErrorKind::ReadOnly))
+    }
+}
+
 impl From<redis::RedisError> for Error {
     fn from(source: redis::RedisError) -> Self {
         Error::Redis { source }
@@ -17,7 +17,10 @@ use std::env;
 mod action;
 mod common;
 mod font;
+mod formatter;
 mod fs;
+mod key_tree;
+mod protobuf;
 mod string;
 mod time;
 mod validate;
@@ -25,8 +28,13 @@ mod validate;
 pub use action::*;
 pub use common::*;
 pub use font::get_font_family;
+pub use formatter::run_formatter_command;
+pub use fs::get_default_file_dir;
 pub use fs::get_or_create_config_dir;
 pub use fs::is_app_store_build;
+pub use fs::sanitize_file_name;
+pub use key_tree::KeyTree;
+pub use protobuf::*;
 pub use string::*;
 pub use time::unix_ts;
 pub use validate::*;
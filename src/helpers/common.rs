@@ -17,7 +17,7 @@ use crate::constants::KEY_TREE_MIN_WIDTH;
 use crate::error::Error;
 use gpui::{Pixels, px};
 use ruzstd::decoding::StreamingDecoder;
-use std::io::Read;
+use std::io::{Read, Write};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -35,3 +35,73 @@ pub fn decompress_zstd(bytes: &[u8]) -> Result<Vec<u8>> {
         .map_err(|e| Error::Invalid { message: e.to_string() })?;
     Ok(decompressed_vec)
 }
+
+fn invalid(e: impl ToString) -> Error {
+    Error::Invalid { message: e.to_string() }
+}
+
+fn read_all(mut reader: impl Read, capacity: usize) -> Result<Vec<u8>> {
+    let mut decompressed_vec = Vec::with_capacity(capacity);
+    reader.read_to_end(&mut decompressed_vec)?;
+    Ok(decompressed_vec)
+}
+
+pub fn decompress_lz4(bytes: &[u8]) -> Result<Vec<u8>> {
+    read_all(lz4_flex::frame::FrameDecoder::new(bytes), bytes.len() * 2)
+}
+
+pub fn compress_lz4(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::with_capacity(bytes.len()));
+    encoder.write_all(bytes)?;
+    encoder.finish().map_err(invalid)
+}
+
+/// Decompress snappy framing format data (stream identifier `sNaPpY`)
+pub fn decompress_snappy(bytes: &[u8]) -> Result<Vec<u8>> {
+    read_all(snap::read::FrameDecoder::new(bytes), bytes.len() * 2)
+}
+
+pub fn compress_snappy(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = snap::write::FrameEncoder::new(Vec::with_capacity(bytes.len()));
+    encoder.write_all(bytes)?;
+    encoder.into_inner().map_err(invalid)
+}
+
+/// Decompress a raw snappy block (no framing, used by most Go and Java clients).
+///
+/// The block has no magic number, its header (decompressed length) is checked
+/// against `max_size` before anything is allocated.
+pub fn decompress_snappy_raw(bytes: &[u8], max_size: usize) -> Result<Vec<u8>> {
+    let size = snap::raw::decompress_len(bytes).map_err(invalid)?;
+    if size > max_size {
+        return Err(invalid(format!("snappy decompressed size {size} is too large")));
+    }
+    snap::raw::Decoder::new().decompress_vec(bytes).map_err(invalid)
+}
+
+pub fn compress_snappy_raw(bytes: &[u8]) -> Result<Vec<u8>> {
+    snap::raw::Encoder::new().compress_vec(bytes).map_err(invalid)
+}
+
+/// Decompress brotli data, failing if it is larger than `max_size`.
+pub fn decompress_brotli(bytes: &[u8], max_size: usize) -> Result<Vec<u8>> {
+    let limit = max_size.saturating_add(1) as u64;
+    let decompressed = read_all(
+        brotli::Decompressor::new(bytes, 4096).take(limit),
+        bytes.len().saturating_mul(4).min(max_size),
+    )?;
+    if decompressed.len() > max_size {
+        return Err(invalid(format!("brotli decompressed size is larger than {max_size}")));
+    }
+    Ok(decompressed)
+}
+
+pub fn compress_brotli(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut compressed = Vec::with_capacity(bytes.len());
+    {
+        // quality 9 and window 22 are the brotli defaults for text
+        let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, 9, 22);
+        writer.write_all(bytes)?;
+    }
+    Ok(compressed)
+}
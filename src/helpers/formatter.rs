@@ -0,0 +1,127 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! External command formatters.
+//!
+//! The raw value bytes are written to the stdin of a shell command,
+//! and its stdout is used as the result (decoded text or encoded bytes).
+
+use super::is_windows;
+use crate::error::Error;
+use std::{
+    io::{Read, Write},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The command is killed if it has not exited after this duration
+const FORMATTER_TIMEOUT: Duration = Duration::from_secs(10);
+/// Interval of checking if the command has exited
+const WAIT_INTERVAL: Duration = Duration::from_millis(20);
+
+fn shell_command(command: &str) -> Command {
+    if is_windows() {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+}
+
+/// Pipe the input through an external command and return its stdout.
+///
+/// The command is run by the system shell, so pipes and arguments can be used.
+/// A non-zero exit status is returned as an error with the stderr output,
+/// and a command still running after FORMATTER_TIMEOUT is killed.
+pub fn run_formatter_command(command: &str, input: &[u8]) -> Result<Vec<u8>> {
+    let mut child = shell_command(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Write stdin from another thread, otherwise a command producing
+    // a large output before reading all input would dead lock.
+    let writer = child.stdin.take().map(|mut stdin| {
+        let input = input.to_vec();
+        thread::spawn(move || stdin.write_all(&input))
+    });
+    let read_all = |mut reader: Box<dyn Read + Send>| {
+        thread::spawn(move || {
+            let mut buf = vec![];
+            reader.read_to_end(&mut buf).map(|_| buf)
+        })
+    };
+    let stdout = child.stdout.take().map(|stdout| read_all(Box::new(stdout)));
+    let stderr = child.stderr.take().map(|stderr| read_all(Box::new(stderr)));
+
+    let started_at = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started_at.elapsed() >= FORMATTER_TIMEOUT {
+            // the output readers are left to end with the pipes
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(Error::Invalid {
+                message: format!("{command} did not exit within {}s", FORMATTER_TIMEOUT.as_secs()),
+            });
+        }
+        thread::sleep(WAIT_INTERVAL);
+    };
+    let join_output = |reader: Option<thread::JoinHandle<std::io::Result<Vec<u8>>>>| -> Result<Vec<u8>> {
+        let Some(reader) = reader else {
+            return Ok(vec![]);
+        };
+        let output = reader.join().map_err(|_| Error::Invalid {
+            message: "read formatter output failed".to_string(),
+        })??;
+        Ok(output)
+    };
+    let stdout = join_output(stdout)?;
+    let stderr = join_output(stderr)?;
+    if let Some(writer) = writer {
+        writer
+            .join()
+            .map_err(|_| Error::Invalid {
+                message: "write formatter stdin failed".to_string(),
+            })?
+            .or_else(|e| {
+                // the command may exit without reading all input
+                if e.kind() == std::io::ErrorKind::BrokenPipe {
+                    Ok(())
+                } else {
+                    Err(e)
+                }
+            })?;
+    }
+
+    if !status.success() {
+        return Err(Error::Invalid {
+            message: format!(
+                "{command} exited with {}: {}",
+                status,
+                String::from_utf8_lossy(&stderr).trim()
+            ),
+        });
+    }
+    Ok(stdout)
+}
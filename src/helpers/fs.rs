@@ -18,9 +18,10 @@
 //! - Directory copying operations
 //! - App Store build detection (for macOS sandboxing)
 //! - Configuration directory management with migration support
+//! - Default directory and file names for exported files
 
 use crate::error::Error;
-use directories::ProjectDirs;
+use directories::{ProjectDirs, UserDirs};
 use home::home_dir;
 use std::{
     env, fs,
@@ -153,3 +154,22 @@ pub fn get_or_create_config_dir() -> Result<PathBuf> {
 
     Ok(config_dir.to_path_buf())
 }
+
+/// Default directory of file dialogs: the download directory, or the home directory.
+pub fn get_default_file_dir() -> PathBuf {
+    UserDirs::new()
+        .and_then(|dirs| dirs.download_dir().map(Path::to_path_buf))
+        .or_else(home_dir)
+        .unwrap_or_else(env::temp_dir)
+}
+
+/// Replace the characters that are not allowed in file names (e.g. to name a file after a key).
+pub fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
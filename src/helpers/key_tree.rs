@@ -0,0 +1,385 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Incremental key tree model.
+//!
+//! Keys are grouped into folders by the key separator. To stay responsive with
+//! millions of keys:
+//! - Keys of a new scan page are merged into the existing folders, nothing is rebuilt
+//! - A folder is only split into sub folders the first time it is expanded,
+//!   until then its keys are kept as a flat list
+//! - Each folder keeps the count of its keys, so counts never walk the sub folders
+//! - A removed key is taken out of its folder, the folders left empty are removed
+//! - Rows are resolved on demand from the row count of each expanded folder,
+//!   only the rendered rows are materialized
+//!
+//! Keys are shared with the server state (`SharedString` clones), and labels
+//! are slices of the key, so the tree itself only adds the folder entries.
+
+use ahash::AHashMap;
+use gpui::SharedString;
+
+/// A row of the key tree, built when it is rendered.
+#[derive(Debug, Clone, Default)]
+pub struct KeyTreeRow {
+    /// The key, or the prefix of the folder (without the trailing separator)
+    pub id: SharedString,
+    /// The last part of the key or folder prefix
+    pub label: SharedString,
+    pub depth: usize,
+    pub is_folder: bool,
+    pub expanded: bool,
+    /// Number of keys in the folder and its sub folders
+    pub children_count: usize,
+}
+
+#[derive(Debug, Default)]
+struct KeyTreeFolder {
+    /// Prefix of the folder, empty for the root
+    id: SharedString,
+    /// Number of parts of the prefix, 0 for the root
+    level: usize,
+    /// Number of keys in the folder and its sub folders
+    count: usize,
+    /// Whether the keys have been split into sub folders
+    built: bool,
+    expanded: bool,
+    folders: Vec<KeyTreeFolder>,
+    /// Position of the sub folders by prefix
+    folder_index: AHashMap<SharedString, usize>,
+    /// Whether the sub folders are sorted (new ones are appended)
+    folders_sorted: bool,
+    /// Sorted keys directly in the folder
+    keys: Vec<SharedString>,
+    /// Keys added since the last prepare, all keys of the folder if it is not built
+    pending: Vec<SharedString>,
+    /// Start row of each sub folder in the content of the folder
+    folder_offsets: Vec<usize>,
+    /// Rows of the content of the folder (sub folders and keys), valid after prepare
+    rows: usize,
+}
+
+impl KeyTreeFolder {
+    fn new(id: SharedString, level: usize) -> Self {
+        Self {
+            id,
+            level,
+            folders_sorted: true,
+            ..Default::default()
+        }
+    }
+    /// Length of the prefix of the children, including the trailing separator
+    fn prefix_len(&self, separator: &str) -> usize {
+        if self.level == 0 {
+            0
+        } else {
+            self.id.len() + separator.len()
+        }
+    }
+    fn insert(&mut self, key: SharedString, separator: &str, max_depth: usize) {
+        self.count += 1;
+        if !self.built {
+            self.pending.push(key);
+            return;
+        }
+        self.dispatch(key, separator, max_depth);
+    }
+    /// Length of the prefix of the sub folder the key belongs to, `None` if it is a key of the folder
+    fn sub_folder_len(&self, key: &str, separator: &str, max_depth: usize) -> Option<usize> {
+        if separator.is_empty() || self.level + 1 >= max_depth {
+            return None;
+        }
+        let prefix_len = self.prefix_len(separator);
+        key.get(prefix_len..)
+            .and_then(|rest| rest.find(separator))
+            .map(|pos| prefix_len + pos)
+    }
+    /// Add the key to the sub folder it belongs to, or to the keys of the folder
+    fn dispatch(&mut self, key: SharedString, separator: &str, max_depth: usize) {
+        let Some(folder_len) = self.sub_folder_len(&key, separator, max_depth) else {
+            self.pending.push(key);
+            return;
+        };
+        let folder_id = &key[..folder_len];
+        let index = if let Some(index) = self.folder_index.get(folder_id) {
+            *index
+        } else {
+            let folder_id: SharedString = folder_id.to_string().into();
+            self.folders.push(KeyTreeFolder::new(folder_id.clone(), self.level + 1));
+            self.folder_index.insert(folder_id, self.folders.len() - 1);
+            self.folders_sorted = false;
+            self.folders.len() - 1
+        };
+        if let Some(folder) = self.folders.get_mut(index) {
+            folder.insert(key, separator, max_depth);
+        }
+    }
+    /// Remove the key from the folder or its sub folders, dropping the sub folders left empty.
+    /// Returns whether the key was found.
+    fn remove(&mut self, key: &str, separator: &str, max_depth: usize) -> bool {
+        let removed = if !self.built {
+            remove_unsorted(&mut self.pending, key)
+        } else if let Some(folder_len) = self.sub_folder_len(key, separator, max_depth) {
+            let Some(index) = self.folder_index.get(&key[..folder_len]).copied() else {
+                return false;
+            };
+            let removed = self.folders[index].remove(key, separator, max_depth);
+            if removed && self.folders[index].count == 0 {
+                let folder = self.folders.swap_remove(index);
+                self.folder_index.remove(&folder.id);
+                if let Some(moved) = self.folders.get(index) {
+                    self.folder_index.insert(moved.id.clone(), index);
+                    self.folders_sorted = false;
+                }
+            }
+            removed
+        } else if let Ok(index) = self.keys.binary_search_by(|existing| existing.as_str().cmp(key)) {
+            self.keys.remove(index);
+            true
+        } else {
+            remove_unsorted(&mut self.pending, key)
+        };
+        if removed {
+            self.count -= 1;
+        }
+        removed
+    }
+    /// Split the keys into sub folders
+    fn build(&mut self, separator: &str, max_depth: usize) {
+        if self.built {
+            return;
+        }
+        self.built = true;
+        for key in std::mem::take(&mut self.pending) {
+            self.dispatch(key, separator, max_depth);
+        }
+    }
+    fn folder_mut(&mut self, id: &str) -> Option<&mut KeyTreeFolder> {
+        let index = *self.folder_index.get(id)?;
+        self.folders.get_mut(index)
+    }
+    fn expand_all(&mut self, separator: &str, max_depth: usize) {
+        self.build(separator, max_depth);
+        self.expanded = true;
+        for folder in self.folders.iter_mut() {
+            folder.expand_all(separator, max_depth);
+        }
+    }
+    fn collapse_all(&mut self) {
+        self.expanded = false;
+        for folder in self.folders.iter_mut().filter(|folder| folder.built) {
+            folder.collapse_all();
+        }
+    }
+    /// Merge the pending keys and count the rows of the expanded folders
+    fn prepare(&mut self) {
+        if !self.folders_sorted {
+            self.folders.sort_unstable_by(|a, b| a.id.cmp(&b.id));
+            self.folder_index = self
+                .folders
+                .iter()
+                .enumerate()
+                .map(|(index, folder)| (folder.id.clone(), index))
+                .collect();
+            self.folders_sorted = true;
+        }
+        if !self.pending.is_empty() {
+            let mut pending = std::mem::take(&mut self.pending);
+            pending.sort_unstable();
+            self.keys = merge_sorted(std::mem::take(&mut self.keys), pending);
+        }
+        self.folder_offsets.clear();
+        self.folder_offsets.reserve(self.folders.len());
+        let mut rows = 0;
+        for folder in self.folders.iter_mut() {
+            self.folder_offsets.push(rows);
+            rows += 1;
+            if folder.expanded {
+                folder.prepare();
+                rows += folder.rows;
+            }
+        }
+        self.rows = rows + self.keys.len();
+    }
+    fn row(&self, index: usize, separator: &str) -> Option<KeyTreeRow> {
+        let prefix_len = self.prefix_len(separator);
+        let folder_rows = self.rows - self.keys.len();
+        if index >= folder_rows {
+            let key = self.keys.get(index - folder_rows)?;
+            return Some(KeyTreeRow {
+                id: key.clone(),
+                label: key.get(prefix_len..).unwrap_or_default().to_string().into(),
+                depth: self.level,
+                ..Default::default()
+            });
+        }
+        let position = self
+            .folder_offsets
+            .partition_point(|offset| *offset <= index)
+            .checked_sub(1)?;
+        let folder = self.folders.get(position)?;
+        let offset = index - self.folder_offsets.get(position)?;
+        if offset == 0 {
+            return Some(KeyTreeRow {
+                id: folder.id.clone(),
+                label: folder.id.get(prefix_len..).unwrap_or_default().to_string().into(),
+                depth: self.level,
+                is_folder: true,
+                expanded: folder.expanded,
+                children_count: folder.count,
+            });
+        }
+        folder.row(offset - 1, separator)
+    }
+}
+
+/// End of the prefix of each folder on the path to the folder `id`, the last one is `id` itself.
+fn folder_path_ends(id: &str, separator: &str) -> Vec<usize> {
+    let mut ends = vec![];
+    if separator.is_empty() {
+        return ends;
+    }
+    let mut start = 0;
+    while let Some(pos) = id.get(start..).and_then(|rest| rest.find(separator)) {
+        ends.push(start + pos);
+        start += pos + separator.len();
+    }
+    ends.push(id.len());
+    ends
+}
+
+/// Remove the key from an unsorted list of keys.
+fn remove_unsorted(keys: &mut Vec<SharedString>, key: &str) -> bool {
+    let Some(index) = keys.iter().position(|existing| existing == key) else {
+        return false;
+    };
+    keys.swap_remove(index);
+    true
+}
+
+/// Merge two sorted lists of keys.
+///
+/// The runs of existing keys between two pending keys are found by binary search
+/// and moved as a whole, so a page only costs a few comparisons per pending key.
+fn merge_sorted(keys: Vec<SharedString>, pending: Vec<SharedString>) -> Vec<SharedString> {
+    if keys.is_empty() {
+        return pending;
+    }
+    let mut merged = Vec::with_capacity(keys.len() + pending.len());
+    let mut keys = keys.into_iter();
+    for key in pending {
+        let count = keys.as_slice().partition_point(|existing| *existing <= key);
+        merged.extend(keys.by_ref().take(count));
+        merged.push(key);
+    }
+    merged.extend(keys);
+    merged
+}
+
+/// Key tree grouping keys into folders by the key separator.
+///
+/// Call `prepare` after adding keys or expanding folders, before reading rows.
+#[derive(Debug)]
+pub struct KeyTree {
+    separator: String,
+    /// Maximum number of parts of a key, the last part is never a folder
+    max_depth: usize,
+    root: KeyTreeFolder,
+}
+
+impl KeyTree {
+    pub fn new(separator: &str, max_depth: usize) -> Self {
+        let mut root = KeyTreeFolder::new(SharedString::default(), 0);
+        root.built = true;
+        root.expanded = true;
+        Self {
+            separator: separator.to_string(),
+            max_depth: max_depth.max(1),
+            root,
+        }
+    }
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+    pub fn is_empty(&self) -> bool {
+        self.root.count == 0
+    }
+    /// Number of visible rows, valid after `prepare`
+    pub fn rows(&self) -> usize {
+        self.root.rows
+    }
+    /// Add keys, they must not be in the tree yet
+    pub fn extend(&mut self, keys: impl IntoIterator<Item = SharedString>) {
+        for key in keys {
+            self.root.insert(key, &self.separator, self.max_depth);
+        }
+    }
+    /// Remove the key and the folders left empty, returns whether it was in the tree.
+    /// Call `prepare` before reading rows.
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.root.remove(key, &self.separator, self.max_depth)
+    }
+    /// Expand the folder, splitting it (and its parents) into sub folders if needed.
+    /// Parents are not expanded, the folder is shown once they are.
+    pub fn expand(&mut self, id: &str) {
+        let separator = self.separator.as_str();
+        let max_depth = self.max_depth;
+        let mut folder = &mut self.root;
+        for end in folder_path_ends(id, separator) {
+            folder.build(separator, max_depth);
+            let Some(next) = folder.folder_mut(&id[..end]) else {
+                return;
+            };
+            folder = next;
+        }
+        folder.build(separator, max_depth);
+        folder.expanded = true;
+    }
+    /// Collapse the folder, its sub folders keep their expanded state
+    pub fn collapse(&mut self, id: &str) {
+        let mut folder = &mut self.root;
+        for end in folder_path_ends(id, &self.separator) {
+            let Some(next) = folder.folder_mut(&id[..end]) else {
+                return;
+            };
+            folder = next;
+        }
+        if folder.level != 0 {
+            folder.expanded = false;
+        }
+    }
+    /// Expand every folder, only meant for small trees
+    pub fn expand_all(&mut self) {
+        self.root.expand_all(&self.separator, self.max_depth);
+    }
+    pub fn collapse_all(&mut self) {
+        self.root.collapse_all();
+        self.root.expanded = true;
+    }
+    /// Merge the keys added since the last call and count the visible rows.
+    /// Only the expanded folders are visited.
+    pub fn prepare(&mut self) {
+        self.root.prepare();
+    }
+    /// Get the visible row at the index
+    pub fn row(&self, index: usize) -> Option<KeyTreeRow> {
+        if index >= self.root.rows {
+            return None;
+        }
+        self.root.row(index, &self.separator)
+    }
+}
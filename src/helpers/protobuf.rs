@@ -0,0 +1,89 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Protobuf utilities.
+//!
+//! Messages are resolved from user supplied FileDescriptorSet files
+//! (e.g. `protoc --include_imports --descriptor_set_out=app.desc app.proto`),
+//! decoded to JSON for display and encoded back from JSON on save.
+
+use crate::error::Error;
+use ahash::AHashMap;
+use parking_lot::Mutex;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor, SerializeOptions, prost::Message};
+use std::{fs, sync::LazyLock, time::SystemTime};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Loaded descriptor pools, keyed by the descriptor files and their modification time,
+/// so an updated descriptor file is reloaded automatically.
+static DESCRIPTOR_POOLS: LazyLock<Mutex<AHashMap<String, DescriptorPool>>> =
+    LazyLock::new(|| Mutex::new(AHashMap::new()));
+
+fn invalid(message: impl ToString) -> Error {
+    Error::Invalid {
+        message: message.to_string(),
+    }
+}
+
+fn get_descriptor_pool(paths: &[String]) -> Result<DescriptorPool> {
+    let mut cache_key = String::new();
+    for path in paths {
+        let modified = fs::metadata(path)?
+            .modified()?
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        cache_key.push_str(&format!("{path}:{modified};"));
+    }
+    if let Some(pool) = DESCRIPTOR_POOLS.lock().get(&cache_key) {
+        return Ok(pool.clone());
+    }
+
+    let mut pool = DescriptorPool::new();
+    for path in paths {
+        let data = fs::read(path)?;
+        pool.decode_file_descriptor_set(data.as_slice())
+            .map_err(|e| invalid(format!("{path}: {e}")))?;
+    }
+    DESCRIPTOR_POOLS.lock().insert(cache_key, pool.clone());
+    Ok(pool)
+}
+
+/// Find a message descriptor by its full name (e.g. `package.User`)
+/// in the given FileDescriptorSet files.
+pub fn find_protobuf_message(paths: &[String], message_name: &str) -> Result<MessageDescriptor> {
+    let pool = get_descriptor_pool(paths)?;
+    pool.get_message_by_name(message_name.trim_start_matches('.'))
+        .ok_or_else(|| invalid(format!("protobuf message {message_name} not found")))
+}
+
+/// Decode protobuf bytes to pretty printed JSON.
+///
+/// Default values are kept, so every field of the message is visible for editing.
+pub fn decode_protobuf(desc: &MessageDescriptor, bytes: &[u8]) -> Result<String> {
+    let message = DynamicMessage::decode(desc.clone(), bytes).map_err(invalid)?;
+    let mut serializer = serde_json::Serializer::pretty(Vec::with_capacity(bytes.len() * 2));
+    let options = SerializeOptions::new().skip_default_fields(false);
+    message.serialize_with_options(&mut serializer, &options)?;
+    String::from_utf8(serializer.into_inner()).map_err(invalid)
+}
+
+/// Encode JSON text to protobuf bytes of the given message.
+pub fn encode_protobuf(desc: &MessageDescriptor, json: &str) -> Result<Vec<u8>> {
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let message = DynamicMessage::deserialize(desc.clone(), &mut deserializer)?;
+    deserializer.end()?;
+    Ok(message.encode_to_vec())
+}
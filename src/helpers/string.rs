@@ -25,6 +25,7 @@ use aes_gcm::{
     aead::{Aead, AeadCore, KeyInit, Nonce, OsRng},
 };
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use std::borrow::Cow;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -169,3 +170,224 @@ pub fn decrypt(cipher_text: &str) -> Result<String> {
     // Convert decrypted bytes to UTF-8 string
     String::from_utf8(plaintext_bytes).map_err(|e| Error::Invalid { message: e.to_string() })
 }
+
+/// Matches a value against a Redis style glob pattern.
+///
+/// Supports `*` (any sequence of characters) and `?` (any single character),
+/// which covers the patterns used for key based rules (e.g. `user:*:profile`).
+///
+/// # Examples
+/// ```
+/// assert!(glob_match("user:*", "user:1"));
+/// assert!(glob_match("cache:?:data", "cache:a:data"));
+/// assert!(!glob_match("user:*", "order:1"));
+/// ```
+pub fn glob_match(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+    let (mut p, mut v) = (0, 0);
+    // Position of the last `*` in pattern and the value position it matched up to
+    let mut star: Option<(usize, usize)> = None;
+
+    while v < value.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == value[v]) {
+            p += 1;
+            v += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, v));
+            p += 1;
+        } else if let Some((star_p, star_v)) = star {
+            // Backtrack: let the last `*` consume one more character
+            p = star_p + 1;
+            v = star_v + 1;
+            star = Some((star_p, star_v + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Escapes raw key bytes to the text form used to display and identify keys.
+///
+/// Valid UTF-8 is kept as is, while `\` is written as `\\`, `\n`, `\r`, `\t` as
+/// their escape sequences and other control characters or invalid UTF-8 bytes
+/// as `\xNN` (the same notation as redis-cli). The result maps back to the
+/// exact original bytes with [`unescape_key`].
+///
+/// # Examples
+/// ```
+/// assert_eq!(escape_key(b"user:1"), "user:1");
+/// assert_eq!(escape_key(b"id:\xff\x01"), "id:\\xff\\x01");
+/// ```
+pub fn escape_key(bytes: &[u8]) -> String {
+    // Fast path: plain UTF-8 keys are the vast majority
+    if let Ok(value) = std::str::from_utf8(bytes)
+        && !value.chars().any(|c| c == '\\' || c.is_control())
+    {
+        return value.to_string();
+    }
+    let mut escaped = String::with_capacity(bytes.len() + 8);
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '\\' => escaped.push_str("\\\\"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '\t' => escaped.push_str("\\t"),
+                c if c.is_control() && c.is_ascii() => escaped.push_str(&format!("\\x{:02x}", c as u8)),
+                c => escaped.push(c),
+            }
+        }
+        for byte in chunk.invalid() {
+            escaped.push_str(&format!("\\x{byte:02x}"));
+        }
+    }
+    escaped
+}
+
+/// Converts a key in its escaped text form (see [`escape_key`]) back to the raw bytes
+/// used in Redis commands.
+///
+/// Unknown escape sequences are kept literally.
+pub fn unescape_key(key: &str) -> Cow<'_, [u8]> {
+    if !key.contains('\\') {
+        return Cow::Borrowed(key.as_bytes());
+    }
+    let bytes = key.as_bytes();
+    let mut raw = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'\\' || i + 1 >= bytes.len() {
+            raw.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+        match bytes[i + 1] {
+            b'\\' => raw.push(b'\\'),
+            b'n' => raw.push(b'\n'),
+            b'r' => raw.push(b'\r'),
+            b't' => raw.push(b'\t'),
+            b'x' => {
+                let byte = key.get(i + 2..i + 4).and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                if let Some(byte) = byte {
+                    raw.push(byte);
+                    i += 4;
+                } else {
+                    raw.push(b'\\');
+                    i += 1;
+                }
+                continue;
+            }
+            _ => {
+                raw.push(b'\\');
+                i += 1;
+                continue;
+            }
+        }
+        i += 2;
+    }
+    Cow::Owned(raw)
+}
+
+/// Converts a SCAN MATCH pattern written with escaped keys (see [`escape_key`]) to raw bytes.
+///
+/// `\xNN`, `\n`, `\r` and `\t` become the raw bytes, escaped for glob if they are
+/// glob special characters. Glob escapes (`\\`, `\*`, `\?`, `\[`, `\]`) are kept,
+/// `\\` matches a literal backslash in both forms.
+///
+/// # Examples
+/// ```
+/// assert_eq!(unescape_glob_pattern("id:\\xff*").as_ref(), b"id:\xff*");
+/// assert_eq!(unescape_glob_pattern("a\\\\b*").as_ref(), b"a\\\\b*");
+/// assert_eq!(unescape_glob_pattern("a\\x2a*").as_ref(), b"a\\**");
+/// ```
+pub fn unescape_glob_pattern(pattern: &str) -> Cow<'_, [u8]> {
+    if !pattern.contains('\\') {
+        return Cow::Borrowed(pattern.as_bytes());
+    }
+    let bytes = pattern.as_bytes();
+    let mut raw = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'\\' || i + 1 >= bytes.len() {
+            raw.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+        match bytes[i + 1] {
+            b'\\' => raw.extend_from_slice(b"\\\\"),
+            b'n' => raw.push(b'\n'),
+            b'r' => raw.push(b'\r'),
+            b't' => raw.push(b'\t'),
+            b'x' => {
+                let byte = pattern
+                    .get(i + 2..i + 4)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                if let Some(byte) = byte {
+                    if matches!(byte, b'*' | b'?' | b'[' | b']' | b'\\') {
+                        raw.push(b'\\');
+                    }
+                    raw.push(byte);
+                    i += 4;
+                } else {
+                    raw.push(b'\\');
+                    i += 1;
+                }
+                continue;
+            }
+            // a glob escape, the next character is pushed as is
+            _ => {
+                raw.push(b'\\');
+                i += 1;
+                continue;
+            }
+        }
+        i += 2;
+    }
+    Cow::Owned(raw)
+}
+
+/// Escapes the glob special characters of an escaped key (see [`escape_key`]),
+/// so it is matched literally by [`unescape_glob_pattern`], e.g. as a SCAN prefix.
+pub fn escape_glob(key: &str) -> String {
+    let mut escaped = String::with_capacity(key.len() + 4);
+    for c in key.chars() {
+        if matches!(c, '*' | '?' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Normalizes a key typed by the user to its escaped text form,
+/// so `\xNN` sequences can be used to enter binary keys.
+pub fn normalize_key(key: &str) -> String {
+    escape_key(&unescape_key(key))
+}
+
+/// Parses a byte size typed by the user, e.g. `512`, `10KB` or `1.5MB`.
+///
+/// Units are case-insensitive and use 1024 as the base.
+///
+/// # Examples
+/// ```
+/// assert_eq!(parse_byte_size("512"), Some(512));
+/// assert_eq!(parse_byte_size("10kb"), Some(10 * 1024));
+/// assert_eq!(parse_byte_size("abc"), None);
+/// ```
+pub fn parse_byte_size(s: &str) -> Option<u64> {
+    let s = s.trim().to_ascii_uppercase();
+    let number_end = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+    let (number, unit) = s.split_at(number_end);
+    let number = number.parse::<f64>().ok()?;
+    let multiplier: u64 = match unit.trim() {
+        "" | "B" => 1,
+        "K" | "KB" => 1 << 10,
+        "M" | "MB" => 1 << 20,
+        "G" | "GB" => 1 << 30,
+        _ => return None,
+    };
+    Some((number * multiplier as f64) as u64)
+}
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::parse_byte_size;
+
 pub fn validate_ttl(s: &str) -> bool {
     if s.is_empty() || s.parse::<usize>().is_ok() {
         return true;
@@ -30,3 +32,7 @@ pub fn validate_common_string(s: &str) -> bool {
 pub fn validate_host(s: &str) -> bool {
     s.len() <= 255 && s.is_ascii()
 }
+
+pub fn validate_byte_size(s: &str) -> bool {
+    s.is_empty() || parse_byte_size(s).is_some()
+}
@@ -4,7 +4,8 @@ use crate::constants::SIDEBAR_WIDTH;
 use crate::helpers::{MemuAction, is_app_store_build, is_development, new_hot_keys};
 use crate::states::{
     FontSize, FontSizeAction, LocaleAction, NotificationCategory, Route, ServerEvent, SettingsAction, ThemeAction,
-    ZedisAppState, ZedisGlobalStore, ZedisServerState, save_app_state, update_app_state_and_save,
+    ZedisAppState, ZedisGlobalStore, ZedisServerState, ZedisTabsState, get_bookmarks, save_app_state,
+    update_app_state_and_save,
 };
 use crate::views::{ZedisContent, ZedisSidebar, ZedisTitleBar, open_about_window};
 use gpui::{
@@ -45,10 +46,11 @@ pub struct Zedis {
 }
 
 impl Zedis {
-    pub fn new(window: &mut Window, cx: &mut Context<Self>, server_state: Entity<ZedisServerState>) -> Self {
-        let sidebar = cx.new(|cx| ZedisSidebar::new(server_state.clone(), window, cx));
-        let content = cx.new(|cx| ZedisContent::new(server_state.clone(), window, cx));
-        cx.subscribe(&server_state, |this, _server_state, event, cx| {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>, tabs: Entity<ZedisTabsState>) -> Self {
+        let sidebar = cx.new(|cx| ZedisSidebar::new(tabs.clone(), window, cx));
+        let content = cx.new(|cx| ZedisContent::new(tabs.clone(), window, cx));
+        // Notifications and errors of all tabs are forwarded by the tabs state
+        cx.subscribe(&tabs, |this, _tabs, event, cx| {
             match event {
                 ServerEvent::Notification(e) => {
                     let message = e.message.clone();
@@ -265,6 +267,14 @@ fn main() {
             error!(error = %e, "get servers fail",);
         }
     }
+    match get_bookmarks() {
+        Ok(bookmarks) => {
+            server_state.set_bookmarks(bookmarks);
+        }
+        Err(e) => {
+            error!(error = %e, "get bookmarks fail",);
+        }
+    }
     info!(is_app_store_build = is_app_store_build(), "detect app build");
 
     app.run(move |cx| {
@@ -307,7 +317,7 @@ fn main() {
             ],
         }]);
 
-        let server_state = cx.new(|_| server_state.clone());
+        let tabs = cx.new(|cx| ZedisTabsState::new(server_state.clone(), cx));
         cx.spawn(async move |cx| {
             cx.open_window(
                 WindowOptions {
@@ -328,7 +338,7 @@ fn main() {
                         cx.hide();
                         false
                     });
-                    let zedis_view = cx.new(|cx| Zedis::new(window, cx, server_state));
+                    let zedis_view = cx.new(|cx| Zedis::new(window, cx, tabs));
                     cx.new(|cx| Root::new(zedis_view, window, cx))
                 },
             )?;
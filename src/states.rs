@@ -15,14 +15,18 @@
 mod app;
 mod i18n;
 mod server;
+mod tabs;
 
 pub use app::*;
 pub use i18n::i18n_common;
+pub use i18n::i18n_diff_viewer;
 pub use i18n::i18n_editor;
 pub use i18n::i18n_hash_editor;
+pub use i18n::i18n_history;
 pub use i18n::i18n_key_tree;
 pub use i18n::i18n_kv_table;
 pub use i18n::i18n_list_editor;
+pub use i18n::i18n_script_editor;
 pub use i18n::i18n_servers;
 pub use i18n::i18n_set_editor;
 pub use i18n::i18n_settings;
@@ -30,7 +34,17 @@ pub use i18n::i18n_sidebar;
 pub use i18n::i18n_status_bar;
 pub use i18n::i18n_zset_editor;
 pub use server::ErrorMessage;
+pub use server::KeyChange;
 pub use server::ServerEvent;
 pub use server::ServerTask;
 pub use server::ZedisServerState;
+pub use server::bookmark::{BookmarkKey, get_bookmarks};
+pub use server::diff::*;
+pub use server::hash::FIELD_TTL_VERSION;
+pub use server::history::HistoryRecord;
+pub use server::list::MAX_REORDER_LIST_SIZE;
+pub use server::reconnect::ConnectionState;
+pub use server::script::{FunctionLibrary, SavedScript, ScriptMode, ScriptOutput};
+pub use server::string::PARTIAL_PAGE_SIZE;
 pub use server::value::*;
+pub use tabs::ZedisTabsState;
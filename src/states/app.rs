@@ -14,7 +14,7 @@
 
 use crate::constants::SIDEBAR_WIDTH;
 use crate::error::Error;
-use crate::helpers::{get_key_tree_widths, get_or_create_config_dir};
+use crate::helpers::{get_key_tree_widths, get_or_create_config_dir, glob_match};
 use gpui::{Action, App, AppContext, Bounds, Context, Entity, Global, Pixels};
 use gpui_component::{PixelsExt, ThemeMode};
 use locale_config::Locale;
@@ -31,6 +31,8 @@ pub enum Route {
     #[default]
     Home,
     Editor,
+    /// Lua script and Redis Functions workbench of the active tab
+    Scripts,
     Settings,
 }
 
@@ -95,6 +97,41 @@ fn get_or_create_server_config() -> Result<PathBuf> {
     Ok(path)
 }
 
+/// Maps keys matching a glob pattern to a protobuf message type
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProtobufRule {
+    pub pattern: String,
+    /// Full message name, e.g. `package.User`
+    pub message: String,
+}
+
+/// External command formatter for custom value formats
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValueFormatter {
+    pub name: String,
+    /// Command decoding the raw value (stdin) to the displayed text (stdout)
+    pub decode: String,
+    /// Command encoding the edited text (stdin) back to the raw value (stdout)
+    pub encode: Option<String>,
+}
+
+/// Maps keys matching a glob pattern to a value formatter
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FormatterRule {
+    pub pattern: String,
+    /// Name of the value formatter
+    pub formatter: String,
+}
+
+/// An editor tab restored on the next start
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EditorTabConfig {
+    pub server_id: String,
+    pub db: usize,
+    /// Selected key of the tab
+    pub key: Option<String>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ZedisAppState {
     route: Route,
@@ -106,6 +143,13 @@ pub struct ZedisAppState {
     max_key_tree_depth: Option<usize>,
     key_separator: Option<String>,
     max_truncate_length: Option<usize>,
+    partial_value_size: Option<usize>,
+    protobuf_descriptors: Option<Vec<String>>,
+    protobuf_rules: Option<Vec<ProtobufRule>>,
+    formatters: Option<Vec<ValueFormatter>>,
+    formatter_rules: Option<Vec<FormatterRule>>,
+    tabs: Option<Vec<EditorTabConfig>>,
+    active_tab: Option<usize>,
 }
 
 #[derive(Debug, Clone)]
@@ -247,6 +291,89 @@ impl ZedisAppState {
     pub fn set_max_truncate_length(&mut self, max_truncate_length: usize) {
         self.max_truncate_length = Some(max_truncate_length);
     }
+    /// String values larger than this (in KB) are loaded page by page, 0 always loads the whole value
+    pub fn partial_value_size(&self) -> usize {
+        self.partial_value_size.unwrap_or(10 * 1024)
+    }
+    pub fn set_partial_value_size(&mut self, partial_value_size: usize) {
+        self.partial_value_size = Some(partial_value_size);
+    }
+    /// FileDescriptorSet files used to decode protobuf values
+    pub fn protobuf_descriptors(&self) -> &[String] {
+        self.protobuf_descriptors.as_deref().unwrap_or_default()
+    }
+    pub fn set_protobuf_descriptors(&mut self, descriptors: Vec<String>) {
+        if descriptors.is_empty() {
+            self.protobuf_descriptors = None;
+            return;
+        }
+        self.protobuf_descriptors = Some(descriptors);
+    }
+    pub fn protobuf_rules(&self) -> &[ProtobufRule] {
+        self.protobuf_rules.as_deref().unwrap_or_default()
+    }
+    pub fn set_protobuf_rules(&mut self, rules: Vec<ProtobufRule>) {
+        if rules.is_empty() {
+            self.protobuf_rules = None;
+            return;
+        }
+        self.protobuf_rules = Some(rules);
+    }
+    /// Get the protobuf message name of the first rule matching the key
+    pub fn protobuf_message(&self, key: &str) -> Option<&str> {
+        self.protobuf_rules()
+            .iter()
+            .find(|rule| glob_match(&rule.pattern, key))
+            .map(|rule| rule.message.as_str())
+    }
+    pub fn formatters(&self) -> &[ValueFormatter] {
+        self.formatters.as_deref().unwrap_or_default()
+    }
+    pub fn set_formatters(&mut self, formatters: Vec<ValueFormatter>) {
+        if formatters.is_empty() {
+            self.formatters = None;
+            return;
+        }
+        self.formatters = Some(formatters);
+    }
+    pub fn formatter_rules(&self) -> &[FormatterRule] {
+        self.formatter_rules.as_deref().unwrap_or_default()
+    }
+    pub fn set_formatter_rules(&mut self, rules: Vec<FormatterRule>) {
+        if rules.is_empty() {
+            self.formatter_rules = None;
+            return;
+        }
+        self.formatter_rules = Some(rules);
+    }
+    /// Editor tabs of the last session
+    pub fn tabs(&self) -> &[EditorTabConfig] {
+        self.tabs.as_deref().unwrap_or_default()
+    }
+    /// Index of the active editor tab of the last session
+    pub fn active_tab(&self) -> usize {
+        self.active_tab.unwrap_or_default()
+    }
+    pub fn set_tabs(&mut self, tabs: Vec<EditorTabConfig>, active_tab: usize) {
+        if tabs.is_empty() {
+            self.tabs = None;
+            self.active_tab = None;
+            return;
+        }
+        self.tabs = Some(tabs);
+        self.active_tab = Some(active_tab);
+    }
+    /// Get the value formatter by name
+    pub fn formatter(&self, name: &str) -> Option<&ValueFormatter> {
+        self.formatters().iter().find(|formatter| formatter.name == name)
+    }
+    /// Get the value formatter of the first rule matching the key
+    pub fn key_formatter(&self, key: &str) -> Option<&ValueFormatter> {
+        self.formatter_rules()
+            .iter()
+            .find(|rule| glob_match(&rule.pattern, key))
+            .and_then(|rule| self.formatter(&rule.formatter))
+    }
 }
 
 /// Update app state in background, persist to disk, and refresh UI
@@ -12,14 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::connection::KeyFilter;
 use crate::connection::QueryMode;
 use crate::connection::RedisClientDescription;
 use crate::connection::RedisServer;
+use crate::connection::ReplicaInfo;
 use crate::connection::get_connection_manager;
 use crate::connection::save_servers;
 use crate::error::Error;
 use crate::helpers::unix_ts;
 use crate::states::NotificationAction;
+use crate::states::i18n_status_bar;
+use crate::states::server::reconnect::ConnectionState;
 use crate::states::server::stat::RedisInfo;
 use ahash::AHashMap;
 use ahash::AHashSet;
@@ -28,17 +32,28 @@ use gpui::EventEmitter;
 use gpui::SharedString;
 use gpui::prelude::*;
 use parking_lot::RwLock;
+use semver::Version;
+use std::collections::hash_map::Entry;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use tracing::debug;
 use tracing::error;
 use uuid::Uuid;
 use value::{KeyType, RedisValue, RedisValueData};
 
+pub mod bookmark;
+pub mod diff;
+pub mod file;
 pub mod hash;
+pub mod history;
 pub mod key;
 pub mod list;
+pub mod reconnect;
+pub mod script;
+pub mod sentinel;
 pub mod set;
+pub mod staging;
 pub mod stat;
 pub mod string;
 pub mod value;
@@ -61,6 +76,13 @@ pub struct ErrorMessage {
     pub created_at: i64,
 }
 
+/// Change of the loaded keys, merged into the key tree in order
+#[derive(Debug, Clone)]
+pub enum KeyChange {
+    Added(SharedString),
+    Removed(SharedString),
+}
+
 /// Redis server connection status
 #[derive(Clone, PartialEq, Default, Debug)]
 pub enum RedisServerStatus {
@@ -93,15 +115,38 @@ pub struct ZedisServerState {
     /// Currently selected database
     db: usize,
 
-    /// Query mode (All/Prefix/Exact) for key filtering
+    /// Query mode (All/Prefix/Exact/Suffix/Regex) for key filtering
     query_mode: QueryMode,
 
+    /// Type, TTL and memory filters of the key search
+    key_filter: KeyFilter,
+
+    /// Pinned keys, saved searches and recent keys of all servers
+    bookmarks: bookmark::Bookmarks,
+
     /// Whether to soft wrap the editor
     soft_wrap: bool,
 
     /// Current server status
     server_status: RedisServerStatus,
 
+    /// Whether the connection is up, being restored or given up
+    connection_state: ConnectionState,
+
+    /// Whether the master is resolved by sentinels
+    is_sentinel: bool,
+    /// Replicas of the master with their lag
+    replicas: Arc<Vec<ReplicaInfo>>,
+    /// Address of the replica browsed (read-only) instead of the master
+    browsed_replica: Option<SharedString>,
+    /// Replicas the keys and values are read from with the read preference `PreferReplica`
+    read_replicas: Vec<SharedString>,
+    /// Keys written in this session, their values are read from the master
+    /// since a replica may not have the write yet
+    written_keys: AHashSet<SharedString>,
+    /// Stop flag of the watch of the sentinel failovers
+    sentinel_watch: Option<Arc<AtomicBool>>,
+
     /// Total number of keys in the database (from DBSIZE command)
     dbsize: Option<u64>,
 
@@ -122,6 +167,34 @@ pub struct ZedisServerState {
     /// Value data for the currently selected key
     value: Option<RedisValue>,
 
+    /// Result of the last value comparison
+    value_diff: Option<Arc<diff::ValueDiff>>,
+
+    /// Edit history of the current server
+    history: Arc<Vec<history::HistoryRecord>>,
+
+    // ===== Script workbench state =====
+    /// Commands run from the script workbench and their replies
+    script_outputs: Arc<Vec<script::ScriptOutput>>,
+
+    /// Function libraries of the current server
+    function_libraries: Arc<Vec<script::FunctionLibrary>>,
+
+    /// Scripts saved for the current server
+    saved_scripts: Arc<Vec<script::SavedScript>>,
+
+    // ===== Staged changes state =====
+    /// Whether writes of the value editors are staged instead of sent
+    staging: bool,
+
+    /// Writes queued in staged mode
+    staged_commands: Arc<Vec<staging::StagedCommand>>,
+
+    /// Digest of the staged keys when they were first staged
+    staged_digests: AHashMap<SharedString, Option<u64>>,
+    /// Whether the staged changes are being applied
+    applying_staged_commands: bool,
+
     // ===== Key scanning state =====
     /// Search keyword for filtering keys
     keyword: SharedString,
@@ -147,6 +220,12 @@ pub struct ZedisServerState {
     /// Map of all loaded keys and their types
     keys: AHashMap<SharedString, KeyType>,
 
+    /// Keys added and removed in order, the key tree merges the changes it has not seen
+    key_changes: Vec<KeyChange>,
+
+    /// Changes when keys are cleared, the key tree is rebuilt instead of merged
+    keys_generation: usize,
+
     // ===== Error tracking =====
     /// Recent error messages (limited to MAX_ERROR_MESSAGES)
     error_messages: Arc<RwLock<Vec<ErrorMessage>>>,
@@ -172,6 +251,9 @@ pub enum ServerTask {
     /// Update the server soft wrap
     UpdateServerSoftWrap,
 
+    /// Update the server key filter
+    UpdateServerKeyFilter,
+
     /// Add new server or update existing server configuration
     UpdateOrInsertServer,
 
@@ -217,14 +299,129 @@ pub enum ServerTask {
     /// Remove a value from a zset
     RemoveZsetValue,
 
+    /// Add or update a field of a hash
+    AddHashValue,
     /// Remove a value from a hash
     RemoveHashValue,
 
     /// Save edited value back to Redis
     SaveValue,
+
+    /// Load and compare two values
+    DiffValues,
+
+    /// Load the edit history of the server
+    LoadHistory,
+    /// Revert a key to a recorded state
+    RevertHistory,
+
+    /// Decode a value with an external command formatter
+    FormatValue,
+
+    /// Replace a string value with the content of a file
+    ImportValue,
+    /// Write a string value to a file
+    ExportValue,
+    /// Load another page of a partially loaded string value
+    LoadValuePage,
+    /// Set or remove the TTL of a hash field
+    UpdateHashFieldTtl,
+    /// Browse a range of a sorted set
+    LoadZsetRange,
+    /// Increment the score of a sorted set member
+    IncrZsetValue,
+    /// Remove a range of a sorted set
+    RemoveZsetRange,
+    /// Insert a value before or after a list element
+    InsertListValue,
+    /// Move an element to another list
+    MoveListValue,
+    /// Trim a list to a range
+    TrimListValue,
+    /// Find the index of a list element
+    FindListValue,
+    /// Reorder the elements of a list
+    ReorderListValue,
+    /// Compute a set operation with other keys
+    LoadSetAlgebra,
+    /// Store the result of a set operation as a new key
+    StoreSetAlgebra,
+    /// Count the members in the intersection of sets
+    CountSetIntersection,
+    /// Check which members exist in a set
+    CheckSetMembers,
+
+    /// Save pinned keys, saved searches and recent keys
+    SaveBookmarks,
+
+    /// Run a Lua script (EVAL/EVALSHA)
+    RunScript,
+    /// Run a read-only Lua script (EVAL_RO)
+    RunReadOnlyScript,
+    /// Load or flush the script cache
+    ManageScriptCache,
+    /// Check if a script is in the script cache
+    CheckScriptExists,
+    /// List the function libraries
+    LoadFunctionLibraries,
+    /// Load (replace) a function library
+    LoadFunctionLibrary,
+    /// Delete a function library
+    DeleteFunctionLibrary,
+    /// Call a function (FCALL)
+    CallFunction,
+    /// Call a read-only function (FCALL_RO)
+    CallReadOnlyFunction,
+    /// Load the scripts saved for the server
+    LoadSavedScripts,
+    /// Save the scripts of the server
+    SaveScripts,
+
+    /// Take the digest of newly staged keys
+    StageChanges,
+    /// Apply the staged changes with MULTI/EXEC
+    ApplyStagedChanges,
+
+    /// Reconnect after a connection or topology error
+    Reconnect,
 }
 
 impl ServerTask {
+    /// Returns true if the task writes keys, refused while a replica is browsed
+    pub fn is_write(&self) -> bool {
+        matches!(
+            self,
+            ServerTask::DeleteKey
+                | ServerTask::AddKey
+                | ServerTask::UpdateKeyTtl
+                | ServerTask::RemoveListValue
+                | ServerTask::UpdateListValue
+                | ServerTask::PushListValue
+                | ServerTask::AddSetValue
+                | ServerTask::RemoveSetValue
+                | ServerTask::AddZsetValue
+                | ServerTask::RemoveZsetValue
+                | ServerTask::AddHashValue
+                | ServerTask::RemoveHashValue
+                | ServerTask::SaveValue
+                | ServerTask::RevertHistory
+                | ServerTask::ImportValue
+                | ServerTask::UpdateHashFieldTtl
+                | ServerTask::IncrZsetValue
+                | ServerTask::RemoveZsetRange
+                | ServerTask::InsertListValue
+                | ServerTask::MoveListValue
+                | ServerTask::TrimListValue
+                | ServerTask::ReorderListValue
+                | ServerTask::StoreSetAlgebra
+                | ServerTask::RunScript
+                | ServerTask::ManageScriptCache
+                | ServerTask::LoadFunctionLibrary
+                | ServerTask::DeleteFunctionLibrary
+                | ServerTask::CallFunction
+                | ServerTask::ApplyStagedChanges
+        )
+    }
     /// Get string representation of task (for logging and error messages)
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -244,19 +441,56 @@ impl ServerTask {
             ServerTask::LoadMoreValue => "load_more_value",
             ServerTask::SaveValue => "save_value",
             ServerTask::UpdateServerQueryMode => "update_server_query_mode",
+            ServerTask::UpdateServerKeyFilter => "update_server_key_filter",
             ServerTask::UpdateServerSoftWrap => "update_server_soft_wrap",
             ServerTask::PushListValue => "push_list_value",
             ServerTask::AddSetValue => "add_set_value",
             ServerTask::RemoveSetValue => "remove_set_value",
             ServerTask::AddZsetValue => "add_zset_value",
             ServerTask::RemoveZsetValue => "remove_zset_value",
+            ServerTask::AddHashValue => "add_hash_value",
             ServerTask::RemoveHashValue => "remove_hash_value",
+            ServerTask::DiffValues => "diff_values",
+            ServerTask::LoadHistory => "load_history",
+            ServerTask::RevertHistory => "revert_history",
+            ServerTask::FormatValue => "format_value",
+            ServerTask::ImportValue => "import_value",
+            ServerTask::ExportValue => "export_value",
+            ServerTask::LoadValuePage => "load_value_page",
+            ServerTask::UpdateHashFieldTtl => "update_hash_field_ttl",
+            ServerTask::LoadZsetRange => "load_zset_range",
+            ServerTask::IncrZsetValue => "incr_zset_value",
+            ServerTask::RemoveZsetRange => "remove_zset_range",
+            ServerTask::InsertListValue => "insert_list_value",
+            ServerTask::MoveListValue => "move_list_value",
+            ServerTask::TrimListValue => "trim_list_value",
+            ServerTask::FindListValue => "find_list_value",
+            ServerTask::ReorderListValue => "reorder_list_value",
+            ServerTask::LoadSetAlgebra => "load_set_algebra",
+            ServerTask::StoreSetAlgebra => "store_set_algebra",
+            ServerTask::CountSetIntersection => "count_set_intersection",
+            ServerTask::CheckSetMembers => "check_set_members",
+            ServerTask::SaveBookmarks => "save_bookmarks",
+            ServerTask::RunScript => "run_script",
+            ServerTask::RunReadOnlyScript => "run_read_only_script",
+            ServerTask::ManageScriptCache => "manage_script_cache",
+            ServerTask::CheckScriptExists => "check_script_exists",
+            ServerTask::LoadFunctionLibraries => "load_function_libraries",
+            ServerTask::LoadFunctionLibrary => "load_function_library",
+            ServerTask::DeleteFunctionLibrary => "delete_function_library",
+            ServerTask::CallFunction => "call_function",
+            ServerTask::CallReadOnlyFunction => "call_read_only_function",
+            ServerTask::LoadSavedScripts => "load_saved_scripts",
+            ServerTask::SaveScripts => "save_scripts",
+            ServerTask::StageChanges => "stage_changes",
+            ServerTask::ApplyStagedChanges => "apply_staged_changes",
+            ServerTask::Reconnect => "reconnect",
         }
     }
 }
 
 /// Events emitted by server state for reactive UI updates
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ServerEvent {
     /// A new background task has started.
     TaskStarted(ServerTask),
@@ -286,6 +520,24 @@ pub enum ServerEvent {
     ValuePaginationFinished(SharedString),
     /// Add a value to a set、list、hash、zset
     ValueAdded(SharedString),
+    /// An element has been located, with its row in the loaded values
+    ValueIndexFound(SharedString, usize),
+    /// Value comparison has completed
+    ValueDiffLoaded,
+    /// Edit history has been loaded or changed
+    HistoryUpdated,
+    /// Pinned keys, saved searches or recent keys have changed
+    BookmarksUpdated,
+    /// A saved search has been applied, with its keyword
+    SearchApplied(SharedString),
+    /// A script workbench command has replied, or the console was cleared
+    ScriptOutputUpdated,
+    /// Function libraries have been loaded
+    FunctionLibrariesUpdated,
+    /// Saved scripts have been loaded or changed
+    SavedScriptsUpdated,
+    /// Staged mode or the staged changes have changed
+    StagedChangesUpdated,
 
     /// User selected a different server
     ServerSelected(SharedString, usize),
@@ -295,6 +547,8 @@ pub enum ServerEvent {
     ServerInfoUpdated(SharedString),
     /// Periodic redis info updated.
     ServerRedisInfoUpdated(SharedString),
+    /// The connection was lost, is being restored, or is restored
+    ConnectionStateChanged(ConnectionState),
 
     /// Soft wrap changed
     SoftWrapToggled(bool),
@@ -319,6 +573,8 @@ impl ZedisServerState {
         self.keyword = SharedString::default();
         self.cursors = None;
         self.keys.clear();
+        self.key_changes.clear();
+        self.keys_generation += 1;
         self.key_tree_id = Uuid::now_v7().to_string().into();
         self.scaning = false;
         self.scan_completed = false;
@@ -331,13 +587,29 @@ impl ZedisServerState {
         self.server_id = SharedString::default();
         self.version = SharedString::default();
         self.nodes = (0, 0);
+        self.connection_state = ConnectionState::Connected;
+        self.is_sentinel = false;
+        self.replicas = Arc::new(vec![]);
+        self.browsed_replica = None;
+        self.read_replicas.clear();
+        self.written_keys.clear();
+        self.stop_sentinel_watch();
         self.keys.clear();
+        self.key_changes.clear();
+        self.keys_generation += 1;
         self.key_tree_id = SharedString::default();
         self.nodes_description = Arc::new(RedisClientDescription::default());
         self.dbsize = None;
         self.key = None;
         self.redis_info = None;
         self.value = None;
+        self.history = Arc::new(vec![]);
+        self.script_outputs = Arc::new(vec![]);
+        self.function_libraries = Arc::new(vec![]);
+        self.saved_scripts = Arc::new(vec![]);
+        // staged changes belong to the server and db they were staged on
+        self.staged_commands = Arc::new(vec![]);
+        self.staged_digests.clear();
         self.reset_scan();
     }
 
@@ -349,10 +621,11 @@ impl ZedisServerState {
         let mut insert_count = 0;
 
         for key in keys {
-            self.keys.entry(key).or_insert_with(|| {
+            if let Entry::Vacant(entry) = self.keys.entry(key.clone()) {
+                entry.insert(KeyType::Unknown);
+                self.key_changes.push(KeyChange::Added(key));
                 insert_count += 1;
-                KeyType::Unknown
-            });
+            }
         }
 
         // Update tree ID only if new keys were added
@@ -361,6 +634,22 @@ impl ZedisServerState {
         }
     }
 
+    /// Add or update a key with its type, generates a new tree ID to trigger UI refresh
+    fn insert_key(&mut self, key: SharedString, key_type: KeyType) {
+        if self.keys.insert(key.clone(), key_type).is_none() {
+            self.key_changes.push(KeyChange::Added(key));
+        }
+        self.key_tree_id = Uuid::now_v7().to_string().into();
+    }
+
+    /// Remove a key from the key map, the key tree removes it in place
+    fn remove_key(&mut self, key: &SharedString) {
+        if self.keys.remove(key).is_some() {
+            self.key_changes.push(KeyChange::Removed(key.clone()));
+        }
+        self.key_tree_id = Uuid::now_v7().to_string().into();
+    }
+
     /// Add an error message to the history and emit error event
     ///
     /// Maintains a rolling window of MAX_ERROR_MESSAGES most recent errors
@@ -407,6 +696,19 @@ impl ZedisServerState {
         T: Send + 'static,
         Fut: Future<Output = Result<T>> + Send + 'static,
     {
+        // a browsed replica is read-only, the write is failed without sending it
+        if name.is_write() && self.is_readonly() {
+            let message = i18n_status_bar(cx, "replica_readonly").to_string();
+            self.add_error_message(name.as_str().to_string(), message.clone(), cx);
+            callback(self, Err(Error::Invalid { message }), cx);
+            return;
+        }
+        // writes go to the master, the selected key is read from it from now on
+        if name.is_write()
+            && let Some(key) = &self.key
+        {
+            self.written_keys.insert(key.clone());
+        }
         cx.emit(ServerEvent::TaskStarted(name.clone()));
         debug!(name = name.as_str(), "Spawning background task");
 
@@ -421,6 +723,14 @@ impl ZedisServerState {
                     let message = format!("{} failed", name.as_str());
                     error!(error = %e, message);
                     this.add_error_message(name.as_str().to_string(), e.to_string(), cx);
+                    // writes are rejected by a browsed replica, it is not a failover
+                    let browsing_replica = this.browsed_replica.is_some() && e.is_readonly_error();
+                    if name != ServerTask::Reconnect
+                        && !browsing_replica
+                        && (e.is_connection_error() || e.is_topology_error())
+                    {
+                        this.handle_connection_error(cx);
+                    }
                 }
                 callback(this, result, cx);
             })
@@ -447,6 +757,8 @@ impl ZedisServerState {
             move |this, result, cx| {
                 if let Ok(servers) = result {
                     this.servers = Some(servers);
+                    // let the other tabs pick up the changed config
+                    cx.emit(ServerEvent::ServerListUpdated);
                 }
                 cx.notify();
             },
@@ -497,10 +809,22 @@ impl ZedisServerState {
             server.soft_wrap = Some(soft_wrap);
         });
     }
-    /// Get the current query mode (All/Prefix/Exact)
+    /// Get the current query mode (All/Prefix/Exact/Suffix/Regex)
     pub fn query_mode(&self) -> QueryMode {
         self.query_mode
     }
+    /// Set the key filter and search the keys again
+    pub fn set_key_filter(&mut self, filter: KeyFilter, cx: &mut Context<Self>) {
+        self.key_filter = filter.clone();
+        self.update_and_save_server_config(ServerTask::UpdateServerKeyFilter, cx, move |server| {
+            server.key_filter = (!filter.is_empty()).then_some(filter);
+        });
+        self.handle_filter(self.keyword.clone(), cx);
+    }
+    /// Get the current key filter
+    pub fn key_filter(&self) -> &KeyFilter {
+        &self.key_filter
+    }
 
     /// Check if the current scan has completed
     pub fn scan_completed(&self) -> bool {
@@ -535,17 +859,50 @@ impl ZedisServerState {
     pub fn nodes_description(&self) -> Arc<RedisClientDescription> {
         self.nodes_description.clone()
     }
+    /// Get the replicas the reads are sent to, empty if they go to the masters
+    pub fn read_replicas(&self) -> &[SharedString] {
+        &self.read_replicas
+    }
+    /// Returns true if the key has been written in this session, its value is then read from the master
+    pub(crate) fn is_key_written(&self, key: &str) -> bool {
+        self.written_keys.contains(key)
+    }
+    /// Reads the value of the key from the master from now on, e.g. a key other than the selected one is written
+    pub(crate) fn mark_key_written(&mut self, key: SharedString) {
+        self.written_keys.insert(key);
+    }
+    /// Warns that the replicas could not be connected to and the reads go to the master
+    fn notify_read_replica_error(&self, error: Option<String>, cx: &mut Context<Self>) {
+        if let Some(error) = error {
+            let message = format!("{} {error}", i18n_status_bar(cx, "read_replicas_unreachable"));
+            cx.emit(ServerEvent::Notification(NotificationAction::new_warning(
+                message.into(),
+            )));
+        }
+    }
 
     /// Get the Redis server version string
     pub fn version(&self) -> &str {
         &self.version
     }
+    /// Checks if the server version is at least the given version
+    pub fn is_at_least_version(&self, version: &str) -> bool {
+        match (Version::parse(&self.version), Version::parse(version)) {
+            (Ok(current), Ok(version)) => current >= version,
+            _ => false,
+        }
+    }
 
     /// Get the currently selected server id
     pub fn server_id(&self) -> &str {
         &self.server_id
     }
 
+    /// Get the currently selected database
+    pub fn db(&self) -> usize {
+        self.db
+    }
+
     /// Get whether the server supports database selection
     pub fn supports_db_selection(&self) -> bool {
         self.supports_db_selection
@@ -581,6 +938,14 @@ impl ZedisServerState {
     pub fn keys(&self) -> &AHashMap<SharedString, KeyType> {
         &self.keys
     }
+    /// Get the keys added and removed since the keys were cleared, in order
+    pub fn key_changes(&self) -> &[KeyChange] {
+        &self.key_changes
+    }
+    /// Get the generation of the loaded keys, changed when keys are cleared
+    pub fn keys_generation(&self) -> usize {
+        self.keys_generation
+    }
 
     /// Get the value data for the currently selected key
     pub fn value(&self) -> Option<&RedisValue> {
@@ -599,11 +964,13 @@ impl ZedisServerState {
     pub fn remove_server(&mut self, id: &str, cx: &mut Context<Self>) {
         let mut servers = self.servers.clone().unwrap_or_default();
         servers.retain(|s| s.id != id);
+        let id = id.to_string();
 
         self.spawn(
             ServerTask::RemoveServer,
             move || async move {
                 save_servers(servers.clone()).await?;
+                get_connection_manager().remove_server(&id);
                 Ok(servers)
             },
             move |this, result, cx| {
@@ -637,12 +1004,15 @@ impl ZedisServerState {
                         message: "Server name is required".to_string(),
                     });
                 }
+                let server_id = server.id.clone();
                 if let Some(existing_server) = servers.iter_mut().find(|s| s.id == server.id) {
                     *existing_server = server;
                 } else {
                     servers.push(server);
                 }
                 save_servers(servers.clone()).await?;
+                // the next connection uses the new config
+                get_connection_manager().remove_server(&server_id);
 
                 Ok(servers)
             },
@@ -677,7 +1047,7 @@ impl ZedisServerState {
             self.reset();
             self.server_id = server_id.clone();
             self.db = db;
-            let (query_mode, soft_wrap) = self
+            let (query_mode, key_filter, soft_wrap) = self
                 .server(server_id.as_str())
                 .map(|server_config| {
                     let mode = server_config
@@ -686,13 +1056,16 @@ impl ZedisServerState {
                         .and_then(|s| QueryMode::from_str(s).ok())
                         .unwrap_or_default();
 
+                    let filter = server_config.key_filter.clone().unwrap_or_default();
+
                     let wrap = server_config.soft_wrap.unwrap_or(true);
 
                     // 返回一个元组，包含所有需要更新的值
-                    (mode, wrap)
+                    (mode, filter, wrap)
                 })
-                .unwrap_or((QueryMode::All, true));
+                .unwrap_or((QueryMode::All, KeyFilter::default(), true));
             self.query_mode = query_mode;
+            self.key_filter = key_filter;
             self.soft_wrap = soft_wrap;
 
             debug!(server_id = self.server_id.as_str(), "Selecting server");
@@ -723,7 +1096,20 @@ impl ZedisServerState {
                     let nodes = client.nodes();
                     let nodes_description = client.nodes_description();
                     let supports_db_selection = client.supports_db_selection();
-                    Ok((dbsize, nodes, nodes_description, version, supports_db_selection))
+                    let replication = (
+                        client.is_sentinel(),
+                        client.browsed_replica(),
+                        client.read_replicas(),
+                        client.read_replica_error(),
+                    );
+                    Ok((
+                        dbsize,
+                        nodes,
+                        nodes_description,
+                        version,
+                        supports_db_selection,
+                        replication,
+                    ))
                 },
                 move |this, result, cx| {
                     // Ignore if user switched to a different server while loading
@@ -732,12 +1118,21 @@ impl ZedisServerState {
                     }
 
                     // Update metadata if successful
-                    if let Ok((dbsize, nodes, nodes_description, version, supports_db_selection)) = result {
+                    if let Ok((dbsize, nodes, nodes_description, version, supports_db_selection, replication)) = result
+                    {
                         this.dbsize = Some(dbsize);
                         this.nodes = nodes;
                         this.nodes_description = Arc::new(nodes_description);
                         this.version = version.into();
                         this.supports_db_selection = supports_db_selection;
+                        let (is_sentinel, browsed_replica, read_replicas, read_replica_error) = replication;
+                        this.is_sentinel = is_sentinel;
+                        this.browsed_replica = browsed_replica.map(SharedString::from);
+                        this.read_replicas = read_replicas.into_iter().map(SharedString::from).collect();
+                        this.notify_read_replica_error(read_replica_error, cx);
+                        if is_sentinel {
+                            this.watch_sentinel(cx);
+                        }
                     };
 
                     let server_id = this.server_id.clone();
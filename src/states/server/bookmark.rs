@@ -0,0 +1,243 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bookmarks module.
+//!
+//! Keeps the keys and searches revisited often, across all servers:
+//! - Pinned keys (server + db + key)
+//! - Saved searches (keyword + query mode + key filter)
+//! - Recently selected keys, tracked automatically
+//!
+//! Bookmarks are persisted in `bookmarks.toml` under the config directory,
+//! next to `redis-servers.toml`.
+
+use super::{ServerEvent, ServerTask, ZedisServerState};
+use crate::{
+    connection::{KeyFilter, QueryMode},
+    error::Error,
+    helpers::{get_or_create_config_dir, unix_ts},
+};
+use gpui::{SharedString, prelude::*};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf, str::FromStr, sync::LazyLock};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Maximum number of recent keys kept
+const MAX_RECENT_KEYS: usize = 30;
+
+/// Serializes writes of the bookmarks file.
+static BOOKMARKS_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+/// A key of a server database.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BookmarkKey {
+    pub server_id: String,
+    pub db: usize,
+    pub key: String,
+    /// Unix timestamp in seconds
+    #[serde(default)]
+    pub created_at: i64,
+}
+
+impl BookmarkKey {
+    fn is_same(&self, server_id: &str, db: usize, key: &str) -> bool {
+        self.server_id == server_id && self.db == db && self.key == key
+    }
+}
+
+/// A key search of a server database.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub server_id: String,
+    pub db: usize,
+    pub keyword: String,
+    /// Query mode (QueryMode::to_string)
+    pub query_mode: String,
+    pub key_filter: Option<KeyFilter>,
+}
+
+impl SavedSearch {
+    /// Query mode of the search.
+    pub fn query_mode(&self) -> QueryMode {
+        QueryMode::from_str(&self.query_mode).unwrap_or_default()
+    }
+    /// Short label of the search, e.g. `^ user:` or `~ ^order:\d+$`.
+    pub fn label(&self) -> String {
+        format!("{} {}", self.query_mode, self.keyword)
+    }
+    /// Check if the search has type, TTL or memory filters
+    pub fn has_key_filter(&self) -> bool {
+        self.key_filter.as_ref().is_some_and(|filter| !filter.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Bookmarks {
+    #[serde(default)]
+    pub pinned_keys: Vec<BookmarkKey>,
+    #[serde(default)]
+    pub saved_searches: Vec<SavedSearch>,
+    /// Newest first
+    #[serde(default)]
+    pub recent_keys: Vec<BookmarkKey>,
+}
+
+fn get_bookmarks_file() -> Result<PathBuf> {
+    Ok(get_or_create_config_dir()?.join("bookmarks.toml"))
+}
+
+/// Load the persisted bookmarks.
+pub fn get_bookmarks() -> Result<Bookmarks> {
+    let path = get_bookmarks_file()?;
+    if !path.exists() {
+        return Ok(Bookmarks::default());
+    }
+    let value = fs::read_to_string(path)?;
+    if value.is_empty() {
+        return Ok(Bookmarks::default());
+    }
+    Ok(toml::from_str(&value)?)
+}
+
+fn save_bookmarks(bookmarks: &Bookmarks) -> Result<()> {
+    let _guard = BOOKMARKS_LOCK.lock();
+    let path = get_bookmarks_file()?;
+    let value = toml::to_string(bookmarks)?;
+    fs::write(path, value)?;
+    Ok(())
+}
+
+impl ZedisServerState {
+    /// Set the bookmarks loaded from the config dir
+    pub fn set_bookmarks(&mut self, bookmarks: Bookmarks) {
+        self.bookmarks = bookmarks;
+    }
+    /// Get the pinned keys, saved searches and recent keys
+    pub fn bookmarks(&self) -> &Bookmarks {
+        &self.bookmarks
+    }
+    /// Check if the key of the current server and db is pinned
+    pub fn is_key_pinned(&self, key: &str) -> bool {
+        self.bookmarks
+            .pinned_keys
+            .iter()
+            .any(|item| item.is_same(&self.server_id, self.db, key))
+    }
+    /// Pin the key of the current server and db, or unpin it if it is already pinned
+    pub fn toggle_pinned_key(&mut self, key: SharedString, cx: &mut Context<Self>) {
+        let (server_id, db) = (self.server_id.clone(), self.db);
+        let pinned_keys = &mut self.bookmarks.pinned_keys;
+        if let Some(index) = pinned_keys.iter().position(|item| item.is_same(&server_id, db, &key)) {
+            pinned_keys.remove(index);
+        } else {
+            pinned_keys.push(BookmarkKey {
+                server_id: server_id.to_string(),
+                db,
+                key: key.to_string(),
+                created_at: unix_ts(),
+            });
+        }
+        self.save_bookmarks(cx);
+    }
+    /// The search of the keyword with the current query mode and key filter
+    fn current_search(&self, keyword: &str) -> SavedSearch {
+        SavedSearch {
+            server_id: self.server_id.to_string(),
+            db: self.db,
+            keyword: keyword.to_string(),
+            query_mode: self.query_mode.to_string(),
+            key_filter: (!self.key_filter.is_empty()).then(|| self.key_filter.clone()),
+        }
+    }
+    /// Check if the search of the keyword is saved
+    pub fn is_search_saved(&self, keyword: &str) -> bool {
+        let search = self.current_search(keyword);
+        self.bookmarks.saved_searches.contains(&search)
+    }
+    /// Save the search of the keyword, or remove it if it is already saved
+    pub fn toggle_saved_search(&mut self, keyword: SharedString, cx: &mut Context<Self>) {
+        let search = self.current_search(&keyword);
+        let saved_searches = &mut self.bookmarks.saved_searches;
+        if let Some(index) = saved_searches.iter().position(|item| item == &search) {
+            saved_searches.remove(index);
+        } else {
+            saved_searches.push(search);
+        }
+        self.save_bookmarks(cx);
+    }
+    /// Clear the recent keys
+    pub fn clear_recent_keys(&mut self, cx: &mut Context<Self>) {
+        self.bookmarks.recent_keys.clear();
+        self.save_bookmarks(cx);
+    }
+    /// Move the key to the front of the recent keys
+    pub(crate) fn record_recent_key(&mut self, key: &str, cx: &mut Context<Self>) {
+        if self.server_id.is_empty() || key.is_empty() {
+            return;
+        }
+        let (server_id, db) = (self.server_id.clone(), self.db);
+        let recent_keys = &mut self.bookmarks.recent_keys;
+        if recent_keys
+            .first()
+            .is_some_and(|item| item.is_same(&server_id, db, key))
+        {
+            return;
+        }
+        recent_keys.retain(|item| !item.is_same(&server_id, db, key));
+        recent_keys.insert(
+            0,
+            BookmarkKey {
+                server_id: server_id.to_string(),
+                db,
+                key: key.to_string(),
+                created_at: unix_ts(),
+            },
+        );
+        recent_keys.truncate(MAX_RECENT_KEYS);
+        self.save_bookmarks(cx);
+    }
+    /// Select the key of a bookmark, switching server and db as needed
+    pub fn open_bookmark_key(&mut self, item: BookmarkKey, cx: &mut Context<Self>) {
+        self.select(item.server_id.into(), item.db, cx);
+        self.select_key(item.key.into(), cx);
+    }
+    /// Run a saved search, switching server and db as needed
+    pub fn open_saved_search(&mut self, search: SavedSearch, cx: &mut Context<Self>) {
+        self.select(search.server_id.clone().into(), search.db, cx);
+        let query_mode = search.query_mode();
+        let key_filter = search.key_filter.clone().unwrap_or_default();
+        self.query_mode = query_mode;
+        self.key_filter = key_filter.clone();
+        self.update_and_save_server_config(ServerTask::UpdateServerQueryMode, cx, move |server| {
+            server.query_mode = Some(query_mode.to_string());
+            server.key_filter = (!key_filter.is_empty()).then_some(key_filter);
+        });
+        let keyword: SharedString = search.keyword.into();
+        cx.emit(ServerEvent::SearchApplied(keyword.clone()));
+        self.handle_filter(keyword, cx);
+    }
+    fn save_bookmarks(&mut self, cx: &mut Context<Self>) {
+        let bookmarks = self.bookmarks.clone();
+        cx.emit(ServerEvent::BookmarksUpdated);
+        cx.notify();
+        self.spawn(
+            ServerTask::SaveBookmarks,
+            move || async move { save_bookmarks(&bookmarks) },
+            move |_this, _result, _cx| {},
+            cx,
+        );
+    }
+}
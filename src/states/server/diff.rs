@@ -0,0 +1,451 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Value diff module.
+//!
+//! Loads two values (each addressed by server + db + key) and compares them:
+//! - Strings are compared line by line, JSON payloads are pretty printed first
+//! - Hashes, sets, zsets and lists are compared structurally (added, removed
+//!   and changed fields/members, score changes)
+//! - Values of different types fall back to a text diff of their rendered form
+
+use super::{
+    KeyType, ServerEvent, ServerTask, ZedisServerState,
+    string::pretty_json,
+    value::{DataFormat, RedisBytesValue},
+};
+use crate::{
+    connection::{RedisAsyncConn, get_connection_manager},
+    error::Error,
+    helpers::unescape_key,
+};
+use ahash::AHashMap;
+use bytes::Bytes;
+use gpui::{SharedString, prelude::*};
+use redis::cmd;
+use similar::{Algorithm, ChangeTag, DiffOp, TextDiff, capture_diff_slices};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Maximum number of items loaded from a collection for comparison.
+const MAX_DIFF_ITEMS: usize = 50_000;
+
+/// Identifies one side of a diff: server + db + key.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiffTarget {
+    pub server_id: SharedString,
+    pub db: usize,
+    pub key: SharedString,
+}
+
+/// Kind of change for a diff line or entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiffKind {
+    Unchanged,
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A single line of a text diff.
+#[derive(Debug, Clone)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    /// Line number on the left side (1-based)
+    pub left_no: Option<usize>,
+    /// Line number on the right side (1-based)
+    pub right_no: Option<usize>,
+    pub text: SharedString,
+}
+
+/// A single field/member of a structural diff.
+#[derive(Debug, Clone)]
+pub struct DiffEntry {
+    pub kind: DiffKind,
+    /// Field name, member or list index
+    pub name: SharedString,
+    /// Value (or score) on the left side
+    pub left: Option<SharedString>,
+    /// Value (or score) on the right side
+    pub right: Option<SharedString>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ValueDiffData {
+    Text(Vec<DiffLine>),
+    Structural(Vec<DiffEntry>),
+}
+
+/// Result of comparing two values.
+#[derive(Debug, Clone)]
+pub struct ValueDiff {
+    pub left: DiffTarget,
+    pub right: DiffTarget,
+    pub left_type: KeyType,
+    pub right_type: KeyType,
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+    pub data: ValueDiffData,
+}
+
+impl ValueDiff {
+    /// Whether both values are identical.
+    pub fn is_same(&self) -> bool {
+        self.added == 0 && self.removed == 0 && self.changed == 0
+    }
+}
+
+/// Fully loaded value used as diff input.
+enum DiffSource {
+    None,
+    Text(String),
+    Hash(Vec<(String, String)>),
+    Set(Vec<String>),
+    Zset(Vec<(String, f64)>),
+    List(Vec<String>),
+}
+
+impl DiffSource {
+    fn key_type(&self) -> KeyType {
+        match self {
+            DiffSource::None => KeyType::Unknown,
+            DiffSource::Text(_) => KeyType::String,
+            DiffSource::Hash(_) => KeyType::Hash,
+            DiffSource::Set(_) => KeyType::Set,
+            DiffSource::Zset(_) => KeyType::Zset,
+            DiffSource::List(_) => KeyType::List,
+        }
+    }
+    /// Render the value as text, used when the two sides have different types.
+    fn to_text(&self) -> String {
+        match self {
+            DiffSource::None => String::new(),
+            DiffSource::Text(text) => text.clone(),
+            DiffSource::Hash(values) => values
+                .iter()
+                .map(|(field, value)| format!("{field}: {value}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            DiffSource::Set(values) | DiffSource::List(values) => values.join("\n"),
+            DiffSource::Zset(values) => values
+                .iter()
+                .map(|(member, score)| format!("{score} {member}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+fn lossy(value: Vec<u8>) -> String {
+    String::from_utf8_lossy(&value).to_string()
+}
+
+/// Convert string bytes to text, pretty printing JSON and decoding known formats.
+fn bytes_to_text(bytes: Vec<u8>) -> String {
+    if let Ok(text) = std::str::from_utf8(&bytes) {
+        return match pretty_json(text, usize::MAX) {
+            Some((pretty, _)) => pretty.to_string(),
+            None => text.to_string(),
+        };
+    }
+    let mut value = RedisBytesValue {
+        format: DataFormat::Text,
+        bytes: Bytes::from(bytes),
+        ..Default::default()
+    };
+    value.detect_and_update(usize::MAX);
+    match value.text {
+        Some(text) => text.to_string(),
+        None => pretty_hex::pretty_hex(&value.bytes),
+    }
+}
+
+fn ensure_diff_size(size: usize) -> Result<()> {
+    if size > MAX_DIFF_ITEMS {
+        return Err(Error::Invalid {
+            message: format!("Too many items to compare: {size} (max {MAX_DIFF_ITEMS})"),
+        });
+    }
+    Ok(())
+}
+
+/// Load the full value of a key for comparison.
+async fn load_diff_source(conn: &mut RedisAsyncConn, key: &str) -> Result<DiffSource> {
+    let key_type: String = cmd("TYPE").arg(unescape_key(key)).query_async(conn).await?;
+    let source = match KeyType::from(key_type.as_str()) {
+        KeyType::String => {
+            let value: Vec<u8> = cmd("GET").arg(unescape_key(key)).query_async(conn).await?;
+            DiffSource::Text(bytes_to_text(value))
+        }
+        KeyType::Hash => {
+            let size: usize = cmd("HLEN").arg(unescape_key(key)).query_async(conn).await?;
+            ensure_diff_size(size)?;
+            let values: Vec<(Vec<u8>, Vec<u8>)> = cmd("HGETALL").arg(unescape_key(key)).query_async(conn).await?;
+            DiffSource::Hash(values.into_iter().map(|(f, v)| (lossy(f), lossy(v))).collect())
+        }
+        KeyType::Set => {
+            let size: usize = cmd("SCARD").arg(unescape_key(key)).query_async(conn).await?;
+            ensure_diff_size(size)?;
+            let values: Vec<Vec<u8>> = cmd("SMEMBERS").arg(unescape_key(key)).query_async(conn).await?;
+            DiffSource::Set(values.into_iter().map(lossy).collect())
+        }
+        KeyType::Zset => {
+            let size: usize = cmd("ZCARD").arg(unescape_key(key)).query_async(conn).await?;
+            ensure_diff_size(size)?;
+            let values: Vec<(Vec<u8>, f64)> = cmd("ZRANGE")
+                .arg(unescape_key(key))
+                .arg(0)
+                .arg(-1)
+                .arg("WITHSCORES")
+                .query_async(conn)
+                .await?;
+            DiffSource::Zset(values.into_iter().map(|(m, s)| (lossy(m), s)).collect())
+        }
+        KeyType::List => {
+            let size: usize = cmd("LLEN").arg(unescape_key(key)).query_async(conn).await?;
+            ensure_diff_size(size)?;
+            let values: Vec<Vec<u8>> = cmd("LRANGE")
+                .arg(unescape_key(key))
+                .arg(0)
+                .arg(-1)
+                .query_async(conn)
+                .await?;
+            DiffSource::List(values.into_iter().map(lossy).collect())
+        }
+        _ if key_type == "none" => DiffSource::None,
+        _ => {
+            return Err(Error::Invalid {
+                message: format!("Unsupported key type for diff: {key_type}"),
+            });
+        }
+    };
+    Ok(source)
+}
+
+/// Line based diff of two texts.
+fn diff_text(left: &str, right: &str) -> Vec<DiffLine> {
+    let diff = TextDiff::from_lines(left, right);
+    diff.iter_all_changes()
+        .map(|change| {
+            let kind = match change.tag() {
+                ChangeTag::Equal => DiffKind::Unchanged,
+                ChangeTag::Insert => DiffKind::Added,
+                ChangeTag::Delete => DiffKind::Removed,
+            };
+            DiffLine {
+                kind,
+                left_no: change.old_index().map(|i| i + 1),
+                right_no: change.new_index().map(|i| i + 1),
+                text: change.value().trim_end_matches(['\r', '\n']).to_string().into(),
+            }
+        })
+        .collect()
+}
+
+/// Compare two keyed collections (hash fields or zset members).
+fn diff_map(left: BTreeMap<String, String>, mut right: BTreeMap<String, String>) -> Vec<DiffEntry> {
+    let mut entries = Vec::with_capacity(left.len().max(right.len()));
+    for (name, left_value) in left {
+        let entry = match right.remove(&name) {
+            Some(right_value) => DiffEntry {
+                kind: if left_value == right_value {
+                    DiffKind::Unchanged
+                } else {
+                    DiffKind::Changed
+                },
+                name: name.into(),
+                left: Some(left_value.into()),
+                right: Some(right_value.into()),
+            },
+            None => DiffEntry {
+                kind: DiffKind::Removed,
+                name: name.into(),
+                left: Some(left_value.into()),
+                right: None,
+            },
+        };
+        entries.push(entry);
+    }
+    for (name, right_value) in right {
+        entries.push(DiffEntry {
+            kind: DiffKind::Added,
+            name: name.into(),
+            left: None,
+            right: Some(right_value.into()),
+        });
+    }
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    entries
+}
+
+/// Compare two sets, members only.
+fn diff_set(left: Vec<String>, right: Vec<String>) -> Vec<DiffEntry> {
+    let to_map = |values: Vec<String>| {
+        values
+            .into_iter()
+            .map(|v| (v, String::new()))
+            .collect::<BTreeMap<_, _>>()
+    };
+    diff_map(to_map(left), to_map(right))
+        .into_iter()
+        .map(|mut entry| {
+            // show the member itself on the side(s) where it exists
+            entry.left = entry.left.map(|_| entry.name.clone());
+            entry.right = entry.right.map(|_| entry.name.clone());
+            entry
+        })
+        .collect()
+}
+
+/// Compare two lists by position, aligning inserted/removed elements.
+fn diff_list(left: Vec<String>, right: Vec<String>) -> Vec<DiffEntry> {
+    let ops = capture_diff_slices(Algorithm::Myers, &left, &right);
+    let mut entries = Vec::with_capacity(left.len().max(right.len()));
+    let removed = |index: usize| DiffEntry {
+        kind: DiffKind::Removed,
+        name: index.to_string().into(),
+        left: left.get(index).map(|v| v.clone().into()),
+        right: None,
+    };
+    let added = |index: usize| DiffEntry {
+        kind: DiffKind::Added,
+        name: index.to_string().into(),
+        left: None,
+        right: right.get(index).map(|v| v.clone().into()),
+    };
+    for op in ops {
+        match op {
+            DiffOp::Equal {
+                old_index,
+                new_index,
+                len,
+            } => {
+                for i in 0..len {
+                    entries.push(DiffEntry {
+                        kind: DiffKind::Unchanged,
+                        name: (new_index + i).to_string().into(),
+                        left: left.get(old_index + i).map(|v| v.clone().into()),
+                        right: right.get(new_index + i).map(|v| v.clone().into()),
+                    });
+                }
+            }
+            DiffOp::Delete { old_index, old_len, .. } => {
+                entries.extend((old_index..old_index + old_len).map(removed));
+            }
+            DiffOp::Insert { new_index, new_len, .. } => {
+                entries.extend((new_index..new_index + new_len).map(added));
+            }
+            DiffOp::Replace {
+                old_index,
+                old_len,
+                new_index,
+                new_len,
+            } => {
+                let paired = old_len.min(new_len);
+                for i in 0..paired {
+                    entries.push(DiffEntry {
+                        kind: DiffKind::Changed,
+                        name: (new_index + i).to_string().into(),
+                        left: left.get(old_index + i).map(|v| v.clone().into()),
+                        right: right.get(new_index + i).map(|v| v.clone().into()),
+                    });
+                }
+                entries.extend((old_index + paired..old_index + old_len).map(removed));
+                entries.extend((new_index + paired..new_index + new_len).map(added));
+            }
+        }
+    }
+    entries
+}
+
+fn compare(left_target: DiffTarget, left: DiffSource, right_target: DiffTarget, right: DiffSource) -> ValueDiff {
+    let left_type = left.key_type();
+    let right_type = right.key_type();
+    let data = match (left, right) {
+        (DiffSource::Hash(l), DiffSource::Hash(r)) => {
+            ValueDiffData::Structural(diff_map(l.into_iter().collect(), r.into_iter().collect()))
+        }
+        (DiffSource::Zset(l), DiffSource::Zset(r)) => {
+            let to_map = |values: Vec<(String, f64)>| {
+                values
+                    .into_iter()
+                    .map(|(member, score)| (member, score.to_string()))
+                    .collect::<BTreeMap<_, _>>()
+            };
+            ValueDiffData::Structural(diff_map(to_map(l), to_map(r)))
+        }
+        (DiffSource::Set(l), DiffSource::Set(r)) => ValueDiffData::Structural(diff_set(l, r)),
+        (DiffSource::List(l), DiffSource::List(r)) => ValueDiffData::Structural(diff_list(l, r)),
+        (l, r) => ValueDiffData::Text(diff_text(&l.to_text(), &r.to_text())),
+    };
+
+    let mut counts: AHashMap<DiffKind, usize> = AHashMap::new();
+    match &data {
+        ValueDiffData::Text(lines) => lines.iter().for_each(|l| *counts.entry(l.kind).or_default() += 1),
+        ValueDiffData::Structural(entries) => entries.iter().for_each(|e| *counts.entry(e.kind).or_default() += 1),
+    }
+
+    ValueDiff {
+        left: left_target,
+        right: right_target,
+        left_type,
+        right_type,
+        added: counts.get(&DiffKind::Added).copied().unwrap_or_default(),
+        removed: counts.get(&DiffKind::Removed).copied().unwrap_or_default(),
+        changed: counts.get(&DiffKind::Changed).copied().unwrap_or_default(),
+        data,
+    }
+}
+
+impl ZedisServerState {
+    /// Get the result of the last value comparison
+    pub fn value_diff(&self) -> Option<Arc<ValueDiff>> {
+        self.value_diff.clone()
+    }
+    /// Load two values and compare them.
+    ///
+    /// Both sides are loaded concurrently from their own server/db, so the same key
+    /// can be compared between two servers (e.g. staging and production).
+    pub fn diff_values(&mut self, left: DiffTarget, right: DiffTarget, cx: &mut Context<Self>) {
+        self.value_diff = None;
+        cx.notify();
+        self.spawn(
+            ServerTask::DiffValues,
+            move || async move {
+                let manager = get_connection_manager();
+                let (left_conn, right_conn) = futures::join!(
+                    manager.get_connection(&left.server_id, left.db),
+                    manager.get_connection(&right.server_id, right.db)
+                );
+                let (mut left_conn, mut right_conn) = (left_conn?, right_conn?);
+                let (left_value, right_value) = futures::join!(
+                    load_diff_source(&mut left_conn, &left.key),
+                    load_diff_source(&mut right_conn, &right.key)
+                );
+                Ok(compare(left, left_value?, right, right_value?))
+            },
+            move |this, result, cx| {
+                if let Ok(diff) = result {
+                    this.value_diff = Some(Arc::new(diff));
+                }
+                cx.emit(ServerEvent::ValueDiffLoaded);
+                cx.notify();
+            },
+            cx,
+        );
+    }
+}
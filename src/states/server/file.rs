@@ -0,0 +1,247 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! String value file transfer module.
+//!
+//! Values are streamed in chunks, so large values never need a single huge command:
+//! - Import: SET of the first chunk to a temporary key, SETRANGE for the rest, then
+//!   RENAME over the key (keeping its TTL), so an interrupted import changes nothing
+//! - Export: STRLEN, then GETRANGE chunk by chunk into the file
+
+use super::{
+    ServerEvent, ServerTask, ZedisServerState,
+    history::{record_history, string_inverse},
+    value::DataFormat,
+};
+use crate::{
+    connection::get_connection_manager,
+    error::Error,
+    helpers::unescape_key,
+    states::{NotificationAction, RedisBytesValue, i18n_editor},
+};
+use gpui::{SharedString, prelude::*};
+use redis::{cmd, pipe};
+use std::{
+    fs::File,
+    io::{BufWriter, Read, Write},
+    path::PathBuf,
+};
+use uuid::Uuid;
+
+/// Size of the chunks sent with SETRANGE and read with GETRANGE
+const CHUNK_SIZE: usize = 1024 * 1024;
+/// TTL in milliseconds of the temporary key of an import, removed if the import is interrupted
+const IMPORT_TEMP_KEY_TTL: u64 = 60 * 60 * 1000;
+
+/// Name of a temporary key in the same cluster slot as `key`, e.g. the key an import is written to.
+///
+/// In a cluster the key must be in the same slot for RENAME: the hash tag of the key
+/// is kept, or the whole key becomes the hash tag. `None` if neither is possible
+/// (no hash tag and a `}` in the key).
+pub(super) fn same_slot_temp_key(key: &[u8], cluster: bool, purpose: &str) -> Option<Vec<u8>> {
+    let suffix = format!(":zedis-{purpose}:{}", Uuid::now_v7());
+    let has_hash_tag = key.iter().position(|b| *b == b'{').is_some_and(|start| {
+        key[start + 1..]
+            .iter()
+            .position(|b| *b == b'}')
+            .is_some_and(|len| len > 0)
+    });
+    let mut temp_key = Vec::with_capacity(key.len() + suffix.len() + 2);
+    if !cluster || has_hash_tag {
+        temp_key.extend_from_slice(key);
+    } else if !key.contains(&b'}') {
+        temp_key.push(b'{');
+        temp_key.extend_from_slice(key);
+        temp_key.push(b'}');
+    } else {
+        return None;
+    }
+    temp_key.extend_from_slice(suffix.as_bytes());
+    Some(temp_key)
+}
+
+impl RedisBytesValue {
+    /// File extension for exporting the value, based on the detected mime type and format
+    pub fn file_extension(&self) -> &'static str {
+        let by_mime = match self.mime.as_ref().map(|mime| mime.as_str()) {
+            Some("image/png") => Some("png"),
+            Some("image/jpeg") => Some("jpg"),
+            Some("image/webp") => Some("webp"),
+            Some("image/gif") => Some("gif"),
+            Some("image/svg+xml") => Some("svg"),
+            Some("application/gzip") => Some("gz"),
+            Some("application/zstd") => Some("zst"),
+            Some("application/x-lz4") => Some("lz4"),
+            Some("application/x-snappy-framed") => Some("sz"),
+            Some("application/pdf") => Some("pdf"),
+            Some("application/zip") => Some("zip"),
+            _ => None,
+        };
+        by_mime.unwrap_or(match self.format {
+            DataFormat::Json => "json",
+            DataFormat::Text => "txt",
+            DataFormat::MessagePack => "msgpack",
+            DataFormat::Brotli => "br",
+            _ => "bin",
+        })
+    }
+}
+
+impl ZedisServerState {
+    /// Replace the value of a string key with the content of a file.
+    ///
+    /// The file is written to a temporary key, renamed over the key at the end,
+    /// so a failure never leaves a truncated value. The TTL of the key is kept.
+    pub fn import_value_from_file(&mut self, key: SharedString, path: PathBuf, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        // the loaded value restores the key if its DUMP is too large to keep
+        let original_bytes = self
+            .value
+            .as_ref()
+            .filter(|_| self.key.as_ref() == Some(&key))
+            .and_then(|value| value.bytes_value())
+            .filter(|value| !value.is_partial())
+            .map(|value| value.bytes.clone());
+        let key_clone = key.clone();
+        self.mark_key_written(key.clone());
+        self.spawn(
+            ServerTask::ImportValue,
+            move || async move {
+                let mut file = File::open(&path)?;
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = client.connection();
+                let raw_key = unescape_key(&key);
+                let temp_key =
+                    same_slot_temp_key(&raw_key, client.is_cluster(), "import").ok_or_else(|| Error::Invalid {
+                        message: format!("No temporary key in the same cluster slot as {key}"),
+                    })?;
+                let write_chunks = async {
+                    let mut conn = conn.clone();
+                    let mut buf = vec![0; CHUNK_SIZE];
+                    let mut offset = 0;
+                    loop {
+                        let n = file.read(&mut buf)?;
+                        // the first chunk is always written, so an empty file gives an empty value
+                        if offset == 0 {
+                            let _: () = cmd("SET")
+                                .arg(&temp_key)
+                                .arg(&buf[..n])
+                                .arg("PX")
+                                .arg(IMPORT_TEMP_KEY_TTL)
+                                .query_async(&mut conn)
+                                .await?;
+                        } else if n > 0 {
+                            let _: () = cmd("SETRANGE")
+                                .arg(&temp_key)
+                                .arg(offset)
+                                .arg(&buf[..n])
+                                .query_async(&mut conn)
+                                .await?;
+                        }
+                        if n == 0 {
+                            break;
+                        }
+                        offset += n;
+                    }
+                    Ok::<_, Error>(offset)
+                };
+                let size = match write_chunks.await {
+                    Ok(size) => size,
+                    Err(e) => {
+                        // the temporary key also expires by itself
+                        let _: Result<(), _> = cmd("DEL").arg(&temp_key).query_async(&mut conn).await;
+                        return Err(e);
+                    }
+                };
+
+                let inverse =
+                    original_bytes.map(|bytes| string_inverse(&key, &bytes, client.is_at_least_version("6.0.0")));
+                record_history(&mut conn, &server_id, db, &key, ServerTask::ImportValue, inverse).await;
+                let pttl: i64 = cmd("PTTL").arg(raw_key.as_ref()).query_async(&mut conn).await?;
+                let mut replace = pipe();
+                replace
+                    .atomic()
+                    .cmd("RENAME")
+                    .arg(&temp_key)
+                    .arg(raw_key.as_ref())
+                    .ignore();
+                // RENAME moves the TTL of the temporary key
+                if pttl > 0 {
+                    replace.cmd("PEXPIRE").arg(raw_key.as_ref()).arg(pttl).ignore();
+                } else {
+                    replace.cmd("PERSIST").arg(raw_key.as_ref()).ignore();
+                }
+                let _: () = replace.query_async(&mut conn).await?;
+                Ok(size)
+            },
+            move |this, result, cx| {
+                if result.is_ok() {
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(i18n_editor(
+                        cx,
+                        "import_value_success",
+                    ))));
+                    // reload the value if the key is still selected
+                    if this.key.as_ref() == Some(&key_clone) {
+                        this.select_key(key_clone, cx);
+                    }
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Write the value of a string key to a file.
+    pub fn export_value_to_file(&mut self, key: SharedString, path: PathBuf, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let display_path: SharedString = path.display().to_string().into();
+        self.spawn(
+            ServerTask::ExportValue,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let size: usize = cmd("STRLEN").arg(unescape_key(&key)).query_async(&mut conn).await?;
+                let mut writer = BufWriter::new(File::create(&path)?);
+                let mut start = 0;
+                while start < size {
+                    let end = (start + CHUNK_SIZE).min(size) - 1;
+                    let chunk: Vec<u8> = cmd("GETRANGE")
+                        .arg(unescape_key(&key))
+                        .arg(start)
+                        .arg(end)
+                        .query_async(&mut conn)
+                        .await?;
+                    // the value was shortened while exporting
+                    if chunk.is_empty() {
+                        break;
+                    }
+                    writer.write_all(&chunk)?;
+                    start += chunk.len();
+                }
+                writer.flush()?;
+                Ok(start)
+            },
+            move |_this, result, cx| {
+                if result.is_ok() {
+                    cx.emit(ServerEvent::Notification(
+                        NotificationAction::new_success(display_path)
+                            .with_title(i18n_editor(cx, "export_value_success")),
+                    ));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+}
@@ -20,25 +20,111 @@
 //! - Removing fields from a HASH (HDEL)
 //! - Filtering HASH fields with pattern matching
 //! - Efficient incremental loading for large HASHes
+//! - Field TTLs (Redis 7.4+): HPEXPIRETIME, HPEXPIRE, HPERSIST, and HGETEX/HSETEX on Redis 8.0+
 
 use super::{
     KeyType, RedisValueData, ServerTask, ZedisServerState,
-    value::{RedisHashValue, RedisValue, RedisValueStatus},
+    history::record_history,
+    staging::StagedCommand,
+    value::{RedisElement, RedisHashValue, RedisValue, RedisValueStatus},
 };
 use crate::{
     connection::{RedisAsyncConn, get_connection_manager},
     error::Error,
+    helpers::{escape_key, unescape_key},
     states::{NotificationAction, ServerEvent, i18n_hash_editor},
 };
+use bytes::Bytes;
+use chrono::Local;
 use gpui::{SharedString, prelude::*};
-use redis::cmd;
-use std::sync::Arc;
+use redis::{cmd, pipe};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
 /// Type alias for HSCAN result: (cursor, vec of (field, value) pairs as bytes)
 type HashScanValue = (u64, Vec<(Vec<u8>, Vec<u8>)>);
 
+/// First version supporting hash field expiration (HPEXPIRE, HPERSIST, HPEXPIRETIME)
+pub const FIELD_TTL_VERSION: &str = "7.4.0";
+/// First version supporting HGETEX and HSETEX
+const FIELD_TTL_EX_VERSION: &str = "8.0.0";
+
+/// New TTL of a hash field
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldTtl {
+    /// Keep the current TTL
+    Keep,
+    /// Remove the TTL, the field never expires
+    Persist,
+    /// Expire the field after the milliseconds
+    Expire(i64),
+}
+
+impl FieldTtl {
+    /// Parse a TTL entered by the user: seconds or a humantime duration.
+    ///
+    /// Empty, `--` or zero remove the TTL.
+    pub fn parse(ttl: &str) -> Result<Self> {
+        let ttl = ttl.trim();
+        if ttl.is_empty() || ttl == "--" {
+            return Ok(FieldTtl::Persist);
+        }
+        let duration = match ttl.parse::<u64>() {
+            Ok(secs) => Duration::from_secs(secs),
+            Err(_) => humantime::parse_duration(ttl).map_err(|e| Error::Invalid { message: e.to_string() })?,
+        };
+        if duration.is_zero() {
+            return Ok(FieldTtl::Persist);
+        }
+        Ok(FieldTtl::Expire(duration.as_millis() as i64))
+    }
+    /// Apply the TTL to the cached expire times of the fields
+    fn apply(self, expires: &mut HashMap<Bytes, i64>, field: &Bytes) {
+        match self {
+            FieldTtl::Keep => {}
+            FieldTtl::Persist => {
+                expires.remove(field);
+            }
+            FieldTtl::Expire(ms) => {
+                expires.insert(field.clone(), Local::now().timestamp_millis() + ms);
+            }
+        }
+    }
+}
+
+fn field_ttl_unsupported() -> Error {
+    Error::Invalid {
+        message: format!("Hash field TTL requires Redis {FIELD_TTL_VERSION} or later"),
+    }
+}
+
+/// Fetches the expire times (unix timestamp in milliseconds) of the fields with HPEXPIRETIME.
+///
+/// Fields without a TTL are not included.
+async fn get_hash_field_expires(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    values: &[(RedisElement, RedisElement)],
+) -> Result<HashMap<Bytes, i64>> {
+    if values.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let mut command = cmd("HPEXPIRETIME");
+    command.arg(unescape_key(key)).arg("FIELDS").arg(values.len());
+    for (field, _) in values {
+        command.arg(field.bytes.as_ref());
+    }
+    let expires: Vec<i64> = command.query_async(conn).await?;
+    Ok(values
+        .iter()
+        .zip(expires)
+        // -1: no TTL, -2: the field does not exist
+        .filter(|(_, expire_at)| *expire_at > 0)
+        .map(|((field, _), expire_at)| (field.bytes.clone(), expire_at))
+        .collect())
+}
+
 /// Retrieves HASH field-value pairs using Redis HSCAN command for cursor-based pagination.
 ///
 /// # Arguments
@@ -56,7 +142,7 @@ async fn get_redis_hash_value(
     keyword: Option<SharedString>,
     cursor: u64,
     count: usize,
-) -> Result<(u64, Vec<(SharedString, SharedString)>)> {
+) -> Result<(u64, Vec<(RedisElement, RedisElement)>)> {
     // Build pattern: wrap keyword with wildcards or match all fields
     let pattern = keyword
         .as_ref()
@@ -65,7 +151,7 @@ async fn get_redis_hash_value(
 
     // Execute HSCAN with MATCH and COUNT options
     let (next_cursor, raw_values): HashScanValue = cmd("HSCAN")
-        .arg(key)
+        .arg(unescape_key(key))
         .arg(cursor)
         .arg("MATCH")
         .arg(pattern)
@@ -79,15 +165,10 @@ async fn get_redis_hash_value(
         return Ok((next_cursor, vec![]));
     }
 
-    // Convert bytes to UTF-8 strings (lossy conversion for non-UTF8 data)
+    // Keep raw bytes, so binary fields and values are not corrupted
     let values = raw_values
-        .iter()
-        .map(|(field, value)| {
-            (
-                String::from_utf8_lossy(field).to_string().into(),
-                String::from_utf8_lossy(value).to_string().into(),
-            )
-        })
+        .into_iter()
+        .map(|(field, value)| (RedisElement::new(field), RedisElement::new(value)))
         .collect();
 
     Ok((next_cursor, values))
@@ -101,12 +182,13 @@ async fn get_redis_hash_value(
 /// # Arguments
 /// * `conn` - Redis async connection
 /// * `key` - The HASH key to load
+/// * `field_ttl` - Whether the server supports field TTLs, fetched with HPEXPIRETIME
 ///
 /// # Returns
 /// A `RedisValue` containing HASH metadata and initial field-value pairs
-pub(crate) async fn first_load_hash_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisValue> {
+pub(crate) async fn first_load_hash_value(conn: &mut RedisAsyncConn, key: &str, field_ttl: bool) -> Result<RedisValue> {
     // Get total number of fields in the HASH
-    let size: usize = cmd("HLEN").arg(key).query_async(conn).await?;
+    let size: usize = cmd("HLEN").arg(unescape_key(key)).query_async(conn).await?;
 
     // Load first batch of field-value pairs (up to 100)
     let (cursor, values) = get_redis_hash_value(conn, key, None, 0, 100).await?;
@@ -114,6 +196,12 @@ pub(crate) async fn first_load_hash_value(conn: &mut RedisAsyncConn, key: &str)
     // If cursor is 0, all values have been loaded in one iteration
     let done = cursor == 0;
 
+    let expires = if field_ttl {
+        get_hash_field_expires(conn, key, &values).await?
+    } else {
+        HashMap::new()
+    };
+
     Ok(RedisValue {
         key_type: KeyType::Hash,
         data: Some(RedisValueData::Hash(Arc::new(RedisHashValue {
@@ -121,6 +209,8 @@ pub(crate) async fn first_load_hash_value(conn: &mut RedisAsyncConn, key: &str)
             size,
             values,
             done,
+            field_ttl,
+            expires,
             ..Default::default()
         }))),
         ..Default::default()
@@ -138,20 +228,160 @@ impl ZedisServerState {
     /// * `new_value` - The value to set for the field
     /// * `cx` - GPUI context for spawning async tasks and UI updates
     pub fn add_hash_value(&mut self, new_field: SharedString, new_value: SharedString, cx: &mut Context<Self>) {
-        self.add_or_update_hash_value(new_field, new_value, cx);
+        self.add_or_update_hash_value(
+            RedisElement::from_text(&new_field),
+            RedisElement::from_text(&new_value),
+            FieldTtl::Keep,
+            cx,
+        );
     }
     /// Updates a field-value pair in the Redis HASH.
     ///
     /// Uses HSET command to update the value of the specified field.
     ///
     /// # Arguments
-    /// * `new_field` - The field name to update
-    /// * `new_value` - The value to set for the field
+    /// * `field` - The field to update
+    /// * `original_value` - The current value of the field
+    /// * `new_value` - The edited text of the value (escaped for binary values)
+    /// * `ttl` - The edited TTL of the field, `None` if it is unchanged
     /// * `cx` - GPUI context for spawning async tasks and UI updates
-    pub fn update_hash_value(&mut self, new_field: SharedString, new_value: SharedString, cx: &mut Context<Self>) {
-        self.add_or_update_hash_value(new_field, new_value, cx);
+    pub fn update_hash_value(
+        &mut self,
+        field: RedisElement,
+        original_value: &RedisElement,
+        new_value: SharedString,
+        ttl: Option<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        let ttl = match ttl.map(|ttl| FieldTtl::parse(&ttl)).transpose() {
+            Ok(ttl) => ttl,
+            Err(e) => {
+                self.add_error_message(ServerTask::UpdateHashFieldTtl.as_str().to_string(), e.to_string(), cx);
+                return;
+            }
+        };
+        let new_bytes = original_value.bytes_from_text(&new_value);
+        // only the TTL has been changed
+        if let Some(ttl) = ttl
+            && new_bytes == original_value.bytes.as_ref()
+        {
+            self.update_hash_field_ttl(field, ttl, cx);
+            return;
+        }
+        self.add_or_update_hash_value(field, RedisElement::new(new_bytes), ttl.unwrap_or(FieldTtl::Keep), cx);
     }
-    fn add_or_update_hash_value(&mut self, new_field: SharedString, new_value: SharedString, cx: &mut Context<Self>) {
+    /// Sets or removes the TTL of a hash field (Redis 7.4+).
+    ///
+    /// Uses HGETEX on Redis 8.0+, otherwise HPEXPIRE or HPERSIST.
+    pub fn update_hash_field_ttl(&mut self, field: RedisElement, ttl: FieldTtl, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        value.status = RedisValueStatus::Updating;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        let field_bytes = field.bytes.clone();
+
+        self.spawn(
+            ServerTask::UpdateHashFieldTtl,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                if !client.is_at_least_version(FIELD_TTL_VERSION) {
+                    return Err(field_ttl_unsupported());
+                }
+                let mut conn = client.connection();
+                record_history(&mut conn, &server_id, db, &key, ServerTask::UpdateHashFieldTtl, None).await;
+
+                if client.is_at_least_version(FIELD_TTL_EX_VERSION) {
+                    let mut command = cmd("HGETEX");
+                    command.arg(unescape_key(&key));
+                    match ttl {
+                        FieldTtl::Expire(ms) => command.arg("PX").arg(ms),
+                        _ => command.arg("PERSIST"),
+                    };
+                    let _: Vec<Option<Vec<u8>>> = command
+                        .arg("FIELDS")
+                        .arg(1)
+                        .arg(field.bytes.as_ref())
+                        .query_async(&mut conn)
+                        .await?;
+                } else {
+                    let mut command = match ttl {
+                        FieldTtl::Expire(ms) => {
+                            let mut command = cmd("HPEXPIRE");
+                            command.arg(unescape_key(&key)).arg(ms);
+                            command
+                        }
+                        _ => {
+                            let mut command = cmd("HPERSIST");
+                            command.arg(unescape_key(&key));
+                            command
+                        }
+                    };
+                    let _: Vec<i64> = command
+                        .arg("FIELDS")
+                        .arg(1)
+                        .arg(field.bytes.as_ref())
+                        .query_async(&mut conn)
+                        .await?;
+                }
+                Ok(())
+            },
+            move |this, result, cx| {
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                    if result.is_ok()
+                        && let Some(RedisValueData::Hash(hash_data)) = value.data.as_mut()
+                    {
+                        ttl.apply(&mut Arc::make_mut(hash_data).expires, &field_bytes);
+                        cx.emit(ServerEvent::ValueUpdated(key_clone));
+                    }
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    fn add_or_update_hash_value(
+        &mut self,
+        new_field: RedisElement,
+        new_value: RedisElement,
+        ttl: FieldTtl,
+        cx: &mut Context<Self>,
+    ) {
+        // HSET discards the TTL of the field, so a kept TTL has to be set again
+        let keep_expire_at = self
+            .value
+            .as_ref()
+            .and_then(|value| value.hash_value())
+            .and_then(|hash| hash.expires.get(&new_field.bytes).copied())
+            .filter(|_| ttl == FieldTtl::Keep);
+        if let Some(key) = self.staging_key() {
+            let mut commands = vec![
+                StagedCommand::new(&key, "HSET")
+                    .arg(new_field.bytes.as_ref())
+                    .arg(new_value.bytes.as_ref()),
+            ];
+            let expire = match (keep_expire_at, ttl) {
+                (Some(expire_at), _) => Some(("HPEXPIREAT", expire_at)),
+                (None, FieldTtl::Expire(ms)) => Some(("HPEXPIRE", ms)),
+                _ => None,
+            };
+            if let Some((name, value)) = expire {
+                commands.push(
+                    StagedCommand::new(&key, name)
+                        .arg(value.to_string())
+                        .arg("FIELDS")
+                        .arg("1")
+                        .arg(new_field.bytes.as_ref()),
+                );
+            }
+            self.stage_commands(commands, cx);
+            return;
+        }
         // Early return if no key/value is selected
         let Some((key, value)) = self.try_get_mut_key_value() else {
             return;
@@ -168,18 +398,71 @@ impl ZedisServerState {
         let new_value_clone = new_value.clone();
 
         self.spawn(
-            ServerTask::AddSetValue,
+            ServerTask::AddHashValue,
             // Async operation: execute HSET on Redis
             move || async move {
-                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = client.connection();
+                record_history(&mut conn, &server_id, db, &key, ServerTask::AddHashValue, None).await;
+
+                let with_ttl = ttl != FieldTtl::Keep || keep_expire_at.is_some();
+                if with_ttl && client.is_at_least_version(FIELD_TTL_EX_VERSION) {
+                    // HSETEX sets the value and the TTL of the field at once,
+                    // without TTL option the TTL of the field is removed.
+                    // It does not tell whether the field was created, HEXISTS does
+                    let mut pipeline = pipe();
+                    pipeline
+                        .atomic()
+                        .cmd("HEXISTS")
+                        .arg(unescape_key(&key))
+                        .arg(new_field.bytes.as_ref());
+                    let command = pipeline.cmd("HSETEX").arg(unescape_key(&key));
+                    match ttl {
+                        FieldTtl::Keep => {
+                            command.arg("KEEPTTL");
+                        }
+                        FieldTtl::Expire(ms) => {
+                            command.arg("PX").arg(ms);
+                        }
+                        FieldTtl::Persist => {}
+                    }
+                    command
+                        .arg("FIELDS")
+                        .arg(1)
+                        .arg(new_field.bytes.as_ref())
+                        .arg(new_value.bytes.as_ref())
+                        .ignore();
+                    let (exists,): (bool,) = pipeline.query_async(&mut conn).await?;
+                    return Ok(usize::from(!exists));
+                }
+                if with_ttl && !client.is_at_least_version(FIELD_TTL_VERSION) {
+                    return Err(field_ttl_unsupported());
+                }
 
                 // HSET returns 1 if new field created, 0 if existing field updated
-                let count: usize = cmd("HSET")
-                    .arg(key.as_str())
-                    .arg(new_field.as_str())
-                    .arg(new_value.as_str())
-                    .query_async(&mut conn)
-                    .await?;
+                let mut pipeline = pipe();
+                pipeline
+                    .atomic()
+                    .cmd("HSET")
+                    .arg(unescape_key(&key))
+                    .arg(new_field.bytes.as_ref())
+                    .arg(new_value.bytes.as_ref());
+                let expire = match (keep_expire_at, ttl) {
+                    (Some(expire_at), _) => Some(("HPEXPIREAT", expire_at)),
+                    (None, FieldTtl::Expire(ms)) => Some(("HPEXPIRE", ms)),
+                    _ => None,
+                };
+                if let Some((name, value)) = expire {
+                    pipeline
+                        .cmd(name)
+                        .arg(unescape_key(&key))
+                        .arg(value)
+                        .arg("FIELDS")
+                        .arg(1)
+                        .arg(new_field.bytes.as_ref())
+                        .ignore();
+                }
+                let (count,): (usize,) = pipeline.query_async(&mut conn).await?;
                 Ok(count)
             },
             // UI callback: handle result and update local state
@@ -199,13 +482,18 @@ impl ZedisServerState {
                         // Increment size only if new field was created
                         hash.size += count;
 
-                        // Update existing field value in local state if field already exists
-                        for item in hash.values.iter_mut() {
-                            if item.0 == new_field_clone {
-                                item.1 = new_value_clone.clone();
-                                break;
-                            }
+                        // Update existing field value in local state if field already exists,
+                        // a new field is appended to the loaded fields
+                        match hash
+                            .values
+                            .iter_mut()
+                            .find(|item| item.0.bytes == new_field_clone.bytes)
+                        {
+                            Some(item) => item.1 = new_value_clone.clone(),
+                            None if count > 0 => hash.values.push((new_field_clone.clone(), new_value_clone.clone())),
+                            None => {}
                         }
+                        ttl.apply(&mut hash.expires, &new_field_clone.bytes);
 
                         // Show different notifications based on operation type
                         if count == 0 {
@@ -248,6 +536,7 @@ impl ZedisServerState {
         let new_hash = RedisHashValue {
             keyword: Some(keyword),
             size: hash.size,
+            field_ttl: hash.field_ttl,
             ..Default::default()
         };
         value.data = Some(RedisValueData::Hash(Arc::new(new_hash)));
@@ -263,10 +552,22 @@ impl ZedisServerState {
     /// # Arguments
     /// * `remove_field` - The field name to remove from the HASH
     /// * `cx` - GPUI context for spawning async tasks and UI updates
-    pub fn remove_hash_value(&mut self, remove_field: SharedString, cx: &mut Context<Self>) {
+    pub fn remove_hash_value(&mut self, remove_field: RedisElement, cx: &mut Context<Self>) {
+        if let Some(key) = self.staging_key() {
+            self.stage_commands(
+                vec![StagedCommand::new(&key, "HDEL").arg(remove_field.bytes.as_ref())],
+                cx,
+            );
+            return;
+        }
         let Some((key, value)) = self.try_get_mut_key_value() else {
             return;
         };
+        // value of the removed field, used to build the inverse operation
+        let remove_value = value
+            .hash_value()
+            .and_then(|hash| hash.values.iter().find(|(field, _)| field.bytes == remove_field.bytes))
+            .map(|(_, value)| value.bytes.clone());
 
         // Update UI state to show loading
         value.status = RedisValueStatus::Loading;
@@ -274,7 +575,7 @@ impl ZedisServerState {
 
         let server_id = self.server_id.clone();
         let db = self.db;
-        let remove_field_clone = remove_field.clone();
+        let remove_field_bytes = remove_field.bytes.clone();
         let key_clone = key.clone();
 
         self.spawn(
@@ -282,11 +583,20 @@ impl ZedisServerState {
             // Async operation: execute HDEL on Redis
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let inverse = remove_value.map(|value| {
+                    vec![
+                        "HSET".to_string(),
+                        key.to_string(),
+                        escape_key(&remove_field.bytes),
+                        escape_key(&value),
+                    ]
+                });
+                record_history(&mut conn, &server_id, db, &key, ServerTask::RemoveHashValue, inverse).await;
 
                 // HDEL returns number of fields removed (0 if doesn't exist, 1 if removed)
                 let count: usize = cmd("HDEL")
-                    .arg(key.as_str())
-                    .arg(remove_field.as_str())
+                    .arg(unescape_key(&key))
+                    .arg(remove_field.bytes.as_ref())
                     .query_async(&mut conn)
                     .await?;
                 Ok(count)
@@ -301,7 +611,7 @@ impl ZedisServerState {
                         let hash = Arc::make_mut(hash_data);
 
                         // Remove from local field-value list
-                        hash.values.retain(|(field, _)| field != &remove_field_clone);
+                        hash.values.retain(|(field, _)| field.bytes != remove_field_bytes);
 
                         // Decrease HASH size by number of removed fields
                         hash.size -= count;
@@ -335,14 +645,15 @@ impl ZedisServerState {
         value.status = RedisValueStatus::Loading;
         cx.notify();
 
-        // Extract current cursor and filter keyword from HASH state
-        let (cursor, keyword) = match value.hash_value() {
-            Some(hash) => (hash.cursor, hash.keyword.clone()),
+        // Extract current cursor, filter keyword and field TTL support from HASH state
+        let (cursor, keyword, field_ttl) = match value.hash_value() {
+            Some(hash) => (hash.cursor, hash.keyword.clone(), hash.field_ttl),
             None => return,
         };
 
         let server_id = self.server_id.clone();
         let db = self.db;
+        let written = self.is_key_written(&key);
         cx.emit(ServerEvent::ValuePaginationStarted(key.clone()));
 
         let key_clone = key.clone();
@@ -351,17 +662,25 @@ impl ZedisServerState {
             ServerTask::LoadMoreValue,
             // Async operation: fetch next batch using HSCAN
             move || async move {
-                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut conn = get_connection_manager()
+                    .get_value_connection(&server_id, db, written)
+                    .await?;
 
                 // Use larger batch size when filtering to reduce round trips
                 let count = if keyword.is_some() { 1000 } else { 100 };
 
-                get_redis_hash_value(&mut conn, &key, keyword, cursor, count).await
+                let (cursor, values) = get_redis_hash_value(&mut conn, &key, keyword, cursor, count).await?;
+                let expires = if field_ttl {
+                    get_hash_field_expires(&mut conn, &key, &values).await?
+                } else {
+                    HashMap::new()
+                };
+                Ok((cursor, values, expires))
             },
             // UI callback: merge results into local state
             move |this, result, cx| {
                 let mut should_load_more = false;
-                if let Ok((new_cursor, new_values)) = result
+                if let Ok((new_cursor, new_values, new_expires)) = result
                     && let Some(RedisValueData::Hash(hash_data)) = this.value.as_mut().and_then(|v| v.data.as_mut())
                 {
                     let hash = Arc::make_mut(hash_data);
@@ -376,6 +695,7 @@ impl ZedisServerState {
                     if !new_values.is_empty() {
                         hash.values.extend(new_values);
                     }
+                    hash.expires.extend(new_expires);
                     if !hash.done && hash.values.len() < 50 {
                         should_load_more = true;
                     }
@@ -0,0 +1,364 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Edit history module.
+//!
+//! Before every write zedis performs, the prior state of the key is captured:
+//! - With DUMP (+ PTTL), reverted with RESTORE ... REPLACE
+//! - With an inverse command built from the loaded value, used when the value
+//!   is too large to keep (checked with MEMORY USAGE before the DUMP is sent)
+//!
+//! Records are persisted per server in `history/<server_id>.toml` under the
+//! config directory, keeping the most recent MAX_HISTORY_RECORDS entries.
+
+use super::{ServerEvent, ServerTask, ZedisServerState};
+use crate::{
+    connection::{RedisAsyncConn, get_connection_manager},
+    error::Error,
+    helpers::{escape_key, get_or_create_config_dir, unescape_key, unix_ts},
+    states::{NotificationAction, i18n_history},
+};
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use gpui::{SharedString, prelude::*};
+use parking_lot::Mutex;
+use redis::{cmd, pipe};
+use serde::{Deserialize, Serialize};
+use std::{env, fs, path::PathBuf, sync::Arc, sync::LazyLock};
+use tracing::error;
+use uuid::Uuid;
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Maximum number of records kept per server
+const MAX_HISTORY_RECORDS: usize = 200;
+/// DUMP payloads larger than this are not kept (inverse command is used instead)
+const MAX_SNAPSHOT_SIZE: usize = 512 * 1024;
+/// Elements of aggregate values assumed when MEMORY USAGE is not available
+const ESTIMATED_ELEMENT_SIZE: usize = 16;
+
+/// Serializes read-modify-write of the history files.
+static HISTORY_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+/// A single write performed by zedis and the state needed to revert it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    pub id: String,
+    pub server_id: String,
+    pub db: usize,
+    pub key: String,
+    /// Operation name (ServerTask::as_str)
+    pub operation: String,
+    /// OS user who performed the write
+    pub user: String,
+    /// Unix timestamp in seconds
+    pub created_at: i64,
+    /// Whether the key existed before the write
+    pub existed: bool,
+    /// Base64 encoded DUMP payload of the prior value
+    pub dump: Option<String>,
+    /// TTL in milliseconds of the prior value (0 means no expire)
+    #[serde(default)]
+    pub pttl: i64,
+    /// Inverse command (command name followed by arguments)
+    pub inverse: Option<Vec<String>>,
+    /// Whether the inverse arguments are escaped (see `escape_key`), so binary values are kept
+    #[serde(default)]
+    pub escaped: bool,
+}
+
+impl HistoryRecord {
+    /// Whether this record holds enough information to be reverted.
+    pub fn revertible(&self) -> bool {
+        !self.existed || self.dump.is_some() || self.inverse.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct HistoryRecords {
+    records: Vec<HistoryRecord>,
+}
+
+fn get_history_file(server_id: &str) -> Result<PathBuf> {
+    let dir = get_or_create_config_dir()?.join("history");
+    if !dir.exists() {
+        fs::create_dir_all(&dir)?;
+    }
+    Ok(dir.join(format!("{server_id}.toml")))
+}
+
+/// Load the persisted history of a server, newest first.
+pub fn get_history(server_id: &str) -> Result<Vec<HistoryRecord>> {
+    let path = get_history_file(server_id)?;
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let value = fs::read_to_string(path)?;
+    if value.is_empty() {
+        return Ok(vec![]);
+    }
+    let history: HistoryRecords = toml::from_str(&value)?;
+    Ok(history.records)
+}
+
+fn save_history(server_id: &str, records: Vec<HistoryRecord>) -> Result<()> {
+    let path = get_history_file(server_id)?;
+    let value = toml::to_string(&HistoryRecords { records })?;
+    fs::write(path, value)?;
+    Ok(())
+}
+
+fn push_history_record(record: HistoryRecord) -> Result<()> {
+    let _guard = HISTORY_LOCK.lock();
+    let mut records = get_history(&record.server_id)?;
+    let server_id = record.server_id.clone();
+    records.insert(0, record);
+    records.truncate(MAX_HISTORY_RECORDS);
+    save_history(&server_id, records)
+}
+
+fn current_user() -> String {
+    env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .unwrap_or_else(|_| "--".to_string())
+}
+
+/// Estimated size of the value before it is dumped, `None` if the key does not exist.
+///
+/// MEMORY USAGE is used (sampled for aggregate values), when it is not available
+/// (e.g. disabled by the provider), the length of the value is used instead.
+async fn estimated_size(conn: &mut RedisAsyncConn, key: &str) -> Result<Option<usize>> {
+    let key = unescape_key(key);
+    let usage: Result<Option<usize>, _> = cmd("MEMORY").arg("USAGE").arg(&key).query_async(conn).await;
+    if let Ok(usage) = usage {
+        return Ok(usage);
+    }
+    let key_type: String = cmd("TYPE").arg(&key).query_async(conn).await?;
+    let length_command = match key_type.as_str() {
+        "none" => return Ok(None),
+        "string" => "STRLEN",
+        "list" => "LLEN",
+        "set" => "SCARD",
+        "zset" => "ZCARD",
+        "hash" => "HLEN",
+        "stream" => "XLEN",
+        // unknown types are never dumped
+        _ => return Ok(Some(usize::MAX)),
+    };
+    let length: usize = cmd(length_command).arg(&key).query_async(conn).await?;
+    if length_command == "STRLEN" {
+        Ok(Some(length))
+    } else {
+        Ok(Some(length.saturating_mul(ESTIMATED_ELEMENT_SIZE)))
+    }
+}
+
+async fn capture(
+    conn: &mut RedisAsyncConn,
+    server_id: &str,
+    db: usize,
+    key: &str,
+    task: ServerTask,
+    inverse: Option<Vec<String>>,
+) -> Result<HistoryRecord> {
+    let size = estimated_size(conn, key).await?;
+    let existed = size.is_some();
+    // large values are not dumped at all, the inverse command is kept instead
+    let (dump, pttl): (Option<Vec<u8>>, i64) = if size.is_some_and(|size| size <= MAX_SNAPSHOT_SIZE) {
+        pipe()
+            .cmd("DUMP")
+            .arg(unescape_key(key))
+            .cmd("PTTL")
+            .arg(unescape_key(key))
+            .query_async(conn)
+            .await?
+    } else {
+        let pttl: i64 = cmd("PTTL").arg(unescape_key(key)).query_async(conn).await?;
+        (None, pttl)
+    };
+    // the estimate is approximate, the payload is checked again
+    let dump = dump
+        .filter(|dump| dump.len() <= MAX_SNAPSHOT_SIZE)
+        .map(|dump| BASE64.encode(dump));
+    // the inverse command is only needed when the DUMP payload is not kept
+    let inverse = if dump.is_some() { None } else { inverse };
+    Ok(HistoryRecord {
+        id: Uuid::now_v7().to_string(),
+        server_id: server_id.to_string(),
+        db,
+        key: key.to_string(),
+        operation: task.as_str().to_string(),
+        user: current_user(),
+        created_at: unix_ts(),
+        existed,
+        dump,
+        pttl: pttl.max(0),
+        inverse,
+        escaped: true,
+    })
+}
+
+/// Inverse command restoring a string value loaded in full, `SET key value [KEEPTTL]`.
+pub(crate) fn string_inverse(key: &str, bytes: &[u8], keep_ttl: bool) -> Vec<String> {
+    let mut inverse = vec!["SET".to_string(), key.to_string(), escape_key(bytes)];
+    if keep_ttl {
+        inverse.push("KEEPTTL".to_string());
+    }
+    inverse
+}
+
+/// Capture the current state of a key before it is modified, without saving it.
+///
+/// Used when the write may not happen (e.g. an aborted MULTI/EXEC),
+/// the snapshot is saved with `save_history_snapshot` once the write succeeded.
+/// Failures are logged and return `None`.
+pub(crate) async fn take_history_snapshot(
+    conn: &mut RedisAsyncConn,
+    server_id: &str,
+    db: usize,
+    key: &str,
+    task: ServerTask,
+    inverse: Option<Vec<String>>,
+) -> Option<HistoryRecord> {
+    match capture(conn, server_id, db, key, task, inverse).await {
+        Ok(record) => Some(record),
+        Err(e) => {
+            error!(error = %e, key, "record history failed");
+            None
+        }
+    }
+}
+
+/// Save a snapshot taken with `take_history_snapshot`.
+pub(crate) fn save_history_snapshot(snapshot: Option<HistoryRecord>) {
+    let Some(record) = snapshot else {
+        return;
+    };
+    if let Err(e) = push_history_record(record) {
+        error!(error = %e, "record history failed");
+    }
+}
+
+/// Capture the current state of a key before it is modified.
+///
+/// Arguments of the inverse command must be escaped with `escape_key`.
+/// Failures are logged but never block the write itself.
+pub(crate) async fn record_history(
+    conn: &mut RedisAsyncConn,
+    server_id: &str,
+    db: usize,
+    key: &str,
+    task: ServerTask,
+    inverse: Option<Vec<String>>,
+) {
+    save_history_snapshot(take_history_snapshot(conn, server_id, db, key, task, inverse).await);
+}
+
+impl ZedisServerState {
+    /// Get the loaded edit history of the current server
+    pub fn history(&self) -> Arc<Vec<HistoryRecord>> {
+        self.history.clone()
+    }
+    /// Load the edit history of the current server
+    pub fn load_history(&mut self, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        self.spawn(
+            ServerTask::LoadHistory,
+            move || async move { get_history(&server_id) },
+            move |this, result, cx| {
+                if let Ok(records) = result {
+                    this.history = Arc::new(records);
+                }
+                cx.emit(ServerEvent::HistoryUpdated);
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Revert a key to the state captured in a history record.
+    ///
+    /// The revert itself is recorded, so it can be reverted again.
+    pub fn revert_history(&mut self, id: SharedString, cx: &mut Context<Self>) {
+        let Some(record) = self.history.iter().find(|item| item.id == id.as_str()).cloned() else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let key: SharedString = record.key.clone().into();
+        if record.db == self.db {
+            self.mark_key_written(key.clone());
+        }
+        self.spawn(
+            ServerTask::RevertHistory,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, record.db).await?;
+                record_history(
+                    &mut conn,
+                    &server_id,
+                    record.db,
+                    &record.key,
+                    ServerTask::RevertHistory,
+                    None,
+                )
+                .await;
+                if !record.existed {
+                    let _: () = cmd("DEL").arg(unescape_key(&record.key)).query_async(&mut conn).await?;
+                } else if let Some(dump) = &record.dump {
+                    let payload = BASE64
+                        .decode(dump)
+                        .map_err(|e| Error::Invalid { message: e.to_string() })?;
+                    let _: () = cmd("RESTORE")
+                        .arg(unescape_key(&record.key))
+                        .arg(record.pttl)
+                        .arg(payload)
+                        .arg("REPLACE")
+                        .query_async(&mut conn)
+                        .await?;
+                } else if let Some(inverse) = record.inverse.as_ref().filter(|args| !args.is_empty()) {
+                    // inverse commands are `COMMAND key args...`
+                    let mut command = cmd(&inverse[0]);
+                    if let Some((key, args)) = inverse[1..].split_first() {
+                        command.arg(unescape_key(key));
+                        for arg in args {
+                            if record.escaped {
+                                command.arg(unescape_key(arg));
+                            } else {
+                                command.arg(arg);
+                            }
+                        }
+                    }
+                    let _: () = command.query_async(&mut conn).await?;
+                } else {
+                    return Err(Error::Invalid {
+                        message: format!("History record {} can not be reverted", record.id),
+                    });
+                }
+                get_history(&server_id)
+            },
+            move |this, result, cx| {
+                if let Ok(records) = result {
+                    this.history = Arc::new(records);
+                    cx.emit(ServerEvent::HistoryUpdated);
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                        i18n_history(cx, "revert_success"),
+                    )));
+                    // reload the value if the reverted key is being viewed
+                    if this.key.as_ref() == Some(&key) {
+                        this.select_key(key, cx);
+                    }
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+}
@@ -14,7 +14,8 @@
 
 use super::{
     ServerEvent, ServerTask, ZedisServerState,
-    hash::first_load_hash_value,
+    hash::{FIELD_TTL_VERSION, first_load_hash_value},
+    history::record_history,
     list::first_load_list_value,
     set::first_load_set_value,
     string::get_redis_value,
@@ -23,18 +24,148 @@ use super::{
 };
 use crate::states::ZedisGlobalStore;
 use crate::{
-    connection::{QueryMode, get_connection_manager},
+    connection::{KeyFilter, QueryMode, RedisClient, get_connection_manager},
     error::Error,
-    helpers::unix_ts,
+    helpers::{escape_glob, find_protobuf_message, normalize_key, unescape_key, unix_ts},
 };
-use futures::{StreamExt, stream};
+use futures::{StreamExt, TryStreamExt, stream};
 use gpui::{SharedString, prelude::*};
 use redis::{cmd, pipe};
+use regex::Regex;
 use std::time::Duration;
-use tracing::debug;
+use tracing::{debug, error};
 use uuid::Uuid;
 
 const DEFAULT_SCAN_RESULT_MAX: usize = 1_000;
+/// SCAN supports the TYPE option since Redis 6.0.
+const SCAN_TYPE_VERSION: &str = "6.0.0";
+/// Number of keys checked by one pipeline when filtering the scanned keys
+const FILTER_BATCH_SIZE: usize = 500;
+/// Number of batches of keys checked concurrently
+const FILTER_CONCURRENCY: usize = 4;
+
+/// Builds the SCAN match pattern of the keyword for the query mode.
+///
+/// Regex mode scans all keys, the expression is applied client-side.
+fn get_scan_pattern(query_mode: QueryMode, keyword: &str) -> String {
+    if keyword.is_empty() {
+        return "*".to_string();
+    }
+    match query_mode {
+        QueryMode::Suffix => format!("*{keyword}"),
+        QueryMode::Regex => "*".to_string(),
+        _ => format!("*{keyword}*"),
+    }
+}
+
+/// Compiles the keyword as a regular expression in regex mode.
+fn get_keyword_regex(query_mode: QueryMode, keyword: &str) -> Result<Option<Regex>, regex::Error> {
+    if query_mode != QueryMode::Regex || keyword.is_empty() {
+        return Ok(None);
+    }
+    Regex::new(keyword).map(Some)
+}
+
+/// Scans keys with the key type of the filter, using SCAN TYPE if the server supports it.
+async fn scan_with_filter(
+    client: &RedisClient,
+    cursors: Option<Vec<u64>>,
+    pattern: &str,
+    count: u64,
+    filter: &KeyFilter,
+) -> Result<(Vec<u64>, Vec<SharedString>), Error> {
+    let key_type = filter
+        .key_type
+        .as_deref()
+        .filter(|_| client.is_at_least_version(SCAN_TYPE_VERSION));
+    if let Some(cursors) = cursors {
+        client.scan(cursors, pattern, count, key_type).await
+    } else {
+        client.first_scan(pattern, count, key_type).await
+    }
+}
+
+/// Keeps the scanned keys matching the regex and the key filter.
+///
+/// TTL and memory filters (and the key type on servers without SCAN TYPE)
+/// are checked with one pipeline per check for each batch of keys, the batches concurrently.
+/// A failed check (e.g. MEMORY USAGE is not supported) fails the scan.
+async fn filter_scanned_keys(
+    client: &RedisClient,
+    keys: Vec<SharedString>,
+    regex: Option<&Regex>,
+    filter: &KeyFilter,
+) -> Result<Vec<SharedString>, Error> {
+    let keys: Vec<SharedString> = if let Some(regex) = regex {
+        keys.into_iter().filter(|key| regex.is_match(key)).collect()
+    } else {
+        keys
+    };
+    let check_type = filter.key_type.is_some() && !client.is_at_least_version(SCAN_TYPE_VERSION);
+    let check_ttl = filter.no_ttl || filter.expires_within.is_some();
+    if keys.is_empty() || (!check_type && !filter.needs_key_check()) {
+        return Ok(keys);
+    }
+    let conn = client.read_connection();
+    let batches: Vec<Vec<SharedString>> = keys.chunks(FILTER_BATCH_SIZE).map(|batch| batch.to_vec()).collect();
+    let batches: Vec<Vec<SharedString>> = stream::iter(batches)
+        .map(|batch: Vec<SharedString>| {
+            let mut conn = conn.clone();
+            async move {
+                let raw_keys: Vec<_> = batch.iter().map(|key| unescape_key(key)).collect();
+                let mut matched = vec![true; batch.len()];
+                if check_type {
+                    let mut pipeline = pipe();
+                    for raw_key in &raw_keys {
+                        pipeline.cmd("TYPE").arg(raw_key.as_ref());
+                    }
+                    let types: Vec<String> = pipeline.query_async(&mut conn).await?;
+                    for (matched, t) in matched.iter_mut().zip(types) {
+                        *matched &= filter.key_type.as_deref() == Some(t.as_str());
+                    }
+                }
+                if check_ttl {
+                    let mut pipeline = pipe();
+                    for raw_key in &raw_keys {
+                        pipeline.cmd("PTTL").arg(raw_key.as_ref());
+                    }
+                    let ttls: Vec<i64> = pipeline.query_async(&mut conn).await?;
+                    for (matched, ttl) in matched.iter_mut().zip(ttls) {
+                        if filter.no_ttl && ttl != -1 {
+                            *matched = false;
+                        }
+                        if let Some(secs) = filter.expires_within
+                            && !(0..=secs.saturating_mul(1000) as i64).contains(&ttl)
+                        {
+                            *matched = false;
+                        }
+                    }
+                }
+                if let Some(min_memory) = filter.min_memory {
+                    let mut pipeline = pipe();
+                    for raw_key in &raw_keys {
+                        pipeline.cmd("MEMORY").arg("USAGE").arg(raw_key.as_ref());
+                    }
+                    let usages: Vec<Option<u64>> = pipeline.query_async(&mut conn).await?;
+                    for (matched, usage) in matched.iter_mut().zip(usages) {
+                        *matched &= usage.unwrap_or_default() > min_memory;
+                    }
+                }
+                Ok::<_, Error>(
+                    batch
+                        .into_iter()
+                        .zip(matched)
+                        .filter(|(_, matched)| *matched)
+                        .map(|(key, _)| key)
+                        .collect(),
+                )
+            }
+        })
+        .buffer_unordered(FILTER_CONCURRENCY)
+        .try_collect()
+        .await?;
+    Ok(batches.into_iter().flatten().collect())
+}
 
 impl ZedisServerState {
     /// Fills the type of keys that are currently loaded but have an unknown type.
@@ -83,7 +214,7 @@ impl ZedisServerState {
         self.spawn(
             ServerTask::FillKeyTypes,
             move || async move {
-                let conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let conn = get_connection_manager().get_read_connection(&server_id, db).await?;
                 // Use a stream to execute commands concurrently with backpressure
                 let types: Vec<(SharedString, String)> = stream::iter(keys.iter().cloned())
                     .map(|key| {
@@ -91,7 +222,7 @@ impl ZedisServerState {
                         let key = key.clone();
                         async move {
                             let t: String = cmd("TYPE")
-                                .arg(key.as_str())
+                                .arg(unescape_key(&key))
                                 .query_async(&mut conn_clone)
                                 .await
                                 .unwrap_or_default();
@@ -132,6 +263,19 @@ impl ZedisServerState {
         // Calculate max limit based on scan times to prevent infinite scrolling from loading too much
         let max = (self.scan_times + 1) * DEFAULT_SCAN_RESULT_MAX;
 
+        let query_mode = self.query_mode;
+        let regex = match get_keyword_regex(query_mode, &keyword) {
+            Ok(regex) => regex,
+            Err(e) => {
+                self.add_error_message(ServerTask::ScanKeys.as_str().to_string(), e.to_string(), cx);
+                self.cursors = None;
+                self.scaning = false;
+                cx.notify();
+                return;
+            }
+        };
+        let filter = self.key_filter.clone();
+
         let processing_server = server_id.clone();
         let processing_keyword = keyword.clone();
         let db = self.db;
@@ -139,18 +283,12 @@ impl ZedisServerState {
             ServerTask::ScanKeys,
             move || async move {
                 let client = get_connection_manager().get_client(&server_id, db).await?;
-                let pattern = if keyword.is_empty() {
-                    "*".to_string()
-                } else {
-                    format!("*{}*", keyword)
-                };
+                let pattern = get_scan_pattern(query_mode, &keyword);
                 // Adjust count based on keyword specificity
                 let count = if keyword.is_empty() { 2_000 } else { 10_000 };
-                if let Some(cursors) = cursors {
-                    client.scan(cursors, &pattern, count).await
-                } else {
-                    client.first_scan(&pattern, count).await
-                }
+                let (cursors, keys) = scan_with_filter(&client, cursors, &pattern, count, &filter).await?;
+                let keys = filter_scanned_keys(&client, keys, regex.as_ref(), &filter).await?;
+                Ok((cursors, keys))
             },
             move |this, result, cx| {
                 match result {
@@ -196,7 +334,7 @@ impl ZedisServerState {
         self.reset_scan();
         match self.query_mode {
             QueryMode::Prefix => self.scan_prefix(keyword, cx),
-            QueryMode::Exact => self.select_key(keyword, cx),
+            QueryMode::Exact => self.select_key(normalize_key(&keyword).into(), cx),
             _ => self.scan(keyword, cx),
         }
     }
@@ -253,9 +391,12 @@ impl ZedisServerState {
         }
         cx.emit(ServerEvent::KeyScanStarted(prefix.clone()));
 
+        // Keys of the folder still have to match the regex and the filter of the search
+        let regex = get_keyword_regex(self.query_mode, &self.keyword).ok().flatten();
+        let filter = self.key_filter.clone();
         let server_id = self.server_id.clone();
         let db = self.db;
-        let pattern = format!("{}*", prefix);
+        let pattern = format!("{}*", escape_glob(&prefix));
         self.spawn(
             ServerTask::ScanPrefix,
             move || async move {
@@ -268,12 +409,9 @@ impl ZedisServerState {
                 // Attempt to fetch keys in a loop (up to 20 iterations)
                 // to gather a sufficient amount without blocking for too long.
                 for _ in 0..20 {
-                    let (new_cursor, keys) = if let Some(cursors) = cursors.clone() {
-                        client.scan(cursors, &pattern, count).await?
-                    } else {
-                        client.first_scan(&pattern, count).await?
-                    };
-                    result_keys.extend(keys);
+                    let (new_cursor, keys) =
+                        scan_with_filter(&client, cursors.clone(), &pattern, count, &filter).await?;
+                    result_keys.extend(filter_scanned_keys(&client, keys, regex.as_ref(), &filter).await?);
                     // Break if scan cycle finishes
                     if new_cursor.iter().sum::<u64>() == 0 {
                         done = true;
@@ -314,6 +452,16 @@ impl ZedisServerState {
 
     /// Selects a key and fetches its details (Type, TTL, Value).
     pub fn select_key(&mut self, key: SharedString, cx: &mut Context<Self>) {
+        self.record_recent_key(&key, cx);
+        self.load_key(key, false, cx);
+    }
+    /// Reloads the selected string key without the partial value limit.
+    pub fn load_full_value(&mut self, cx: &mut Context<Self>) {
+        if let Some(key) = self.key.clone() {
+            self.load_key(key, true, cx);
+        }
+    }
+    fn load_key(&mut self, key: SharedString, full: bool, cx: &mut Context<Self>) {
         self.key = Some(key.clone());
         if key.is_empty() {
             return;
@@ -333,18 +481,25 @@ impl ZedisServerState {
 
         let server_id = self.server_id.clone();
         let db = self.db;
+        let written = self.is_key_written(&key);
         let current_key = key.clone();
-        let max_truncate_length = cx.global::<ZedisGlobalStore>().read(cx).max_truncate_length();
+        let app_state = cx.global::<ZedisGlobalStore>().read(cx);
+        let max_truncate_length = app_state.max_truncate_length();
+        let partial_size = if full { 0 } else { app_state.partial_value_size() * 1024 };
+        let protobuf_descriptors = app_state.protobuf_descriptors().to_vec();
+        let protobuf_message = app_state.protobuf_message(&key).map(|message| message.to_string());
+        let formatter = app_state.key_formatter(&key).cloned();
 
         self.spawn(
             ServerTask::Selectkey,
             move || async move {
-                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = client.value_connection(written);
                 let (t, ttl): (String, i64) = pipe()
                     .cmd("TYPE")
-                    .arg(key.as_str())
+                    .arg(unescape_key(&key))
                     .cmd("TTL")
-                    .arg(key.as_str())
+                    .arg(unescape_key(&key))
                     .query_async(&mut conn)
                     .await?;
                 // the key does not exist
@@ -363,11 +518,20 @@ impl ZedisServerState {
 
                 let key_type = KeyType::from(t.as_str());
                 let mut redis_value = match key_type {
-                    KeyType::String => get_redis_value(&mut conn, &key, max_truncate_length).await,
+                    KeyType::String => {
+                        let protobuf = protobuf_message.and_then(|message| {
+                            find_protobuf_message(&protobuf_descriptors, &message)
+                                .inspect_err(|e| error!(error = %e, message, "find protobuf message failed"))
+                                .ok()
+                        });
+                        get_redis_value(&mut conn, &key, max_truncate_length, partial_size, protobuf, formatter).await
+                    }
                     KeyType::List => first_load_list_value(&mut conn, &key).await,
                     KeyType::Set => first_load_set_value(&mut conn, &key).await,
                     KeyType::Zset => first_load_zset_value(&mut conn, &key, SortOrder::Asc).await,
-                    KeyType::Hash => first_load_hash_value(&mut conn, &key).await,
+                    KeyType::Hash => {
+                        first_load_hash_value(&mut conn, &key, client.is_at_least_version(FIELD_TTL_VERSION)).await
+                    }
                     _ => Err(Error::Invalid {
                         message: "unsupported key type".to_string(),
                     }),
@@ -384,21 +548,10 @@ impl ZedisServerState {
                 match result {
                     Ok(value) => {
                         if !value.is_expired()
-                            && let Some(key) = this.key.as_ref()
+                            && let Some(key) = this.key.clone()
+                            && this.keys.get(&key) != Some(&value.key_type)
                         {
-                            let mut should_refresh_key_tree = false;
-                            if let Some(k) = this.keys.get_mut(key) {
-                                if *k != value.key_type {
-                                    should_refresh_key_tree = true;
-                                    *k = value.key_type();
-                                }
-                            } else {
-                                should_refresh_key_tree = true;
-                                this.keys.insert(key.clone(), value.key_type());
-                            }
-                            if should_refresh_key_tree {
-                                this.key_tree_id = Uuid::now_v7().to_string().into();
-                            }
+                            this.insert_key(key, value.key_type());
                         }
                         this.value = Some(value);
                     }
@@ -426,14 +579,14 @@ impl ZedisServerState {
             ServerTask::DeleteKey,
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
-                let _: () = cmd("DEL").arg(key.as_str()).query_async(&mut conn).await?;
+                record_history(&mut conn, &server_id, db, &key, ServerTask::DeleteKey, None).await;
+                let _: () = cmd("DEL").arg(unescape_key(&key)).query_async(&mut conn).await?;
                 Ok(())
             },
             move |this, result, cx| {
                 if let Ok(()) = result {
-                    this.keys.remove(&remove_key);
                     // Force refresh of the key tree view
-                    this.key_tree_id = Uuid::now_v7().to_string().into();
+                    this.remove_key(&remove_key);
                     // Deselect if the deleted key was selected
                     if this.key == Some(remove_key) {
                         this.key = None;
@@ -484,8 +637,9 @@ impl ZedisServerState {
                     });
                 }
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                record_history(&mut conn, &server_id, db, &key, ServerTask::UpdateKeyTtl, None).await;
                 let _: () = cmd("EXPIRE")
-                    .arg(key.as_str())
+                    .arg(unescape_key(&key))
                     .arg(new_ttl.as_secs())
                     .query_async(&mut conn)
                     .await?;
@@ -508,12 +662,15 @@ impl ZedisServerState {
         let server_id = self.server_id.clone();
         let db = self.db;
         let key_type = KeyType::from(category.to_lowercase().as_str());
+        let key: SharedString = normalize_key(&key).into();
         let key_clone = key.clone();
+        // the new key is selected once added, read it from the master
+        self.mark_key_written(key.clone());
         self.spawn(
             ServerTask::AddKey,
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
-                let exists: bool = cmd("EXISTS").arg(key.as_str()).query_async(&mut conn).await?;
+                let exists: bool = cmd("EXISTS").arg(unescape_key(&key)).query_async(&mut conn).await?;
                 let ttl_duration = if ttl.is_empty() {
                     None
                 } else if let Ok(secs) = ttl.parse::<u64>() {
@@ -528,27 +685,32 @@ impl ZedisServerState {
                         message: "Key already exists".to_string(),
                     });
                 }
+                record_history(&mut conn, &server_id, db, &key, ServerTask::AddKey, None).await;
                 match key_type {
                     KeyType::String => {
-                        let _: () = cmd("SET").arg(key.as_str()).arg("").query_async(&mut conn).await?;
+                        let _: () = cmd("SET")
+                            .arg(unescape_key(&key))
+                            .arg("")
+                            .query_async(&mut conn)
+                            .await?;
                     }
                     KeyType::List => {
                         let _: () = cmd("LPUSH")
-                            .arg(key.as_str())
+                            .arg(unescape_key(&key))
                             .arg("list item 1")
                             .query_async(&mut conn)
                             .await?;
                     }
                     KeyType::Set => {
                         let _: () = cmd("SADD")
-                            .arg(key.as_str())
+                            .arg(unescape_key(&key))
                             .arg("set item 1")
                             .query_async(&mut conn)
                             .await?;
                     }
                     KeyType::Zset => {
                         let _: () = cmd("ZADD")
-                            .arg(key.as_str())
+                            .arg(unescape_key(&key))
                             .arg(1.0)
                             .arg("zset item 1")
                             .query_async(&mut conn)
@@ -556,7 +718,7 @@ impl ZedisServerState {
                     }
                     KeyType::Hash => {
                         let _: () = cmd("HSET")
-                            .arg(key.as_str())
+                            .arg(unescape_key(&key))
                             .arg("field1")
                             .arg("value1")
                             .query_async(&mut conn)
@@ -570,7 +732,7 @@ impl ZedisServerState {
                 };
                 if let Some(ttl_duration) = ttl_duration {
                     let _: () = cmd("EXPIRE")
-                        .arg(key.as_str())
+                        .arg(unescape_key(&key))
                         .arg(ttl_duration.as_secs())
                         .query_async(&mut conn)
                         .await?;
@@ -580,8 +742,7 @@ impl ZedisServerState {
             },
             move |this, result, cx| {
                 if result.is_ok() {
-                    this.keys.insert(key_clone.clone(), key_type);
-                    this.key_tree_id = Uuid::now_v7().to_string().into();
+                    this.insert_key(key_clone.clone(), key_type);
                     this.select_key(key_clone, cx);
                 }
                 cx.notify();
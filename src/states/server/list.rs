@@ -12,15 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! Redis List operations module.
+//!
+//! This module provides functionality for managing Redis List data structures:
+//! - Loading list elements with pagination (LRANGE), jumping to any index of long lists
+//! - Pushing (LPUSH/RPUSH), inserting (LINSERT), updating (LSET) and removing elements
+//! - Moving elements to another list (LMOVE) and trimming to a range (LTRIM)
+//! - Searching elements (LPOS) and reordering small lists in one MULTI/EXEC transaction
+
 use super::{
     KeyType, RedisValueData, ServerTask, ZedisServerState,
-    value::{RedisListValue, RedisValue, RedisValueStatus},
+    history::{record_history, save_history_snapshot, take_history_snapshot},
+    staging::StagedCommand,
+    value::{RedisElement, RedisListValue, RedisValue, RedisValueStatus},
 };
 use crate::{
     connection::{RedisAsyncConn, get_connection_manager},
     error::Error,
-    states::ServerEvent,
+    helpers::{escape_key, unescape_key},
+    states::{NotificationAction, ServerEvent, i18n_list_editor},
 };
+use bytes::Bytes;
 use gpui::{SharedString, prelude::*};
 use redis::{cmd, pipe};
 use std::sync::Arc;
@@ -28,29 +40,51 @@ use uuid::Uuid;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// Number of elements loaded per page
+const PAGE_SIZE: usize = 100;
+/// Jumping further than this past the loaded elements starts a new window instead of loading the gap
+const MAX_LOAD_GAP: usize = 1000;
+/// Lists longer than this can not be reordered by dragging
+pub const MAX_REORDER_LIST_SIZE: usize = 1000;
+/// First version supporting LMOVE
+const LMOVE_VERSION: &str = "6.2.0";
+/// First version supporting LPOS
+const LPOS_VERSION: &str = "6.0.6";
+/// Attempts of an insert whose transaction is aborted by a concurrent change of the list
+const MAX_INSERT_ATTEMPTS: usize = 3;
+
 /// Fetch a range of elements from a Redis List.
 ///
-/// Returns a vector of strings. Binary data is lossily converted to UTF-8.
-async fn get_redis_list_value(conn: &mut RedisAsyncConn, key: &str, start: usize, stop: usize) -> Result<Vec<String>> {
+/// Returns the raw elements, so binary data is kept unchanged.
+async fn get_redis_list_value(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    start: usize,
+    stop: usize,
+) -> Result<Vec<RedisElement>> {
     // Fetch raw bytes to handle binary data safely
-    let value: Vec<Vec<u8>> = cmd("LRANGE").arg(key).arg(start).arg(stop).query_async(conn).await?;
+    let value: Vec<Vec<u8>> = cmd("LRANGE")
+        .arg(unescape_key(key))
+        .arg(start)
+        .arg(stop)
+        .query_async(conn)
+        .await?;
     if value.is_empty() {
         return Ok(vec![]);
     }
-    let value: Vec<String> = value.iter().map(|v| String::from_utf8_lossy(v).to_string()).collect();
-    Ok(value)
+    Ok(value.into_iter().map(RedisElement::new).collect())
 }
 
 /// Initial load for a List key.
 /// Fetches the total length (LLEN) and the first 100 items.
 pub(crate) async fn first_load_list_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisValue> {
-    let size: usize = cmd("LLEN").arg(key).query_async(conn).await?;
-    let values = get_redis_list_value(conn, key, 0, 99).await?;
+    let size: usize = cmd("LLEN").arg(unescape_key(key)).query_async(conn).await?;
+    let values = get_redis_list_value(conn, key, 0, PAGE_SIZE - 1).await?;
     Ok(RedisValue {
         key_type: KeyType::List,
         data: Some(RedisValueData::List(Arc::new(RedisListValue {
             size,
-            values: values.into_iter().map(|v| v.into()).collect(),
+            values,
             ..Default::default()
         }))),
         expire_at: None,
@@ -69,15 +103,35 @@ impl ZedisServerState {
         let new_list_value = RedisListValue {
             keyword: Some(keyword.clone()),
             size: list_value.size,
+            offset: list_value.offset,
             values: list_value.values.clone(),
         };
         value.data = Some(RedisValueData::List(Arc::new(new_list_value)));
         cx.emit(ServerEvent::ValueUpdated(self.key.clone().unwrap_or_default()));
     }
     pub fn remove_list_value(&mut self, index: usize, cx: &mut Context<Self>) {
+        if let Some(key) = self.staging_key() {
+            // the element is only removed after all other staged commands,
+            // so the indexes of the displayed list stay valid
+            let offset = self
+                .value
+                .as_ref()
+                .and_then(|value| value.list_value())
+                .map_or(0, |list| list.offset);
+            let unique_marker = Uuid::new_v4().to_string();
+            let commands = vec![
+                StagedCommand::new(&key, "LSET")
+                    .arg((offset + index).to_string())
+                    .arg(&unique_marker),
+                StagedCommand::new(&key, "LREM").arg("1").arg(&unique_marker).deferred(),
+            ];
+            self.stage_commands(commands, cx);
+            return;
+        }
         let Some((key, value)) = self.try_get_mut_key_value() else {
             return;
         };
+        let offset = value.list_value().map_or(0, |list| list.offset);
         value.status = RedisValueStatus::Updating;
         cx.notify();
         let server_id = self.server_id.clone();
@@ -88,14 +142,15 @@ impl ZedisServerState {
             move || async move {
                 let unique_marker = Uuid::new_v4().to_string();
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                record_history(&mut conn, &server_id, db, &key, ServerTask::RemoveListValue, None).await;
                 let _: () = pipe()
                     .atomic()
                     .cmd("LSET")
-                    .arg(key.as_str())
-                    .arg(index)
+                    .arg(unescape_key(&key))
+                    .arg(offset + index)
                     .arg(&unique_marker)
                     .cmd("LREM")
-                    .arg(key.as_str())
+                    .arg(unescape_key(&key))
                     .arg(1)
                     .arg(&unique_marker)
                     .query_async(&mut conn)
@@ -121,20 +176,33 @@ impl ZedisServerState {
         );
     }
     pub fn push_list_value(&mut self, new_value: SharedString, mode: SharedString, cx: &mut Context<Self>) {
+        if let Some(key) = self.staging_key() {
+            // a push shifts the indexes, it runs after the staged LSETs of displayed elements
+            let cmd_name = if mode == "1" { "LPUSH" } else { "RPUSH" };
+            let command = StagedCommand::new(&key, cmd_name).arg(new_value.as_bytes()).deferred();
+            self.stage_commands(vec![command], cx);
+            return;
+        }
         let Some((key, value)) = self.try_get_mut_key_value() else {
             return;
         };
         let is_lpush = mode == "1";
+        let new_element = RedisElement::from_text(&new_value);
         let mut pushed_value = false;
         value.status = RedisValueStatus::Updating;
         if let Some(RedisValueData::List(list_data)) = value.data.as_mut() {
             // Use Arc::make_mut to get mutable access (Cow behavior)
             let list = Arc::make_mut(list_data);
             if is_lpush {
-                list.values.insert(0, new_value.clone());
-                pushed_value = true;
-            } else if list.values.len() == list.size {
-                list.values.push(new_value.clone());
+                // the loaded window moves down when it does not start at the head
+                if list.offset == 0 {
+                    list.values.insert(0, new_element);
+                    pushed_value = true;
+                } else {
+                    list.offset += 1;
+                }
+            } else if list.offset + list.values.len() == list.size {
+                list.values.push(new_element);
                 pushed_value = true;
             }
             list.size += 1;
@@ -148,10 +216,11 @@ impl ZedisServerState {
             ServerTask::PushListValue,
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                record_history(&mut conn, &server_id, db, &key, ServerTask::PushListValue, None).await;
                 let cmd_name = if is_lpush { "LPUSH" } else { "RPUSH" };
 
                 let _: () = cmd(cmd_name)
-                    .arg(key.as_str())
+                    .arg(unescape_key(&key))
                     .arg(new_value.as_str())
                     .query_async(&mut conn)
                     .await?;
@@ -161,7 +230,6 @@ impl ZedisServerState {
                 if let Some(value) = this.value.as_mut() {
                     value.status = RedisValueStatus::Idle;
                     if result.is_err()
-                        && pushed_value
                         && let Some(RedisValueData::List(list_data)) = this.value.as_mut().and_then(|v| v.data.as_mut())
                     {
                         // Use Arc::make_mut to get mutable access (Cow behavior)
@@ -172,6 +240,8 @@ impl ZedisServerState {
                             } else {
                                 list.values.pop();
                             }
+                        } else if is_lpush {
+                            list.offset -= 1;
                         }
                         list.size -= 1;
                     }
@@ -186,22 +256,40 @@ impl ZedisServerState {
     ///
     /// Performs an optimistic lock check: verifies if the current value at `index`
     /// matches `original_value` before updating.
+    /// The new value is the edited text of the element, binary elements are edited escaped.
     pub fn update_list_value(
         &mut self,
         index: usize,
-        original_value: SharedString,
+        original_value: RedisElement,
         new_value: SharedString,
         cx: &mut Context<Self>,
     ) {
+        if let Some(key) = self.staging_key() {
+            let redis_index = self
+                .value
+                .as_ref()
+                .and_then(|value| value.list_value())
+                .map_or(0, |list| list.offset)
+                + index;
+            let new_bytes = original_value.bytes_from_text(&new_value);
+            let command = StagedCommand::new(&key, "LSET")
+                .arg(redis_index.to_string())
+                .arg(new_bytes);
+            self.stage_commands(vec![command], cx);
+            return;
+        }
         let Some((key, value)) = self.try_get_mut_key_value() else {
             return;
         };
+        let new_bytes = original_value.bytes_from_text(&new_value);
+        let new_value = RedisElement::new(new_bytes.clone());
+        let redis_index = value.list_value().map_or(0, |list| list.offset) + index;
         value.status = RedisValueStatus::Updating;
         if let Some(RedisValueData::List(list_data)) = value.data.as_mut() {
             // Use Arc::make_mut to get mutable access (Cow behavior)
             let list = Arc::make_mut(list_data);
             if index < list.values.len() {
-                list.values[index] = new_value.clone();
+                list.values[index] = new_value;
                 cx.emit(ServerEvent::ValueUpdated(key.clone()));
             }
         }
@@ -213,8 +301,7 @@ impl ZedisServerState {
 
         // Prepare data for the async block (move ownership)
         let key_clone = key.clone();
-        let original_value_clone = original_value.clone();
-        let new_value_clone = new_value.clone();
+        let original_bytes = original_value.bytes.clone();
 
         self.spawn(
             ServerTask::UpdateListValue,
@@ -222,26 +309,42 @@ impl ZedisServerState {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
 
                 // 1. Optimistic Lock Check: Get current value
-                let current_value: String = cmd("LINDEX")
-                    .arg(key.as_str())
-                    .arg(index)
+                let current_value: Vec<u8> = cmd("LINDEX")
+                    .arg(unescape_key(&key))
+                    .arg(redis_index)
                     .query_async(&mut conn)
                     .await?;
 
-                if current_value != original_value_clone {
+                if current_value != original_bytes {
                     return Err(Error::Invalid {
                         message: format!(
                             "Value changed (expected: '{}', actual: '{}'), update aborted.",
-                            original_value_clone, current_value
+                            escape_key(&original_bytes),
+                            escape_key(&current_value)
                         ),
                     });
                 }
 
                 // 2. Perform Update
+                let inverse = vec![
+                    "LSET".to_string(),
+                    key.to_string(),
+                    redis_index.to_string(),
+                    escape_key(&current_value),
+                ];
+                record_history(
+                    &mut conn,
+                    &server_id,
+                    db,
+                    &key,
+                    ServerTask::UpdateListValue,
+                    Some(inverse),
+                )
+                .await;
                 let _: () = cmd("LSET")
-                    .arg(key.as_str())
-                    .arg(index)
-                    .arg(new_value_clone.as_str())
+                    .arg(unescape_key(&key))
+                    .arg(redis_index)
+                    .arg(new_bytes)
                     .query_async(&mut conn)
                     .await?;
 
@@ -278,21 +381,24 @@ impl ZedisServerState {
 
         // Check if we have valid list data
         let current_len = match value.list_value() {
-            Some(list) => list.values.len(),
+            Some(list) => list.offset + list.values.len(),
             None => return,
         };
 
         let server_id = self.server_id.clone();
         let db = self.db;
+        let written = self.is_key_written(&key);
         // Calculate pagination
         let start = current_len;
-        let stop = start + 99; // Load 100 items
+        let stop = start + PAGE_SIZE - 1;
         cx.emit(ServerEvent::ValuePaginationStarted(key.clone()));
         let key_clone = key.clone();
         self.spawn(
             ServerTask::LoadMoreValue,
             move || async move {
-                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut conn = get_connection_manager()
+                    .get_value_connection(&server_id, db, written)
+                    .await?;
                 // Fetch only the new items
                 let new_values = get_redis_list_value(&mut conn, &key, start, stop).await?;
                 Ok(new_values)
@@ -305,7 +411,7 @@ impl ZedisServerState {
                     // Append new items to the existing list
                     if let Some(RedisValueData::List(list_data)) = this.value.as_mut().and_then(|v| v.data.as_mut()) {
                         let list = Arc::make_mut(list_data);
-                        list.values.extend(new_values.into_iter().map(|v| v.into()));
+                        list.values.extend(new_values);
                     }
                 }
                 cx.emit(ServerEvent::ValuePaginationFinished(key_clone));
@@ -317,4 +423,427 @@ impl ZedisServerState {
             cx,
         );
     }
+    /// Reload the first page of the list after a write that changes many indexes.
+    fn reload_list_value(&mut self, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        value.status = RedisValueStatus::Loading;
+        cx.notify();
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        self.spawn(
+            ServerTask::LoadMoreValue,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let size: usize = cmd("LLEN").arg(unescape_key(&key)).query_async(&mut conn).await?;
+                let values = get_redis_list_value(&mut conn, &key, 0, PAGE_SIZE - 1).await?;
+                Ok((size, values))
+            },
+            move |this, result, cx| {
+                if this.key.as_ref() != Some(&key_clone) {
+                    return;
+                }
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                    if let Ok((size, values)) = result {
+                        value.data = Some(RedisValueData::List(Arc::new(RedisListValue {
+                            size,
+                            values,
+                            ..Default::default()
+                        })));
+                    }
+                }
+                cx.emit(ServerEvent::ValueLoaded(key_clone));
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Insert a value before or after the element at `index` of the list (LINSERT).
+    ///
+    /// LINSERT finds its pivot by value, so the element is temporarily replaced
+    /// by a unique marker in the same transaction to target exactly this index.
+    /// The list is WATCHed from reading the pivot until EXEC, and the insert
+    /// is retried when it has been changed in between.
+    pub fn insert_list_value(&mut self, index: usize, before: bool, new_value: SharedString, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        value.status = RedisValueStatus::Updating;
+        cx.notify();
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        let new_element = RedisElement::from_text(&new_value);
+        let new_bytes = new_element.bytes.clone();
+        self.spawn(
+            ServerTask::InsertListValue,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                // WATCH applies to the whole connection, the shared one also runs other tasks
+                let mut conn = client.dedicated_connection().await?;
+                for _ in 0..MAX_INSERT_ATTEMPTS {
+                    let _: () = cmd("WATCH").arg(unescape_key(&key)).query_async(&mut conn).await?;
+                    let pivot: Option<Vec<u8>> = cmd("LINDEX")
+                        .arg(unescape_key(&key))
+                        .arg(index)
+                        .query_async(&mut conn)
+                        .await?;
+                    let Some(pivot) = pivot else {
+                        let _: () = cmd("UNWATCH").query_async(&mut conn).await?;
+                        return Err(Error::Invalid {
+                            message: format!("Index {index} is out of range"),
+                        });
+                    };
+                    let snapshot =
+                        take_history_snapshot(&mut conn, &server_id, db, &key, ServerTask::InsertListValue, None).await;
+                    let unique_marker = Uuid::new_v4().to_string();
+                    // the pivot moves down by one when inserting before it
+                    let pivot_index = if before { index + 1 } else { index };
+                    // EXEC replies nil when the list has been changed since WATCH
+                    let replies: Option<()> = pipe()
+                        .atomic()
+                        .cmd("LSET")
+                        .arg(unescape_key(&key))
+                        .arg(index)
+                        .arg(&unique_marker)
+                        .ignore()
+                        .cmd("LINSERT")
+                        .arg(unescape_key(&key))
+                        .arg(if before { "BEFORE" } else { "AFTER" })
+                        .arg(&unique_marker)
+                        .arg(new_bytes.as_ref())
+                        .ignore()
+                        .cmd("LSET")
+                        .arg(unescape_key(&key))
+                        .arg(pivot_index)
+                        .arg(pivot)
+                        .ignore()
+                        .query_async(&mut conn)
+                        .await?;
+                    if replies.is_some() {
+                        save_history_snapshot(snapshot);
+                        return Ok(());
+                    }
+                }
+                Err(Error::Invalid {
+                    message: format!("The list is being changed concurrently, the value was not inserted at {index}"),
+                })
+            },
+            move |this, result, cx| {
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                    if result.is_ok()
+                        && let Some(RedisValueData::List(list_data)) = value.data.as_mut()
+                    {
+                        let list = Arc::make_mut(list_data);
+                        let insert_at = if before { index } else { index + 1 };
+                        if insert_at < list.offset {
+                            list.offset += 1;
+                        } else if insert_at - list.offset <= list.values.len() {
+                            list.values.insert(insert_at - list.offset, new_element);
+                        }
+                        list.size += 1;
+                        cx.emit(ServerEvent::ValueUpdated(key_clone));
+                    }
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Move the head or tail element of the list to another list (LMOVE).
+    ///
+    /// Redis before 6.2 only supports moving the tail to the head (RPOPLPUSH).
+    pub fn move_list_value(
+        &mut self,
+        destination: SharedString,
+        from_left: bool,
+        to_left: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((key, _)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        // the destination is written too, it is read from the master if opened next
+        self.mark_key_written(destination.clone());
+        self.spawn(
+            ServerTask::MoveListValue,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = client.connection();
+                let side = |left: bool| if left { "LEFT" } else { "RIGHT" };
+                let command = if client.is_at_least_version(LMOVE_VERSION) {
+                    let mut command = cmd("LMOVE");
+                    command
+                        .arg(unescape_key(&key))
+                        .arg(unescape_key(&destination))
+                        .arg(side(from_left))
+                        .arg(side(to_left));
+                    command
+                } else if !from_left && to_left {
+                    let mut command = cmd("RPOPLPUSH");
+                    command.arg(unescape_key(&key)).arg(unescape_key(&destination));
+                    command
+                } else {
+                    return Err(Error::Invalid {
+                        message: format!("LMOVE requires Redis {LMOVE_VERSION} or later"),
+                    });
+                };
+                record_history(&mut conn, &server_id, db, &key, ServerTask::MoveListValue, None).await;
+                record_history(&mut conn, &server_id, db, &destination, ServerTask::MoveListValue, None).await;
+                let moved: Option<Vec<u8>> = command.query_async(&mut conn).await?;
+                Ok(moved.is_some())
+            },
+            move |this, result, cx| {
+                if let Ok(true) = result {
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                        i18n_list_editor(cx, "move_value_success"),
+                    )));
+                    this.reload_list_value(cx);
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Trim the list to the elements between `start` and `stop` (LTRIM), negative indexes count from the tail.
+    pub fn trim_list_value(&mut self, start: i64, stop: i64, cx: &mut Context<Self>) {
+        if let Some(key) = self.staging_key() {
+            let command = StagedCommand::new(&key, "LTRIM")
+                .arg(start.to_string())
+                .arg(stop.to_string());
+            self.stage_commands(vec![command], cx);
+            return;
+        }
+        let Some((key, _)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::TrimListValue,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                record_history(&mut conn, &server_id, db, &key, ServerTask::TrimListValue, None).await;
+                let _: () = cmd("LTRIM")
+                    .arg(unescape_key(&key))
+                    .arg(start)
+                    .arg(stop)
+                    .query_async(&mut conn)
+                    .await?;
+                Ok(())
+            },
+            move |this, result, cx| {
+                if result.is_ok() {
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                        i18n_list_editor(cx, "trim_success"),
+                    )));
+                    this.reload_list_value(cx);
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Locate the element at `index` of the list, loading it when needed,
+    /// then emit `ValueIndexFound` with its row in the loaded elements.
+    ///
+    /// Indexes close after the loaded elements are appended, others start a new window,
+    /// so very long lists never load everything before the index.
+    pub fn go_to_list_index(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let Some(list) = value.list_value() else {
+            return;
+        };
+        if list.size == 0 {
+            return;
+        }
+        let index = index.min(list.size - 1);
+        let (offset, loaded_end) = (list.offset, list.offset + list.values.len());
+        if (offset..loaded_end).contains(&index) {
+            cx.emit(ServerEvent::ValueIndexFound(key, index - offset));
+            return;
+        }
+        value.status = RedisValueStatus::Loading;
+        cx.emit(ServerEvent::ValuePaginationStarted(key.clone()));
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let written = self.is_key_written(&key);
+        let key_clone = key.clone();
+        // append the gap or start a new window a few elements before the index
+        let append = index >= loaded_end && index - loaded_end <= MAX_LOAD_GAP;
+        let start = if append {
+            loaded_end
+        } else {
+            index.saturating_sub(PAGE_SIZE / 2)
+        };
+        let stop = index.max(start) + PAGE_SIZE - 1;
+        self.spawn(
+            ServerTask::LoadMoreValue,
+            move || async move {
+                let mut conn = get_connection_manager()
+                    .get_value_connection(&server_id, db, written)
+                    .await?;
+                get_redis_list_value(&mut conn, &key, start, stop).await
+            },
+            move |this, result, cx| {
+                let mut new_offset = None;
+                if let Ok(new_values) = result
+                    && let Some(RedisValueData::List(list_data)) = this.value.as_mut().and_then(|v| v.data.as_mut())
+                {
+                    let list = Arc::make_mut(list_data);
+                    if append {
+                        list.values.extend(new_values);
+                    } else {
+                        list.offset = start;
+                        list.values = new_values;
+                    }
+                    new_offset = Some(list.offset);
+                }
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                }
+                cx.emit(ServerEvent::ValuePaginationFinished(key_clone.clone()));
+                if let Some(offset) = new_offset {
+                    cx.emit(ServerEvent::ValueIndexFound(key_clone, index - offset));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Find the `rank`-th (1-based) occurrence of an element with LPOS and jump to it.
+    pub fn find_list_value(&mut self, element: SharedString, rank: usize, cx: &mut Context<Self>) {
+        let Some((key, _)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        self.spawn(
+            ServerTask::FindListValue,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                if !client.is_at_least_version(LPOS_VERSION) {
+                    return Err(Error::Invalid {
+                        message: format!("LPOS requires Redis {LPOS_VERSION} or later"),
+                    });
+                }
+                let mut conn = client.connection();
+                let index: Option<usize> = cmd("LPOS")
+                    .arg(unescape_key(&key))
+                    .arg(RedisElement::from_text(&element).bytes.as_ref())
+                    .arg("RANK")
+                    .arg(rank.max(1))
+                    .query_async(&mut conn)
+                    .await?;
+                Ok(index)
+            },
+            move |this, result, cx| {
+                if this.key.as_ref() != Some(&key_clone) {
+                    return;
+                }
+                match result {
+                    Ok(Some(index)) => this.go_to_list_index(index, cx),
+                    Ok(None) => {
+                        cx.emit(ServerEvent::Notification(NotificationAction::new_info(
+                            i18n_list_editor(cx, "find_value_not_found"),
+                        )));
+                    }
+                    _ => {}
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Move the element at `from` to `to`, applied as one MULTI/EXEC transaction of LSET.
+    ///
+    /// Only fully loaded lists up to MAX_REORDER_LIST_SIZE elements can be reordered,
+    /// the affected elements are checked first so concurrent changes are not overwritten.
+    pub fn reorder_list_value(&mut self, from: usize, to: usize, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let Some(list) = value.list_value() else {
+            return;
+        };
+        if from == to
+            || list.offset != 0
+            || list.values.len() != list.size
+            || list.size > MAX_REORDER_LIST_SIZE
+            || from.max(to) >= list.size
+        {
+            return;
+        }
+        let (low, high) = (from.min(to), from.max(to));
+        let original_values: Vec<Bytes> = list.values[low..=high].iter().map(|item| item.bytes.clone()).collect();
+        let mut reordered = list.values.clone();
+        let item = reordered.remove(from);
+        reordered.insert(to, item);
+        let new_values: Vec<Bytes> = reordered[low..=high].iter().map(|item| item.bytes.clone()).collect();
+
+        value.status = RedisValueStatus::Updating;
+        cx.notify();
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        self.spawn(
+            ServerTask::ReorderListValue,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let current: Vec<Vec<u8>> = cmd("LRANGE")
+                    .arg(unescape_key(&key))
+                    .arg(low)
+                    .arg(high)
+                    .query_async(&mut conn)
+                    .await?;
+                if current.len() != original_values.len()
+                    || current
+                        .iter()
+                        .zip(&original_values)
+                        .any(|(a, b)| a.as_slice() != b.as_ref())
+                {
+                    return Err(Error::Invalid {
+                        message: "List changed, reorder aborted.".to_string(),
+                    });
+                }
+                record_history(&mut conn, &server_id, db, &key, ServerTask::ReorderListValue, None).await;
+                let mut pipeline = pipe();
+                pipeline.atomic();
+                for (offset, value) in new_values.iter().enumerate() {
+                    pipeline
+                        .cmd("LSET")
+                        .arg(unescape_key(&key))
+                        .arg(low + offset)
+                        .arg(value.as_ref())
+                        .ignore();
+                }
+                let _: () = pipeline.query_async(&mut conn).await?;
+                Ok(())
+            },
+            move |this, result, cx| {
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                    if result.is_ok()
+                        && let Some(RedisValueData::List(list_data)) = value.data.as_mut()
+                    {
+                        Arc::make_mut(list_data).values = reordered;
+                        cx.emit(ServerEvent::ValueUpdated(key_clone));
+                    }
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
 }
@@ -0,0 +1,127 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Automatic reconnection.
+//!
+//! When a task fails with a connection error (dropped or refused connection,
+//! timeout) or a topology error (MOVED, CLUSTERDOWN, a failed over master), the
+//! cached client is dropped and a new one is created with exponential backoff.
+//! Creating the client discovers the nodes again, so the new masters of a cluster
+//! or a sentinel are picked up. After `MAX_RECONNECT_ATTEMPTS` the server is
+//! offline until the user retries from the status bar.
+
+use super::{ServerEvent, ServerTask, ZedisServerState};
+use crate::connection::get_connection_manager;
+use gpui::{SharedString, prelude::*};
+use std::{sync::Arc, time::Duration};
+use tracing::info;
+
+/// Number of attempts before giving up
+const MAX_RECONNECT_ATTEMPTS: usize = 5;
+/// Delay before the first attempt, doubled for each attempt
+const RECONNECT_BASE_DELAY: Duration = Duration::from_millis(500);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// State of the connection to the selected server
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ConnectionState {
+    #[default]
+    Connected,
+    /// Reconnecting, with the current attempt (starting from 1)
+    Reconnecting { attempt: usize },
+    /// All attempts failed
+    Offline,
+}
+
+/// Delay before the attempt, 0.5s, 1s, 2s... up to `RECONNECT_MAX_DELAY`
+fn reconnect_delay(attempt: usize) -> Duration {
+    let factor = 1u32 << attempt.saturating_sub(1).min(16);
+    RECONNECT_BASE_DELAY.saturating_mul(factor).min(RECONNECT_MAX_DELAY)
+}
+
+impl ZedisServerState {
+    pub fn connection_state(&self) -> ConnectionState {
+        self.connection_state
+    }
+    fn set_connection_state(&mut self, state: ConnectionState, cx: &mut Context<Self>) {
+        self.connection_state = state;
+        cx.emit(ServerEvent::ConnectionStateChanged(state));
+        cx.notify();
+    }
+    /// Start reconnecting after a connection or topology error, unless already reconnecting
+    pub(crate) fn handle_connection_error(&mut self, cx: &mut Context<Self>) {
+        if self.server_id.is_empty() || matches!(self.connection_state, ConnectionState::Reconnecting { .. }) {
+            return;
+        }
+        self.try_reconnect(1, reconnect_delay(1), cx);
+    }
+    /// Reconnect right away, used to retry an offline server
+    pub fn reconnect(&mut self, cx: &mut Context<Self>) {
+        if self.server_id.is_empty() || matches!(self.connection_state, ConnectionState::Reconnecting { .. }) {
+            return;
+        }
+        self.try_reconnect(1, Duration::ZERO, cx);
+    }
+    fn try_reconnect(&mut self, attempt: usize, delay: Duration, cx: &mut Context<Self>) {
+        self.set_connection_state(ConnectionState::Reconnecting { attempt }, cx);
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let reconnecting_server_id = server_id.clone();
+        self.spawn(
+            ServerTask::Reconnect,
+            move || async move {
+                smol::Timer::after(delay).await;
+                info!(server_id = server_id.as_str(), attempt, "reconnecting");
+                let client = get_connection_manager().reconnect(&server_id, db).await?;
+                client.ping().await?;
+                let dbsize = client.dbsize().await?;
+                Ok((
+                    dbsize,
+                    client.nodes(),
+                    client.nodes_description(),
+                    client.browsed_replica(),
+                    client.read_replicas(),
+                    client.read_replica_error(),
+                ))
+            },
+            move |this, result, cx| {
+                // the server was switched (or reset) while reconnecting
+                if this.server_id != reconnecting_server_id || this.db != db {
+                    return;
+                }
+                match result {
+                    Ok((dbsize, nodes, nodes_description, browsed_replica, read_replicas, read_replica_error)) => {
+                        this.dbsize = Some(dbsize);
+                        this.nodes = nodes;
+                        this.nodes_description = Arc::new(nodes_description);
+                        // the browsed replica may be gone after a failover
+                        this.browsed_replica = browsed_replica.map(SharedString::from);
+                        // the replicas are picked again, their lag may have changed
+                        this.read_replicas = read_replicas.into_iter().map(SharedString::from).collect();
+                        this.notify_read_replica_error(read_replica_error, cx);
+                        this.set_connection_state(ConnectionState::Connected, cx);
+                        cx.emit(ServerEvent::ServerInfoUpdated(reconnecting_server_id));
+                    }
+                    Err(_) if attempt < MAX_RECONNECT_ATTEMPTS => {
+                        this.try_reconnect(attempt + 1, reconnect_delay(attempt + 1), cx);
+                    }
+                    Err(_) => {
+                        this.set_connection_state(ConnectionState::Offline, cx);
+                    }
+                }
+            },
+            cx,
+        );
+    }
+}
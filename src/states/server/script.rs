@@ -0,0 +1,578 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Lua scripts and Redis Functions module.
+//!
+//! This module backs the script workbench:
+//! - Running scripts with EVAL, EVALSHA and EVAL_RO (KEYS and ARGV from the editor)
+//! - Managing the script cache with SCRIPT LOAD, SCRIPT EXISTS and SCRIPT FLUSH
+//! - Listing, loading (REPLACE), calling (FCALL/FCALL_RO) and deleting function libraries
+//!
+//! Replies are formatted like redis-cli and appended to a console. In cluster mode,
+//! SCRIPT and FUNCTION management commands are routed to all masters by the client.
+//!
+//! Scripts are saved per server in `scripts/<server_id>.toml` under the config directory.
+
+use super::{ServerEvent, ServerTask, ZedisServerState};
+use crate::{
+    connection::get_connection_manager,
+    error::Error,
+    helpers::{escape_key, get_or_create_config_dir, unescape_key, unix_ts},
+};
+use gpui::{SharedString, prelude::*};
+use parking_lot::Mutex;
+use redis::{Cmd, Script, Value, cmd};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf, sync::Arc, sync::LazyLock};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// First version supporting EVAL_RO and Redis Functions
+const FUNCTIONS_VERSION: &str = "7.0.0";
+/// Maximum number of outputs kept in the console
+const MAX_SCRIPT_OUTPUTS: usize = 100;
+/// Scripts longer than this are abbreviated in the console
+const MAX_COMMAND_SCRIPT_LENGTH: usize = 48;
+
+/// Serializes writes of the saved scripts files.
+static SCRIPTS_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+/// Command used to run a Lua script.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ScriptMode {
+    #[default]
+    Eval,
+    /// Runs the cached script by its SHA1 digest
+    EvalSha,
+    /// Read-only variant, also allowed on replicas (Redis 7.0+)
+    EvalRo,
+}
+
+impl ScriptMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScriptMode::Eval => "EVAL",
+            ScriptMode::EvalSha => "EVALSHA",
+            ScriptMode::EvalRo => "EVAL_RO",
+        }
+    }
+}
+
+/// A command run from the workbench and its formatted reply.
+#[derive(Debug, Clone)]
+pub struct ScriptOutput {
+    pub command: SharedString,
+    pub output: SharedString,
+    /// Whether the reply is an error
+    pub error: bool,
+    /// Unix timestamp in seconds
+    pub created_at: i64,
+}
+
+/// A function of a library.
+#[derive(Debug, Clone, Default)]
+pub struct FunctionInfo {
+    pub name: String,
+    /// Flags such as `no-writes`
+    pub flags: Vec<String>,
+}
+
+impl FunctionInfo {
+    /// Whether the function can be called with FCALL_RO.
+    pub fn is_read_only(&self) -> bool {
+        self.flags.iter().any(|flag| flag == "no-writes")
+    }
+}
+
+/// A function library returned by FUNCTION LIST WITHCODE.
+#[derive(Debug, Clone, Default)]
+pub struct FunctionLibrary {
+    pub name: String,
+    pub engine: String,
+    pub functions: Vec<FunctionInfo>,
+    pub code: String,
+}
+
+/// A script saved locally.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedScript {
+    pub name: String,
+    pub script: String,
+    /// Escaped KEYS (see `escape_key`)
+    #[serde(default)]
+    pub keys: Vec<String>,
+    /// Escaped ARGV (see `escape_key`)
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Unix timestamp in seconds
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SavedScripts {
+    scripts: Vec<SavedScript>,
+}
+
+fn get_scripts_file(server_id: &str) -> Result<PathBuf> {
+    let dir = get_or_create_config_dir()?.join("scripts");
+    if !dir.exists() {
+        fs::create_dir_all(&dir)?;
+    }
+    Ok(dir.join(format!("{server_id}.toml")))
+}
+
+/// Load the saved scripts of a server.
+fn get_saved_scripts(server_id: &str) -> Result<Vec<SavedScript>> {
+    let path = get_scripts_file(server_id)?;
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let value = fs::read_to_string(path)?;
+    if value.is_empty() {
+        return Ok(vec![]);
+    }
+    let saved: SavedScripts = toml::from_str(&value)?;
+    Ok(saved.scripts)
+}
+
+fn save_saved_scripts(server_id: &str, scripts: Vec<SavedScript>) -> Result<()> {
+    let _guard = SCRIPTS_LOCK.lock();
+    let path = get_scripts_file(server_id)?;
+    let value = toml::to_string(&SavedScripts { scripts })?;
+    fs::write(path, value)?;
+    Ok(())
+}
+
+/// Formats a scalar reply, `None` for aggregate types.
+fn format_scalar(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::Nil => "(nil)".to_string(),
+        Value::Int(value) => format!("(integer) {value}"),
+        Value::BulkString(bytes) => format!("\"{}\"", escape_key(bytes)),
+        Value::SimpleString(value) => value.clone(),
+        Value::Okay => "OK".to_string(),
+        Value::Double(value) => format!("(double) {value}"),
+        Value::Boolean(value) => format!("({value})"),
+        Value::VerbatimString { text, .. } => text.clone(),
+        Value::ServerError(err) => format!("(error) {err}"),
+        Value::Array(_) | Value::Set(_) | Value::Map(_) | Value::Attribute { .. } | Value::Push { .. } => {
+            return None;
+        }
+        _ => format!("{value:?}"),
+    };
+    Some(text)
+}
+
+/// Formats a reply like redis-cli, one entry per line and nested replies indented.
+fn format_lines(value: &Value) -> Vec<String> {
+    if let Some(text) = format_scalar(value) {
+        return vec![text];
+    }
+    let (items, marker): (Vec<(Option<&Value>, &Value)>, char) = match value {
+        Value::Array(items) | Value::Push { data: items, .. } => (items.iter().map(|item| (None, item)).collect(), ')'),
+        Value::Set(items) => (items.iter().map(|item| (None, item)).collect(), '~'),
+        Value::Map(pairs) => (pairs.iter().map(|(key, value)| (Some(key), value)).collect(), '#'),
+        Value::Attribute { data, .. } => return format_lines(data),
+        _ => return vec![format!("{value:?}")],
+    };
+    if items.is_empty() {
+        return vec![if marker == '#' { "(empty hash)" } else { "(empty array)" }.to_string()];
+    }
+    let width = items.len().to_string().len();
+    let mut lines = vec![];
+    for (index, (key, item)) in items.into_iter().enumerate() {
+        let mut prefix = format!("{:>width$}{marker} ", index + 1);
+        if let Some(key) = key {
+            prefix.push_str(&format!("{} => ", format_lines(key).join(" ")));
+        }
+        let padding = " ".repeat(prefix.chars().count());
+        for (line_index, line) in format_lines(item).into_iter().enumerate() {
+            if line_index == 0 {
+                lines.push(format!("{prefix}{line}"));
+            } else {
+                lines.push(format!("{padding}{line}"));
+            }
+        }
+    }
+    lines
+}
+
+/// Formats a reply like redis-cli.
+fn format_redis_value(value: &Value) -> String {
+    format_lines(value).join("\n")
+}
+
+/// Key/value pairs of a reply, from a RESP3 map or a RESP2 flat array.
+fn value_pairs(value: &Value) -> Vec<(String, &Value)> {
+    let pairs: Vec<(&Value, &Value)> = match value {
+        Value::Map(pairs) => pairs.iter().map(|(key, value)| (key, value)).collect(),
+        Value::Array(items) => items.chunks_exact(2).map(|pair| (&pair[0], &pair[1])).collect(),
+        _ => vec![],
+    };
+    pairs
+        .into_iter()
+        .map(|(key, value)| (value_to_string(key), value))
+        .collect()
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::BulkString(bytes) => String::from_utf8_lossy(bytes).to_string(),
+        Value::SimpleString(value) => value.clone(),
+        Value::VerbatimString { text, .. } => text.clone(),
+        Value::Int(value) => value.to_string(),
+        Value::Okay => "OK".to_string(),
+        _ => String::new(),
+    }
+}
+
+fn value_items(value: &Value) -> &[Value] {
+    match value {
+        Value::Array(items) | Value::Set(items) => items,
+        _ => &[],
+    }
+}
+
+/// Parses the reply of FUNCTION LIST WITHCODE.
+fn parse_function_libraries(value: &Value) -> Vec<FunctionLibrary> {
+    value_items(value)
+        .iter()
+        .map(|library| {
+            let mut info = FunctionLibrary::default();
+            for (field, value) in value_pairs(library) {
+                match field.as_str() {
+                    "library_name" => info.name = value_to_string(value),
+                    "engine" => info.engine = value_to_string(value),
+                    "library_code" => info.code = value_to_string(value),
+                    "functions" => {
+                        info.functions = value_items(value)
+                            .iter()
+                            .map(|function| {
+                                let mut function_info = FunctionInfo::default();
+                                for (field, value) in value_pairs(function) {
+                                    match field.as_str() {
+                                        "name" => function_info.name = value_to_string(value),
+                                        "flags" => {
+                                            function_info.flags =
+                                                value_items(value).iter().map(value_to_string).collect()
+                                        }
+                                        _ => {}
+                                    }
+                                }
+                                function_info
+                            })
+                            .collect();
+                    }
+                    _ => {}
+                }
+            }
+            info
+        })
+        .collect()
+}
+
+/// Short form of a script for the console, e.g. `"return redis.call('GET', KEYS[1])"`.
+fn abbreviate_script(script: &str) -> String {
+    let script = script.split_whitespace().collect::<Vec<_>>().join(" ");
+    if script.chars().count() <= MAX_COMMAND_SCRIPT_LENGTH {
+        return format!("\"{script}\"");
+    }
+    let prefix: String = script.chars().take(MAX_COMMAND_SCRIPT_LENGTH).collect();
+    format!("\"{prefix}...\"")
+}
+
+/// Appends `numkeys key... arg...` to the command and its console form.
+fn append_keys_and_args(command: &mut Cmd, label: &mut String, keys: &[SharedString], args: &[SharedString]) {
+    command.arg(keys.len());
+    label.push_str(&format!(" {}", keys.len()));
+    for item in keys.iter().chain(args) {
+        command.arg(unescape_key(item));
+        label.push_str(&format!(" {item}"));
+    }
+}
+
+impl ZedisServerState {
+    /// Get the outputs of the console, oldest first
+    pub fn script_outputs(&self) -> Arc<Vec<ScriptOutput>> {
+        self.script_outputs.clone()
+    }
+    /// Get the function libraries of the current server
+    pub fn function_libraries(&self) -> Arc<Vec<FunctionLibrary>> {
+        self.function_libraries.clone()
+    }
+    /// Get the scripts saved for the current server
+    pub fn saved_scripts(&self) -> Arc<Vec<SavedScript>> {
+        self.saved_scripts.clone()
+    }
+    /// Clear the console
+    pub fn clear_script_outputs(&mut self, cx: &mut Context<Self>) {
+        self.script_outputs = Arc::new(vec![]);
+        cx.emit(ServerEvent::ScriptOutputUpdated);
+        cx.notify();
+    }
+    fn push_script_output(&mut self, output: ScriptOutput, cx: &mut Context<Self>) {
+        let mut outputs = self.script_outputs.to_vec();
+        outputs.push(output);
+        if outputs.len() > MAX_SCRIPT_OUTPUTS {
+            outputs.remove(0);
+        }
+        self.script_outputs = Arc::new(outputs);
+        cx.emit(ServerEvent::ScriptOutputUpdated);
+        cx.notify();
+    }
+    /// Run the command and write its reply (or error) to the console.
+    ///
+    /// Commands needing Redis 7.0 are refused on older servers.
+    fn run_console_command(
+        &mut self,
+        task: ServerTask,
+        command: Cmd,
+        label: String,
+        requires_functions: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let reload_functions = matches!(
+            task,
+            ServerTask::LoadFunctionLibrary | ServerTask::DeleteFunctionLibrary
+        );
+        self.spawn(
+            task,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                if requires_functions && !client.is_at_least_version(FUNCTIONS_VERSION) {
+                    return Err(Error::Invalid {
+                        message: format!("{label} requires Redis {FUNCTIONS_VERSION} or later"),
+                    });
+                }
+                let mut conn = client.connection();
+                let (output, error) = match command.query_async::<Value>(&mut conn).await {
+                    Ok(value) => (format_redis_value(&value), false),
+                    Err(e) => (format!("(error) {e}"), true),
+                };
+                Ok(ScriptOutput {
+                    command: label.into(),
+                    output: output.into(),
+                    error,
+                    created_at: unix_ts(),
+                })
+            },
+            move |this, result, cx| {
+                if let Ok(output) = result {
+                    let error = output.error;
+                    this.push_script_output(output, cx);
+                    if reload_functions && !error {
+                        this.load_function_libraries(cx);
+                    }
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Run the script with EVAL, EVALSHA (SHA1 of the script) or EVAL_RO
+    pub fn run_script(
+        &mut self,
+        mode: ScriptMode,
+        script: SharedString,
+        keys: Vec<SharedString>,
+        args: Vec<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        if script.trim().is_empty() || (mode != ScriptMode::EvalRo && self.refuse_write(cx)) {
+            return;
+        }
+        let mut command = cmd(mode.as_str());
+        let mut label = mode.as_str().to_string();
+        if mode == ScriptMode::EvalSha {
+            let hash = Script::new(&script).get_hash().to_string();
+            label.push_str(&format!(" {hash}"));
+            command.arg(hash);
+        } else {
+            label.push_str(&format!(" {}", abbreviate_script(&script)));
+            command.arg(script.as_str());
+        }
+        append_keys_and_args(&mut command, &mut label, &keys, &args);
+        let task = if mode == ScriptMode::EvalRo {
+            ServerTask::RunReadOnlyScript
+        } else {
+            // the keys may be written by the script
+            for key in &keys {
+                self.mark_key_written(key.clone());
+            }
+            ServerTask::RunScript
+        };
+        self.run_console_command(task, command, label, mode == ScriptMode::EvalRo, cx);
+    }
+    /// Load the script into the script cache (SCRIPT LOAD), replying its SHA1
+    pub fn load_script(&mut self, script: SharedString, cx: &mut Context<Self>) {
+        if script.trim().is_empty() || self.refuse_write(cx) {
+            return;
+        }
+        let mut command = cmd("SCRIPT");
+        command.arg("LOAD").arg(script.as_str());
+        let label = format!("SCRIPT LOAD {}", abbreviate_script(&script));
+        self.run_console_command(ServerTask::ManageScriptCache, command, label, false, cx);
+    }
+    /// Check if the script is in the script cache (SCRIPT EXISTS)
+    pub fn check_script_exists(&mut self, script: SharedString, cx: &mut Context<Self>) {
+        if script.trim().is_empty() {
+            return;
+        }
+        let hash = Script::new(&script).get_hash().to_string();
+        let mut command = cmd("SCRIPT");
+        command.arg("EXISTS").arg(&hash);
+        let label = format!("SCRIPT EXISTS {hash}");
+        self.run_console_command(ServerTask::CheckScriptExists, command, label, false, cx);
+    }
+    /// Remove all scripts from the script cache (SCRIPT FLUSH)
+    pub fn flush_scripts(&mut self, cx: &mut Context<Self>) {
+        if self.refuse_write(cx) {
+            return;
+        }
+        let mut command = cmd("SCRIPT");
+        command.arg("FLUSH");
+        self.run_console_command(
+            ServerTask::ManageScriptCache,
+            command,
+            "SCRIPT FLUSH".to_string(),
+            false,
+            cx,
+        );
+    }
+    /// Load the function libraries with their code (FUNCTION LIST WITHCODE)
+    pub fn load_function_libraries(&mut self, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::LoadFunctionLibraries,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                if !client.is_at_least_version(FUNCTIONS_VERSION) {
+                    return Ok(vec![]);
+                }
+                let mut conn = client.connection();
+                let value: Value = cmd("FUNCTION")
+                    .arg("LIST")
+                    .arg("WITHCODE")
+                    .query_async(&mut conn)
+                    .await?;
+                Ok(parse_function_libraries(&value))
+            },
+            move |this, result, cx| {
+                if let Ok(libraries) = result {
+                    this.function_libraries = Arc::new(libraries);
+                }
+                cx.emit(ServerEvent::FunctionLibrariesUpdated);
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Load the library code, replacing the library with the same name (FUNCTION LOAD REPLACE)
+    pub fn load_function_library(&mut self, code: SharedString, cx: &mut Context<Self>) {
+        if code.trim().is_empty() {
+            return;
+        }
+        let mut command = cmd("FUNCTION");
+        command.arg("LOAD").arg("REPLACE").arg(code.as_str());
+        let label = format!("FUNCTION LOAD REPLACE {}", abbreviate_script(&code));
+        self.run_console_command(ServerTask::LoadFunctionLibrary, command, label, true, cx);
+    }
+    /// Delete the library and all its functions (FUNCTION DELETE)
+    pub fn delete_function_library(&mut self, library: SharedString, cx: &mut Context<Self>) {
+        let mut command = cmd("FUNCTION");
+        command.arg("DELETE").arg(library.as_str());
+        let label = format!("FUNCTION DELETE {library}");
+        self.run_console_command(ServerTask::DeleteFunctionLibrary, command, label, true, cx);
+    }
+    /// Call the function (FCALL, or FCALL_RO for read-only calls)
+    pub fn call_function(
+        &mut self,
+        function: SharedString,
+        keys: Vec<SharedString>,
+        args: Vec<SharedString>,
+        read_only: bool,
+        cx: &mut Context<Self>,
+    ) {
+        if !read_only && self.refuse_write(cx) {
+            return;
+        }
+        let name = if read_only { "FCALL_RO" } else { "FCALL" };
+        let mut command = cmd(name);
+        command.arg(function.as_str());
+        let mut label = format!("{name} {function}");
+        append_keys_and_args(&mut command, &mut label, &keys, &args);
+        let task = if read_only {
+            ServerTask::CallReadOnlyFunction
+        } else {
+            // the keys may be written by the function
+            for key in &keys {
+                self.mark_key_written(key.clone());
+            }
+            ServerTask::CallFunction
+        };
+        self.run_console_command(task, command, label, true, cx);
+    }
+    /// Load the scripts saved for the current server
+    pub fn load_saved_scripts(&mut self, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        self.spawn(
+            ServerTask::LoadSavedScripts,
+            move || async move { get_saved_scripts(&server_id) },
+            move |this, result, cx| {
+                if let Ok(scripts) = result {
+                    this.saved_scripts = Arc::new(scripts);
+                }
+                cx.emit(ServerEvent::SavedScriptsUpdated);
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Save the script, replacing the saved script with the same name
+    pub fn save_script(&mut self, mut script: SavedScript, cx: &mut Context<Self>) {
+        if script.name.trim().is_empty() {
+            return;
+        }
+        script.updated_at = unix_ts();
+        let mut scripts = self.saved_scripts.to_vec();
+        if let Some(item) = scripts.iter_mut().find(|item| item.name == script.name) {
+            *item = script;
+        } else {
+            scripts.push(script);
+        }
+        self.update_saved_scripts(scripts, cx);
+    }
+    /// Delete the saved script
+    pub fn delete_saved_script(&mut self, name: SharedString, cx: &mut Context<Self>) {
+        let mut scripts = self.saved_scripts.to_vec();
+        scripts.retain(|item| item.name != name.as_str());
+        self.update_saved_scripts(scripts, cx);
+    }
+    fn update_saved_scripts(&mut self, scripts: Vec<SavedScript>, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        self.saved_scripts = Arc::new(scripts.clone());
+        cx.emit(ServerEvent::SavedScriptsUpdated);
+        cx.notify();
+        self.spawn(
+            ServerTask::SaveScripts,
+            move || async move { save_saved_scripts(&server_id, scripts) },
+            move |_this, _result, _cx| {},
+            cx,
+        );
+    }
+}
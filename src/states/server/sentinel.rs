@@ -0,0 +1,115 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sentinel failover following and replica browsing.
+//!
+//! The `+switch-master` events of the sentinels are watched while a sentinel server
+//! is selected, a failover reconnects to the new master. The replicas of the master
+//! are listed with their lag, and one of them can be browsed (read-only) instead of
+//! the master.
+
+use super::{ServerEvent, ZedisServerState};
+use crate::{
+    connection::{ReplicaInfo, get_connection_manager},
+    states::{NotificationAction, i18n_status_bar},
+};
+use futures::{StreamExt, channel::mpsc};
+use gpui::{SharedString, prelude::*};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use tracing::info;
+
+impl ZedisServerState {
+    /// Returns true if the master of the server is resolved by sentinels
+    pub fn is_sentinel(&self) -> bool {
+        self.is_sentinel
+    }
+    /// Replicas of the master with their lag, refreshed with the redis info
+    pub fn replicas(&self) -> Arc<Vec<ReplicaInfo>> {
+        self.replicas.clone()
+    }
+    /// Address of the replica browsed instead of the master
+    pub fn browsed_replica(&self) -> Option<&SharedString> {
+        self.browsed_replica.as_ref()
+    }
+    /// Returns true if the keys can not be written, a replica is browsed.
+    /// Write tasks are refused and the editors are readonly.
+    pub fn is_readonly(&self) -> bool {
+        self.browsed_replica.is_some()
+    }
+    /// Warns that the keys can not be written while a replica is browsed,
+    /// returns true if the write has to be refused.
+    pub(crate) fn refuse_write(&self, cx: &mut Context<Self>) -> bool {
+        if !self.is_readonly() {
+            return false;
+        }
+        let message = i18n_status_bar(cx, "replica_readonly");
+        cx.emit(ServerEvent::Notification(NotificationAction::new_warning(message)));
+        true
+    }
+    /// Browse the keys of a replica (read-only), or the master again with `None`.
+    /// The server is selected again on the new connection.
+    pub fn browse_replica(&mut self, addr: Option<SharedString>, cx: &mut Context<Self>) {
+        if self.server_id.is_empty() || self.browsed_replica == addr {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        get_connection_manager().set_browsed_replica(&server_id, addr.map(|addr| addr.to_string()));
+        // clear the id so the same server is selected again
+        self.server_id = SharedString::default();
+        self.select(server_id, self.db, cx);
+    }
+    /// Stop watching the failovers of the previous server
+    pub(crate) fn stop_sentinel_watch(&mut self) {
+        if let Some(stop) = self.sentinel_watch.take() {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+    /// Watch the `+switch-master` events of the sentinels of the selected server,
+    /// reconnecting to the new master on a failover.
+    pub(crate) fn watch_sentinel(&mut self, cx: &mut Context<Self>) {
+        self.stop_sentinel_watch();
+        let stop = Arc::new(AtomicBool::new(false));
+        self.sentinel_watch = Some(stop.clone());
+        let server_id = self.server_id.clone();
+        let (tx, mut rx) = mpsc::unbounded();
+        let watch_stop = stop.clone();
+        cx.background_spawn(async move {
+            get_connection_manager()
+                .watch_sentinel(&server_id, watch_stop, tx)
+                .await;
+        })
+        .detach();
+
+        cx.spawn(async move |handle, cx| {
+            while let Some((host, port)) = rx.next().await {
+                if stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                let result = handle.update(cx, |this, cx| {
+                    info!(server_id = this.server_id.as_str(), host, port, "follow the new master");
+                    let message = format!("{} {host}:{port}", i18n_status_bar(cx, "master_switched"));
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_info(message.into())));
+                    this.reconnect(cx);
+                });
+                if result.is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+}
@@ -12,21 +12,165 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! Redis SET operations module.
+//!
+//! This module provides functionality for managing Redis SET data structures:
+//! - Loading members with cursor-based pagination (SSCAN) and filtering
+//! - Adding (SADD) and removing (SREM) members
+//! - Set algebra with other keys (SINTER/SUNION/SDIFF), stored as a new key with *STORE
+//! - Intersection cardinality (SINTERCARD) and membership checks (SMISMEMBER)
+//!
+//! The result of an operation is stored in a temporary key and scanned with SSCAN,
+//! at most `MAX_ALGEBRA_MEMBERS` members are shown. A browsed replica can not store
+//! the result, it is computed client-side then.
+//!
+//! In cluster mode, keys in different hash slots can not be used by one command,
+//! so their results are computed client-side from SMEMBERS (and can not be stored),
+//! only when the keys have at most `MAX_CLIENT_SIDE_MEMBERS` members in total.
+
 use super::{
     KeyType, RedisValueData, ServerTask, ZedisServerState,
-    value::{NotificationAction, RedisSetValue, RedisValue, RedisValueStatus},
+    file::same_slot_temp_key,
+    history::record_history,
+    staging::StagedCommand,
+    value::{NotificationAction, RedisElement, RedisSetValue, RedisValue, RedisValueStatus, SetAlgebra, SetOperation},
 };
 use crate::{
-    connection::{RedisAsyncConn, get_connection_manager},
+    connection::{RedisAsyncConn, RedisClient, get_connection_manager},
     error::Error,
+    helpers::{escape_key, unescape_key},
     states::{ServerEvent, i18n_set_editor},
 };
 use gpui::{SharedString, prelude::*};
-use redis::cmd;
-use std::sync::Arc;
+use redis::{cmd, pipe};
+use std::{collections::HashSet, sync::Arc};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// First version supporting SINTERCARD
+const SINTERCARD_VERSION: &str = "7.0.0";
+/// First version supporting SMISMEMBER
+const SMISMEMBER_VERSION: &str = "6.2.0";
+/// Maximum number of members listed in the membership check notification
+const MAX_LISTED_MEMBERS: usize = 20;
+/// Maximum number of members of a set operation result that are loaded
+const MAX_ALGEBRA_MEMBERS: usize = 10_000;
+/// Maximum number of members loaded with SMEMBERS to compute a set operation client-side
+const MAX_CLIENT_SIDE_MEMBERS: usize = 100_000;
+/// TTL(ms) of the temporary key a set operation result is stored in, in case it is not deleted
+const ALGEBRA_TEMP_KEY_TTL: u64 = 60 * 1000;
+
+/// Checks whether all keys hash to the same cluster slot (CLUSTER KEYSLOT),
+/// always true for standalone and sentinel servers.
+pub(super) async fn is_same_slot(client: &RedisClient, keys: &[SharedString]) -> Result<bool> {
+    if !client.is_cluster() {
+        return Ok(true);
+    }
+    let mut conn = client.connection();
+    let mut slots = HashSet::new();
+    for key in keys {
+        let slot: u16 = cmd("CLUSTER")
+            .arg("KEYSLOT")
+            .arg(unescape_key(key))
+            .query_async(&mut conn)
+            .await?;
+        slots.insert(slot);
+    }
+    Ok(slots.len() <= 1)
+}
+
+/// Computes the set operation client-side from the members of each key,
+/// refused if the keys have more than `MAX_CLIENT_SIDE_MEMBERS` members in total.
+async fn compute_set_algebra(
+    conn: &mut RedisAsyncConn,
+    operation: SetOperation,
+    keys: &[SharedString],
+) -> Result<Vec<Vec<u8>>> {
+    let mut pipeline = pipe();
+    for key in keys {
+        pipeline.cmd("SCARD").arg(unescape_key(key));
+    }
+    let sizes: Vec<usize> = pipeline.query_async(conn).await?;
+    let total: usize = sizes.iter().sum();
+    if total > MAX_CLIENT_SIDE_MEMBERS {
+        return Err(Error::Invalid {
+            message: format!(
+                "The keys have {total} members, more than {MAX_CLIENT_SIDE_MEMBERS} can not be computed by the client"
+            ),
+        });
+    }
+    let mut result: Option<HashSet<Vec<u8>>> = None;
+    for key in keys {
+        let members: HashSet<Vec<u8>> = cmd("SMEMBERS").arg(unescape_key(key)).query_async(conn).await?;
+        result = Some(match result {
+            None => members,
+            Some(current) => match operation {
+                SetOperation::Inter => current.intersection(&members).cloned().collect(),
+                SetOperation::Union => current.union(&members).cloned().collect(),
+                SetOperation::Diff => current.difference(&members).cloned().collect(),
+            },
+        });
+    }
+    Ok(result.unwrap_or_default().into_iter().collect())
+}
+
+/// Stores the set operation in a temporary key (SINTERSTORE / SUNIONSTORE / SDIFFSTORE),
+/// deleted once its first `max_members` members are scanned.
+///
+/// Returns the number of members of the result and the scanned members.
+async fn store_and_scan_set_algebra(
+    client: &RedisClient,
+    operation: SetOperation,
+    keys: &[SharedString],
+    max_members: usize,
+) -> Result<(usize, Vec<Vec<u8>>)> {
+    let mut conn = client.connection();
+    let temp_key =
+        same_slot_temp_key(&unescape_key(&keys[0]), client.is_cluster(), "set-algebra").ok_or_else(|| {
+            Error::Invalid {
+                message: format!("No temporary key in the same cluster slot as {}", keys[0]),
+            }
+        })?;
+    let mut command = cmd(operation.store_command());
+    command.arg(&temp_key);
+    for key in keys {
+        command.arg(unescape_key(key));
+    }
+    let (size,): (usize,) = pipe()
+        .atomic()
+        .add_command(command)
+        .cmd("PEXPIRE")
+        .arg(&temp_key)
+        .arg(ALGEBRA_TEMP_KEY_TTL)
+        .ignore()
+        .query_async(&mut conn)
+        .await?;
+    let scan = async {
+        let mut members = Vec::with_capacity(size.min(max_members));
+        let mut cursor = 0;
+        while members.len() < max_members {
+            let (next_cursor, values): (u64, Vec<Vec<u8>>) = cmd("SSCAN")
+                .arg(&temp_key)
+                .arg(cursor)
+                .arg("COUNT")
+                .arg(1000)
+                .query_async(&mut conn)
+                .await?;
+            members.extend(values);
+            cursor = next_cursor;
+            if cursor == 0 {
+                break;
+            }
+        }
+        members.truncate(max_members);
+        Ok::<_, Error>(members)
+    };
+    let result = scan.await;
+    // the temporary key also expires by itself
+    let _: Result<(), _> = cmd("DEL").arg(&temp_key).query_async(&mut conn).await;
+    Ok((size, result?))
+}
+
 /// Retrieves SET members using Redis SSCAN command for cursor-based pagination.
 ///
 /// # Arguments
@@ -44,7 +188,7 @@ async fn get_redis_set_value(
     keyword: Option<SharedString>,
     cursor: u64,
     count: usize,
-) -> Result<(u64, Vec<String>)> {
+) -> Result<(u64, Vec<RedisElement>)> {
     // Build pattern: wrap keyword with wildcards or match all
     let pattern = keyword
         .as_ref()
@@ -53,7 +197,7 @@ async fn get_redis_set_value(
 
     // Execute SSCAN with MATCH and COUNT options
     let (next_cursor, raw_values): (u64, Vec<Vec<u8>>) = cmd("SSCAN")
-        .arg(key)
+        .arg(unescape_key(key))
         .arg(cursor)
         .arg("MATCH")
         .arg(pattern)
@@ -67,11 +211,8 @@ async fn get_redis_set_value(
         return Ok((next_cursor, vec![]));
     }
 
-    // Convert bytes to UTF-8 strings (lossy conversion for non-UTF8 data)
-    let values = raw_values
-        .iter()
-        .map(|v| String::from_utf8_lossy(v).to_string())
-        .collect();
+    // Keep raw bytes, so binary members are not corrupted
+    let values = raw_values.into_iter().map(RedisElement::new).collect();
 
     Ok((next_cursor, values))
 }
@@ -89,7 +230,7 @@ async fn get_redis_set_value(
 /// A `RedisValue` containing SET metadata and initial member values
 pub(crate) async fn first_load_set_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisValue> {
     // Get total number of members in the SET
-    let size: usize = cmd("SCARD").arg(key).query_async(conn).await?;
+    let size: usize = cmd("SCARD").arg(unescape_key(key)).query_async(conn).await?;
 
     // Load first batch of values (up to 100 members)
     let (cursor, values) = get_redis_set_value(conn, key, None, 0, 100).await?;
@@ -102,7 +243,7 @@ pub(crate) async fn first_load_set_value(conn: &mut RedisAsyncConn, key: &str) -
         data: Some(RedisValueData::Set(Arc::new(RedisSetValue {
             cursor,
             size,
-            values: values.into_iter().map(|v| v.into()).collect(),
+            values,
             done,
             ..Default::default()
         }))),
@@ -120,6 +261,10 @@ impl ZedisServerState {
     /// * `new_value` - The member value to add to the SET
     /// * `cx` - GPUI context for spawning async tasks and UI updates
     pub fn add_set_value(&mut self, new_value: SharedString, cx: &mut Context<Self>) {
+        if let Some(key) = self.staging_key() {
+            self.stage_commands(vec![StagedCommand::new(&key, "SADD").arg(new_value.as_bytes())], cx);
+            return;
+        }
         // Early return if no key/value is selected
         let Some((key, value)) = self.try_get_mut_key_value() else {
             return;
@@ -132,17 +277,18 @@ impl ZedisServerState {
         let server_id = self.server_id.clone();
         let db = self.db;
         let key_clone = key.clone();
-        let new_value_clone = new_value.clone();
+        let new_element = RedisElement::from_text(&new_value);
 
         self.spawn(
             ServerTask::AddSetValue,
             // Async operation: execute SADD on Redis
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                record_history(&mut conn, &server_id, db, &key, ServerTask::AddSetValue, None).await;
 
                 // SADD returns number of elements added (0 if already exists, 1 if new)
                 let count: usize = cmd("SADD")
-                    .arg(key.as_str())
+                    .arg(unescape_key(&key))
                     .arg(new_value.as_str())
                     .query_async(&mut conn)
                     .await?;
@@ -170,8 +316,8 @@ impl ZedisServerState {
                             set.size += count;
 
                             // Only add to UI list if scan is complete and value isn't already shown
-                            if set.done && !set.values.contains(&new_value_clone) {
-                                set.values.push(new_value_clone);
+                            if set.done && !set.values.iter().any(|item| item.bytes == new_element.bytes) {
+                                set.values.push(new_element);
                             }
 
                             cx.emit(ServerEvent::Notification(
@@ -239,6 +385,7 @@ impl ZedisServerState {
 
         let server_id = self.server_id.clone();
         let db = self.db;
+        let written = self.is_key_written(&key);
         cx.emit(ServerEvent::ValuePaginationStarted(key.clone()));
 
         let key_clone = key.clone();
@@ -248,7 +395,9 @@ impl ZedisServerState {
             ServerTask::LoadMoreValue,
             // Async operation: fetch next batch using SSCAN
             move || async move {
-                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut conn = get_connection_manager()
+                    .get_value_connection(&server_id, db, written)
+                    .await?;
 
                 // Use larger batch size when filtering to reduce round trips
                 let count = if keyword.is_some() { 1000 } else { 100 };
@@ -272,7 +421,7 @@ impl ZedisServerState {
 
                     // Append new members to existing list
                     if !new_values.is_empty() {
-                        set.values.extend(new_values.into_iter().map(|v| v.into()));
+                        set.values.extend(new_values);
                     }
 
                     // Auto-load more batches when filtering until we have enough results
@@ -306,7 +455,14 @@ impl ZedisServerState {
     /// # Arguments
     /// * `remove_value` - The member value to remove from the SET
     /// * `cx` - GPUI context for spawning async tasks and UI updates
-    pub fn remove_set_value(&mut self, remove_value: SharedString, cx: &mut Context<Self>) {
+    pub fn remove_set_value(&mut self, remove_value: RedisElement, cx: &mut Context<Self>) {
+        if let Some(key) = self.staging_key() {
+            self.stage_commands(
+                vec![StagedCommand::new(&key, "SREM").arg(remove_value.bytes.as_ref())],
+                cx,
+            );
+            return;
+        }
         let Some((key, value)) = self.try_get_mut_key_value() else {
             return;
         };
@@ -317,7 +473,7 @@ impl ZedisServerState {
 
         let server_id = self.server_id.clone();
         let db = self.db;
-        let remove_value_clone = remove_value.clone();
+        let remove_bytes = remove_value.bytes.clone();
         let key_clone = key.clone();
 
         self.spawn(
@@ -325,11 +481,21 @@ impl ZedisServerState {
             // Async operation: execute SREM on Redis
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let inverse = vec!["SADD".to_string(), key.to_string(), escape_key(&remove_value.bytes)];
+                record_history(
+                    &mut conn,
+                    &server_id,
+                    db,
+                    &key,
+                    ServerTask::RemoveSetValue,
+                    Some(inverse),
+                )
+                .await;
 
                 // SREM returns number of members removed (0 if doesn't exist, 1 if removed)
                 let count: usize = cmd("SREM")
-                    .arg(key.as_str())
-                    .arg(remove_value.as_str())
+                    .arg(unescape_key(&key))
+                    .arg(remove_value.bytes.as_ref())
                     .query_async(&mut conn)
                     .await?;
                 Ok(count)
@@ -345,7 +511,7 @@ impl ZedisServerState {
                     set.size -= count;
 
                     // Remove from local values list
-                    set.values.retain(|v| v != &remove_value_clone);
+                    set.values.retain(|v| v.bytes != remove_bytes);
                 }
 
                 cx.emit(ServerEvent::ValueUpdated(key_clone));
@@ -359,4 +525,257 @@ impl ZedisServerState {
             cx,
         );
     }
+    /// Shows the result of a set operation between the selected set and other keys,
+    /// `None` browses the members of the set again.
+    ///
+    /// Keys in different cluster slots are computed client-side with a warning.
+    pub fn set_set_algebra(&mut self, algebra: Option<SetAlgebra>, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        if algebra.as_ref().is_some_and(|algebra| algebra.keys.is_empty()) {
+            return;
+        }
+        value.status = RedisValueStatus::Loading;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+
+        self.spawn(
+            ServerTask::LoadSetAlgebra,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = client.connection();
+                let Some(algebra) = algebra else {
+                    let value = first_load_set_value(&mut conn, &key).await?;
+                    return Ok((value.set_value().cloned().unwrap_or_default(), true));
+                };
+                let mut keys = vec![key.clone()];
+                keys.extend(algebra.keys.iter().cloned());
+                let same_slot = is_same_slot(&client, &keys).await?;
+                let (size, members) = if same_slot && client.browsed_replica().is_none() {
+                    store_and_scan_set_algebra(&client, algebra.operation, &keys, MAX_ALGEBRA_MEMBERS).await?
+                } else {
+                    let members = compute_set_algebra(&mut conn, algebra.operation, &keys).await?;
+                    (members.len(), members)
+                };
+                let values: Vec<RedisElement> = members.into_iter().map(RedisElement::new).collect();
+                Ok((
+                    Arc::new(RedisSetValue {
+                        size,
+                        values,
+                        done: true,
+                        algebra: Some(algebra),
+                        ..Default::default()
+                    }),
+                    same_slot,
+                ))
+            },
+            move |this, result, cx| {
+                // the selected key has been changed
+                if this.key.as_ref() != Some(&key_clone) {
+                    return;
+                }
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                    if let Ok((set, same_slot)) = result {
+                        if set.values.len() < set.size {
+                            let message = format!(
+                                "{} {}/{}",
+                                i18n_set_editor(cx, "algebra_truncated_tips"),
+                                set.values.len(),
+                                set.size
+                            );
+                            cx.emit(ServerEvent::Notification(NotificationAction::new_warning(
+                                message.into(),
+                            )));
+                        }
+                        value.data = Some(RedisValueData::Set(set));
+                        if !same_slot {
+                            cx.emit(ServerEvent::Notification(NotificationAction::new_warning(
+                                i18n_set_editor(cx, "cross_slot_tips"),
+                            )));
+                        }
+                    }
+                }
+                cx.emit(ServerEvent::ValueLoaded(key_clone));
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Stores the result of the current set operation as a new key (SINTERSTORE / SUNIONSTORE / SDIFFSTORE).
+    pub fn store_set_algebra(&mut self, destination: SharedString, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let Some(algebra) = value.set_value().and_then(|set| set.algebra.clone()) else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.mark_key_written(destination.clone());
+
+        self.spawn(
+            ServerTask::StoreSetAlgebra,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = client.connection();
+                let mut keys = vec![destination.clone(), key];
+                keys.extend(algebra.keys.iter().cloned());
+                if !is_same_slot(&client, &keys).await? {
+                    return Err(Error::Invalid {
+                        message: "CROSSSLOT Keys in request don't hash to the same slot".to_string(),
+                    });
+                }
+                record_history(
+                    &mut conn,
+                    &server_id,
+                    db,
+                    &destination,
+                    ServerTask::StoreSetAlgebra,
+                    None,
+                )
+                .await;
+                let mut command = cmd(algebra.operation.store_command());
+                for key in &keys {
+                    command.arg(unescape_key(key));
+                }
+                let count: usize = command.query_async(&mut conn).await?;
+                Ok(count)
+            },
+            move |_this, result, cx| {
+                if let Ok(count) = result {
+                    let message = format!("{} {count}", i18n_set_editor(cx, "store_result_success"));
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                        message.into(),
+                    )));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Counts the members in the intersection of the set and other keys (SINTERCARD),
+    /// without transferring the members on Redis 7.0+.
+    pub fn count_set_intersection(&mut self, keys: Vec<SharedString>, cx: &mut Context<Self>) {
+        let Some((key, _)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+
+        self.spawn(
+            ServerTask::CountSetIntersection,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = client.connection();
+                let mut all_keys = vec![key];
+                all_keys.extend(keys);
+                let same_slot = is_same_slot(&client, &all_keys).await?;
+                let can_store = same_slot && client.browsed_replica().is_none();
+                if !same_slot || (!can_store && !client.is_at_least_version(SINTERCARD_VERSION)) {
+                    let members = compute_set_algebra(&mut conn, SetOperation::Inter, &all_keys).await?;
+                    return Ok(members.len());
+                }
+                let count: usize = if client.is_at_least_version(SINTERCARD_VERSION) {
+                    let mut command = cmd("SINTERCARD");
+                    command.arg(all_keys.len());
+                    for key in &all_keys {
+                        command.arg(unescape_key(key));
+                    }
+                    command.query_async(&mut conn).await?
+                } else {
+                    // SINTERSTORE counts the members without transferring them
+                    let (count, _) = store_and_scan_set_algebra(&client, SetOperation::Inter, &all_keys, 0).await?;
+                    count
+                };
+                Ok(count)
+            },
+            move |_this, result, cx| {
+                if let Ok(count) = result {
+                    let message = format!("{} {count}", i18n_set_editor(cx, "intersection_count"));
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_info(message.into())));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Checks which of the members exist in the set (SMISMEMBER, SISMEMBER before Redis 6.2).
+    pub fn check_set_members(&mut self, members: Vec<SharedString>, cx: &mut Context<Self>) {
+        let Some((key, _)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        if members.is_empty() {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+
+        self.spawn(
+            ServerTask::CheckSetMembers,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = client.connection();
+                let elements: Vec<RedisElement> = members.iter().map(|item| RedisElement::from_text(item)).collect();
+                let exists: Vec<bool> = if client.is_at_least_version(SMISMEMBER_VERSION) {
+                    let mut command = cmd("SMISMEMBER");
+                    command.arg(unescape_key(&key));
+                    for element in &elements {
+                        command.arg(element.bytes.as_ref());
+                    }
+                    command.query_async(&mut conn).await?
+                } else {
+                    let mut pipeline = pipe();
+                    for element in &elements {
+                        pipeline
+                            .cmd("SISMEMBER")
+                            .arg(unescape_key(&key))
+                            .arg(element.bytes.as_ref());
+                    }
+                    pipeline.query_async(&mut conn).await?
+                };
+                let missing: Vec<SharedString> = members
+                    .into_iter()
+                    .zip(&exists)
+                    .filter(|(_, exists)| !**exists)
+                    .map(|(member, _)| member)
+                    .collect();
+                Ok((exists.len(), missing))
+            },
+            move |_this, result, cx| {
+                if let Ok((total, missing)) = result {
+                    let title = i18n_set_editor(cx, "check_members_result");
+                    let mut message = format!(
+                        "{} {}/{total}",
+                        i18n_set_editor(cx, "check_members_found"),
+                        total - missing.len()
+                    );
+                    if !missing.is_empty() {
+                        let mut listed = missing
+                            .iter()
+                            .take(MAX_LISTED_MEMBERS)
+                            .map(|item| item.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        if missing.len() > MAX_LISTED_MEMBERS {
+                            listed.push_str(", ...");
+                        }
+                        message = format!("{message}\n{} {listed}", i18n_set_editor(cx, "check_members_missing"));
+                    }
+                    let action = if missing.is_empty() {
+                        NotificationAction::new_success(message.into())
+                    } else {
+                        NotificationAction::new_warning(message.into())
+                    };
+                    cx.emit(ServerEvent::Notification(action.with_title(title)));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
 }
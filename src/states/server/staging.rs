@@ -0,0 +1,385 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Staged changes module.
+//!
+//! In staged mode, the writes of the hash, list, set and zset editors are queued
+//! instead of sent, so related changes of several keys can be reviewed first:
+//! - Applied at once in a MULTI/EXEC transaction, or discarded
+//! - The touched keys are WATCHed, and compared with a digest (DUMP) taken when
+//!   each key was first staged, so the apply is aborted if someone else changed them
+//!
+//! Commands run in the staged order, except deferred ones (list pushes and the LREM
+//! of removed list elements) which run last, so the indexes of the displayed list stay valid.
+//! Writes that read the value first (list insert/move/reorder, zset range removal,
+//! field TTL, set algebra stores) are always sent immediately.
+
+use super::{
+    ServerEvent, ServerTask, ZedisServerState,
+    history::{save_history_snapshot, take_history_snapshot},
+    set::is_same_slot,
+};
+use crate::{
+    connection::{RedisAsyncConn, get_connection_manager},
+    error::Error,
+    helpers::{escape_key, unescape_key},
+    states::{NotificationAction, i18n_editor},
+};
+use ahash::AHashMap;
+use gpui::{SharedString, prelude::*};
+use redis::{Value, cmd, pipe};
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::Arc,
+};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Maximum number of staged commands
+const MAX_STAGED_COMMANDS: usize = 1000;
+/// Arguments longer than this are abbreviated in the command line
+const MAX_ARG_DISPLAY_LENGTH: usize = 64;
+
+/// A write queued in staged mode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StagedCommand {
+    /// The key written by the command
+    pub key: SharedString,
+    /// Command name, the key and the arguments, escaped (see `escape_key`)
+    pub args: Vec<String>,
+    /// Commands staged by one edit share the group, and are removed together
+    pub group: usize,
+    /// Whether the command runs after all other commands
+    pub deferred: bool,
+}
+
+impl StagedCommand {
+    /// A command writing the key, e.g. `StagedCommand::new(key, "HDEL").arg(field)`.
+    pub fn new(key: &SharedString, name: &str) -> Self {
+        Self {
+            key: key.clone(),
+            args: vec![name.to_string(), key.to_string()],
+            group: 0,
+            deferred: false,
+        }
+    }
+    /// Run the command after all other commands.
+    pub fn deferred(mut self) -> Self {
+        self.deferred = true;
+        self
+    }
+    /// Append a raw argument.
+    pub fn arg(mut self, value: impl AsRef<[u8]>) -> Self {
+        self.args.push(escape_key(value.as_ref()));
+        self
+    }
+    /// The command as it would be typed in redis-cli, long arguments abbreviated.
+    pub fn command_line(&self) -> String {
+        self.args
+            .iter()
+            .map(|arg| {
+                let arg = if arg.chars().count() > MAX_ARG_DISPLAY_LENGTH {
+                    let prefix: String = arg.chars().take(MAX_ARG_DISPLAY_LENGTH).collect();
+                    format!("{prefix}...")
+                } else {
+                    arg.clone()
+                };
+                if arg.is_empty() || arg.contains(char::is_whitespace) {
+                    format!("\"{arg}\"")
+                } else {
+                    arg
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// The commands in the order they run: staged order, deferred commands last.
+fn execution_order(commands: &[StagedCommand]) -> impl Iterator<Item = &StagedCommand> {
+    let (immediate, deferred): (Vec<_>, Vec<_>) = commands.iter().partition(|command| !command.deferred);
+    immediate.into_iter().chain(deferred)
+}
+
+/// Digest of the serialized value of the key, `None` if the key does not exist.
+async fn key_digest(conn: &mut RedisAsyncConn, key: &str) -> Result<Option<u64>> {
+    let dump: Option<Vec<u8>> = cmd("DUMP").arg(unescape_key(key)).query_async(conn).await?;
+    Ok(dump.map(|dump| {
+        let mut hasher = DefaultHasher::new();
+        dump.hash(&mut hasher);
+        hasher.finish()
+    }))
+}
+
+impl ZedisServerState {
+    /// Check if writes are staged instead of sent
+    pub fn is_staging(&self) -> bool {
+        self.staging
+    }
+    /// Turn staged mode on or off, the staged commands are kept
+    pub fn set_staging(&mut self, staging: bool, cx: &mut Context<Self>) {
+        self.staging = staging;
+        cx.emit(ServerEvent::StagedChangesUpdated);
+        cx.notify();
+    }
+    /// Get the staged commands, in order
+    pub fn staged_commands(&self) -> Arc<Vec<StagedCommand>> {
+        self.staged_commands.clone()
+    }
+    /// Check if the staged commands are being applied
+    pub fn is_applying_staged_commands(&self) -> bool {
+        self.applying_staged_commands
+    }
+    /// The selected key, if writes of it have to be staged
+    pub(crate) fn staging_key(&self) -> Option<SharedString> {
+        if !self.staging {
+            return None;
+        }
+        self.key.clone()
+    }
+    /// Queue the commands, taking the digest of keys staged for the first time
+    pub(crate) fn stage_commands(&mut self, commands: Vec<StagedCommand>, cx: &mut Context<Self>) {
+        if self.refuse_write(cx) {
+            return;
+        }
+        if self.staged_commands.len() + commands.len() > MAX_STAGED_COMMANDS {
+            let message = i18n_editor(cx, "staged_changes_too_many");
+            cx.emit(ServerEvent::Notification(NotificationAction::new_warning(message)));
+            return;
+        }
+        let mut new_keys = vec![];
+        for command in &commands {
+            if !self.staged_digests.contains_key(&command.key) && !new_keys.contains(&command.key) {
+                new_keys.push(command.key.clone());
+            }
+        }
+        let group = self.staged_commands.last().map_or(0, |command| command.group + 1);
+        let mut staged_commands = self.staged_commands.to_vec();
+        staged_commands.extend(commands.into_iter().map(|command| StagedCommand { group, ..command }));
+        self.staged_commands = Arc::new(staged_commands);
+        cx.emit(ServerEvent::StagedChangesUpdated);
+        cx.notify();
+        if new_keys.is_empty() {
+            return;
+        }
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::StageChanges,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut digests = Vec::with_capacity(new_keys.len());
+                for key in new_keys {
+                    let digest = key_digest(&mut conn, &key).await?;
+                    digests.push((key, digest));
+                }
+                Ok(digests)
+            },
+            move |this, result, _cx| {
+                if let Ok(digests) = result {
+                    for (key, digest) in digests {
+                        this.staged_digests.entry(key).or_insert(digest);
+                    }
+                }
+            },
+            cx,
+        );
+    }
+    /// Remove the commands staged by one edit
+    pub fn remove_staged_group(&mut self, group: usize, cx: &mut Context<Self>) {
+        let mut staged_commands = self.staged_commands.to_vec();
+        staged_commands.retain(|command| command.group != group);
+        // keep the digest of keys still written by other commands
+        self.staged_digests
+            .retain(|key, _| staged_commands.iter().any(|command| &command.key == key));
+        self.staged_commands = Arc::new(staged_commands);
+        cx.emit(ServerEvent::StagedChangesUpdated);
+        cx.notify();
+    }
+    /// Discard all staged commands
+    pub fn discard_staged_commands(&mut self, cx: &mut Context<Self>) {
+        self.staged_commands = Arc::new(vec![]);
+        self.staged_digests.clear();
+        cx.emit(ServerEvent::StagedChangesUpdated);
+        cx.notify();
+    }
+    /// Apply the staged commands in one MULTI/EXEC.
+    ///
+    /// The touched keys are WATCHed and compared with their digest of staging time,
+    /// the apply is aborted (and the commands kept) if any of them has been changed.
+    /// It is refused while the digest of a key is still being taken (or could not be taken).
+    pub fn apply_staged_commands(&mut self, cx: &mut Context<Self>) {
+        if self.staged_commands.is_empty() || self.applying_staged_commands {
+            return;
+        }
+        let commands = self.staged_commands.clone();
+        let digests = self.staged_digests.clone();
+        let mut keys: Vec<SharedString> = vec![];
+        for command in commands.iter() {
+            if !keys.contains(&command.key) {
+                keys.push(command.key.clone());
+            }
+        }
+        if keys.iter().any(|key| !digests.contains_key(key)) {
+            let message = i18n_editor(cx, "staged_changes_not_verified");
+            cx.emit(ServerEvent::Notification(NotificationAction::new_warning(message)));
+            return;
+        }
+        self.applying_staged_commands = true;
+        for key in &keys {
+            self.mark_key_written(key.clone());
+        }
+        cx.notify();
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::ApplyStagedChanges,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                if !is_same_slot(&client, &keys).await? {
+                    return Err(Error::Invalid {
+                        message: "Staged changes of keys in different hash slots can not be applied in one transaction"
+                            .to_string(),
+                    });
+                }
+                // WATCH applies to the whole connection, the shared one also runs other tasks
+                let mut conn = client.dedicated_connection().await?;
+                let mut watch = cmd("WATCH");
+                for key in &keys {
+                    watch.arg(unescape_key(key));
+                }
+                let _: () = watch.query_async(&mut conn).await?;
+
+                let changed_keys = verify_digests(&mut conn, &keys, &digests).await;
+                let changed_keys = match changed_keys {
+                    Ok(changed_keys) => changed_keys,
+                    Err(e) => {
+                        let _: Result<(), _> = cmd("UNWATCH").query_async(&mut conn).await;
+                        return Err(e);
+                    }
+                };
+                if !changed_keys.is_empty() {
+                    let _: () = cmd("UNWATCH").query_async(&mut conn).await?;
+                    return Err(Error::Invalid {
+                        message: format!("Changed since the changes were staged: {}", changed_keys.join(", ")),
+                    });
+                }
+
+                // reading does not touch the watched keys,
+                // the snapshots are only saved once the transaction is executed
+                let mut snapshots = Vec::with_capacity(keys.len());
+                for key in &keys {
+                    snapshots.push(
+                        take_history_snapshot(&mut conn, &server_id, db, key, ServerTask::ApplyStagedChanges, None)
+                            .await,
+                    );
+                }
+
+                let mut pipeline = pipe();
+                pipeline.atomic();
+                for command in execution_order(&commands) {
+                    let Some((name, args)) = command.args.split_first() else {
+                        continue;
+                    };
+                    let staged = pipeline.cmd(name);
+                    for arg in args {
+                        staged.arg(unescape_key(arg));
+                    }
+                }
+                // EXEC replies nil when a watched key has been changed
+                let replies: Option<Vec<Value>> = pipeline.query_async(&mut conn).await?;
+                let Some(replies) = replies else {
+                    return Ok(None);
+                };
+                for snapshot in snapshots {
+                    save_history_snapshot(snapshot);
+                }
+                let failed = replies
+                    .iter()
+                    .filter(|reply| matches!(reply, Value::ServerError(_)))
+                    .count();
+                Ok(Some(failed))
+            },
+            move |this, result, cx| {
+                this.applying_staged_commands = false;
+                match result {
+                    Ok(Some(failed)) => {
+                        this.staged_commands = Arc::new(vec![]);
+                        this.staged_digests.clear();
+                        let message = if failed == 0 {
+                            NotificationAction::new_success(i18n_editor(cx, "staged_changes_applied"))
+                        } else {
+                            let message = format!("{} {failed}", i18n_editor(cx, "staged_changes_partially_applied"));
+                            NotificationAction::new_warning(message.into())
+                        };
+                        cx.emit(ServerEvent::Notification(message));
+                        // reload the value of the selected key
+                        if let Some(key) = this.key.clone() {
+                            this.select_key(key, cx);
+                        }
+                    }
+                    Ok(None) => {
+                        let message = i18n_editor(cx, "staged_changes_aborted");
+                        cx.emit(ServerEvent::Notification(NotificationAction::new_warning(message)));
+                    }
+                    Err(_) => {}
+                }
+                cx.emit(ServerEvent::StagedChangesUpdated);
+                cx.notify();
+            },
+            cx,
+        );
+    }
+}
+
+/// Keys whose digest differs from the one taken when they were staged.
+async fn verify_digests(
+    conn: &mut RedisAsyncConn,
+    keys: &[SharedString],
+    digests: &AHashMap<SharedString, Option<u64>>,
+) -> Result<Vec<SharedString>> {
+    let mut changed_keys = vec![];
+    for key in keys {
+        // a key without digest can not be verified
+        let expected = digests.get(key).ok_or_else(|| Error::Invalid {
+            message: format!("The digest of {key} has not been taken"),
+        })?;
+        if key_digest(conn, key).await? != *expected {
+            changed_keys.push(key.clone());
+        }
+    }
+    Ok(changed_keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_execution_order_push_then_remove() {
+        let key = SharedString::from("list");
+        // LPUSH staged first, then the removal of the displayed element at index 2
+        let commands = vec![
+            StagedCommand::new(&key, "LPUSH").arg("new").deferred(),
+            StagedCommand::new(&key, "LSET").arg("2").arg("marker"),
+            StagedCommand::new(&key, "LREM").arg("1").arg("marker").deferred(),
+        ];
+        let order: Vec<_> = execution_order(&commands)
+            .map(|command| command.args[0].as_str())
+            .collect();
+        // the LSET runs before the push shifts the indexes
+        assert_eq!(vec!["LSET", "LPUSH", "LREM"], order);
+    }
+}
@@ -17,6 +17,7 @@ use crate::states::{ServerEvent, ServerTask, ZedisServerState};
 use gpui::prelude::*;
 use redis::cmd;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 use tracing::error;
@@ -269,11 +270,14 @@ impl ZedisServerState {
                 let infos: Vec<RedisInfo> = list.iter().map(|info| RedisInfo::parse(info)).collect();
                 let mut info = aggregate_redis_info(infos);
                 info.latency = latency;
-                Ok(info)
+                // the replicas are informative, their failure does not fail the refresh
+                let replicas = client.replicas().await.unwrap_or_default();
+                Ok((info, replicas))
             },
             move |this, result, cx| match result {
-                Ok(info) => {
+                Ok((info, replicas)) => {
                     this.redis_info = Some(info);
+                    this.replicas = Arc::new(replicas);
                     cx.emit(ServerEvent::ServerRedisInfoUpdated(server_id_clone.clone()));
                 }
                 Err(e) => {
@@ -12,19 +12,35 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::value::{DataFormat, KeyType, RedisBytesValue, RedisValue, RedisValueData, detect_format};
-use crate::helpers::decompress_zstd;
-use crate::{connection::RedisAsyncConn, error::Error};
+use super::value::{
+    DataFormat, KeyType, PartialValue, RedisBytesValue, RedisValue, RedisValueData, detect_format, is_valid_messagepack,
+};
+use crate::helpers::{
+    compress_brotli, compress_lz4, compress_snappy, compress_snappy_raw, decode_protobuf, decompress_brotli,
+    decompress_lz4, decompress_snappy, decompress_snappy_raw, decompress_zstd, run_formatter_command, unescape_key,
+};
+use crate::{connection::RedisAsyncConn, error::Error, states::ValueFormatter};
 use bytes::Bytes;
 use flate2::read::GzDecoder;
 use gpui::SharedString;
+use prost_reflect::MessageDescriptor;
 use redis::cmd;
 use serde_json::Value;
 use std::io::Read;
 use std::sync::Arc;
+use tracing::error;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// Number of bytes loaded per page of a partially loaded value
+pub const PARTIAL_PAGE_SIZE: usize = 256 * 1024;
+/// Max decompressed size of the compressions without magic number
+const MAGICLESS_MAX_SIZE: usize = 16 * 1024 * 1024;
+/// Max expansion of a raw snappy block (a 64 bytes copy is encoded in 3 bytes)
+const SNAPPY_RAW_MAX_RATIO: usize = 32;
+/// Max expansion accepted for brotli, higher ratios are treated as invalid data
+const BROTLI_MAX_RATIO: usize = 128;
+
 fn truncate_long_strings(max_truncate_length: usize, v: &mut Value, truncated: &mut bool) {
     match v {
         Value::String(s) => {
@@ -54,7 +70,7 @@ fn truncate_long_strings(max_truncate_length: usize, v: &mut Value, truncated: &
 
 /// Attempts to format a string as pretty-printed JSON.
 /// Returns None if the string is not valid JSON or doesn't look like JSON.
-fn pretty_json(value: &str, max_truncate_length: usize) -> Option<(SharedString, bool)> {
+pub(crate) fn pretty_json(value: &str, max_truncate_length: usize) -> Option<(SharedString, bool)> {
     let trimmed = value.trim();
     if !((trimmed.starts_with('{') && trimmed.ends_with('}')) || (trimmed.starts_with('[') && trimmed.ends_with(']'))) {
         return None;
@@ -85,26 +101,108 @@ fn format_text(data: &[u8], max_truncate_length: usize) -> Option<(DataFormat, S
     }
 }
 
+fn format_messagepack(data: &[u8]) -> Option<(DataFormat, SharedString)> {
+    rmp_serde::from_slice::<serde_json::Value>(data)
+        .ok()
+        .and_then(|v| serde_json::to_string_pretty(&v).ok())
+        .map(|s| (DataFormat::Preview, SharedString::from(s)))
+}
+
+/// Whether the data is not valid UTF-8 or contains control characters
+fn looks_binary(data: &[u8]) -> bool {
+    std::str::from_utf8(data)
+        .map(|s| s.chars().any(|c| c.is_control() && !c.is_whitespace()))
+        .unwrap_or(true)
+}
+
+/// Format decompressed data.
+///
+/// Complete text keeps the compression format, so it can be edited and compressed
+/// again on save. MessagePack and truncated JSON are shown as a readonly preview.
+fn format_decompressed(
+    format: DataFormat,
+    decompressed: &[u8],
+    max_truncate_length: usize,
+) -> Option<(DataFormat, SharedString)> {
+    match format_text(decompressed, max_truncate_length) {
+        Some((DataFormat::Preview, text)) => Some((DataFormat::Preview, text)),
+        Some((_, text)) => Some((format, text)),
+        None if is_valid_messagepack(decompressed) => format_messagepack(decompressed),
+        None => None,
+    }
+}
+
+/// Try compressions without magic number (raw snappy, brotli).
+///
+/// Only accepted if the decompressed data is readable text or MessagePack.
+/// Any binary value is tried, so the decompressed size is capped: snappy can't
+/// expand more than `SNAPPY_RAW_MAX_RATIO` times, larger headers are invalid.
+fn detect_magicless_compression(data: &[u8], max_truncate_length: usize) -> Option<(DataFormat, SharedString)> {
+    let snappy_max_size = data.len().saturating_mul(SNAPPY_RAW_MAX_RATIO).min(MAGICLESS_MAX_SIZE);
+    let brotli_max_size = data.len().saturating_mul(BROTLI_MAX_RATIO).min(MAGICLESS_MAX_SIZE);
+    let try_decompress = |format: DataFormat, decompressed: Result<Vec<u8>>| {
+        let decompressed = decompressed.ok()?;
+        if decompressed.is_empty() || (looks_binary(&decompressed) && !is_valid_messagepack(&decompressed)) {
+            return None;
+        }
+        format_decompressed(format, &decompressed, max_truncate_length)
+    };
+    try_decompress(DataFormat::SnappyRaw, decompress_snappy_raw(data, snappy_max_size))
+        .or_else(|| try_decompress(DataFormat::Brotli, decompress_brotli(data, brotli_max_size)))
+}
+
+/// Compress data with the compression format of the value (used when saving an edit)
+pub(crate) fn compress_bytes(format: DataFormat, data: &[u8]) -> Result<Vec<u8>> {
+    match format {
+        DataFormat::Lz4 => compress_lz4(data),
+        DataFormat::Snappy => compress_snappy(data),
+        DataFormat::SnappyRaw => compress_snappy_raw(data),
+        DataFormat::Brotli => compress_brotli(data),
+        _ => Err(Error::Invalid {
+            message: format!("{} is not a compression format", format.as_str()),
+        }),
+    }
+}
+
 impl RedisBytesValue {
     pub fn detect_and_update(&mut self, max_truncate_length: usize) {
+        self.detect(max_truncate_length, true);
+    }
+    /// Detect the format of a collection member, without trying the compressions
+    /// without magic number (raw snappy, brotli), too costly for every member.
+    pub fn detect_and_update_element(&mut self, max_truncate_length: usize) {
+        self.detect(max_truncate_length, false);
+    }
+    fn detect(&mut self, max_truncate_length: usize, magicless: bool) {
         let data = self.bytes.as_ref();
         if data.is_empty() {
             return;
         }
 
+        // The key is mapped to a protobuf message, decode it before detecting the format
+        if let Some(desc) = &self.protobuf {
+            match decode_protobuf(desc, data) {
+                Ok(text) => {
+                    self.format = DataFormat::Protobuf;
+                    self.mime = None;
+                    self.text = Some(text.into());
+                    return;
+                }
+                Err(e) => {
+                    error!(error = %e, message = desc.full_name(), "decode protobuf value failed");
+                }
+            }
+        }
+
         let (initial_format, mime) = detect_format(data);
         self.mime = mime;
 
         let process_decompressed = |decompressed: Option<Vec<u8>>| {
-            decompressed
-                .and_then(|vec| format_text(&vec, max_truncate_length).map(|(_, text)| (DataFormat::Preview, text)))
+            decompressed.and_then(|vec| format_decompressed(DataFormat::Preview, &vec, max_truncate_length))
         };
 
         let result = match initial_format {
-            DataFormat::MessagePack => rmp_serde::from_slice::<serde_json::Value>(data)
-                .ok()
-                .and_then(|v| serde_json::to_string_pretty(&v).ok())
-                .map(|s| (DataFormat::Preview, SharedString::from(s))),
+            DataFormat::MessagePack => format_messagepack(data),
 
             DataFormat::Gzip => process_decompressed({
                 let mut decoder = GzDecoder::new(data);
@@ -114,9 +212,25 @@ impl RedisBytesValue {
 
             DataFormat::Zstd => process_decompressed(decompress_zstd(data).ok()),
 
+            DataFormat::Lz4 => decompress_lz4(data)
+                .ok()
+                .and_then(|vec| format_decompressed(DataFormat::Lz4, &vec, max_truncate_length)),
+
+            DataFormat::Snappy => decompress_snappy(data)
+                .ok()
+                .and_then(|vec| format_decompressed(DataFormat::Snappy, &vec, max_truncate_length)),
+
             DataFormat::Svg | DataFormat::Jpeg | DataFormat::Png | DataFormat::Webp | DataFormat::Gif => None,
 
-            _ => format_text(data, max_truncate_length),
+            _ => {
+                let result = format_text(data, max_truncate_length);
+                // raw snappy and brotli have no magic number, only try them for binary data
+                if magicless && looks_binary(data) {
+                    detect_magicless_compression(data, max_truncate_length).or(result)
+                } else {
+                    result
+                }
+            }
         };
 
         if let Some((new_format, text)) = result {
@@ -128,21 +242,110 @@ impl RedisBytesValue {
     }
 }
 
+impl RedisBytesValue {
+    /// Detect the format of a page of a partially loaded value.
+    ///
+    /// A page can cut a multi-byte character or a JSON document, so it is never
+    /// decoded or pretty printed, only shown as plain text or hex.
+    pub fn detect_partial(&mut self) {
+        let mut data = self.bytes.as_ref();
+        let (format, mime) = detect_format(data);
+        self.mime = mime;
+        // skip the continuation bytes of a character cut at the page start
+        if self.partial.is_some_and(|partial| partial.offset > 0) {
+            let cut = data.iter().take(3).take_while(|byte| *byte & 0xc0 == 0x80).count();
+            data = &data[cut..];
+        }
+        let text = match std::str::from_utf8(data) {
+            Ok(text) => Some(text),
+            // a character cut at the page end
+            Err(e) if e.error_len().is_none() => std::str::from_utf8(&data[..e.valid_up_to()]).ok(),
+            Err(_) => None,
+        };
+        match text.filter(|text| !text.chars().any(|c| c.is_control() && !c.is_whitespace())) {
+            Some(text) => {
+                self.format = DataFormat::Text;
+                self.text = Some(text.to_string().into());
+            }
+            None => {
+                self.format = format;
+                self.text = None;
+            }
+        }
+    }
+    /// Decode the value with an external command formatter.
+    pub fn format_with(&mut self, formatter: ValueFormatter) -> Result<()> {
+        let output = run_formatter_command(&formatter.decode, &self.bytes)?;
+        self.format = DataFormat::External;
+        self.mime = None;
+        self.text = Some(String::from_utf8_lossy(&output).to_string().into());
+        self.formatter = Some(formatter);
+        Ok(())
+    }
+}
+
+/// Fetch a page of a string value with GETRANGE, starting at the offset.
+pub(crate) async fn get_redis_value_page(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    offset: usize,
+    total: usize,
+) -> Result<RedisBytesValue> {
+    let value_bytes: Vec<u8> = cmd("GETRANGE")
+        .arg(unescape_key(key))
+        .arg(offset)
+        .arg(offset + PARTIAL_PAGE_SIZE - 1)
+        .query_async(conn)
+        .await?;
+    let mut data = RedisBytesValue {
+        bytes: Bytes::from(value_bytes),
+        partial: Some(PartialValue { offset, total }),
+        ..Default::default()
+    };
+    data.detect_partial();
+    Ok(data)
+}
+
 /// Fetch a string value from Redis.
 /// Returns a RedisValue with the string value and the size.
+/// Values larger than `partial_size` (0 for no limit) are checked with STRLEN
+/// and only their first page is loaded.
+/// If a protobuf message is given, the value is decoded as that message.
+/// If a formatter is given, the value is decoded by the external command.
 pub(crate) async fn get_redis_value(
     conn: &mut RedisAsyncConn,
     key: &str,
     max_truncate_length: usize,
+    partial_size: usize,
+    protobuf: Option<MessageDescriptor>,
+    formatter: Option<ValueFormatter>,
 ) -> Result<RedisValue> {
-    let value_bytes: Vec<u8> = cmd("GET").arg(key).query_async(conn).await?;
+    if partial_size > 0 {
+        let size: usize = cmd("STRLEN").arg(unescape_key(key)).query_async(conn).await?;
+        if size > partial_size {
+            let data = get_redis_value_page(conn, key, 0, size).await?;
+            return Ok(RedisValue {
+                key_type: KeyType::String,
+                data: Some(RedisValueData::Bytes(Arc::new(data))),
+                size,
+                ..Default::default()
+            });
+        }
+    }
+    let value_bytes: Vec<u8> = cmd("GET").arg(unescape_key(key)).query_async(conn).await?;
     let size = value_bytes.len();
     let mut data = RedisBytesValue {
         format: DataFormat::Text,
         bytes: Bytes::from(value_bytes),
+        protobuf,
         ..Default::default()
     };
     data.detect_and_update(max_truncate_length);
+    if let Some(formatter) = formatter
+        && let Err(e) = data.format_with(formatter)
+    {
+        error!(error = %e, key, "format value with external command failed");
+    }
     Ok(RedisValue {
         key_type: KeyType::String,
         data: Some(RedisValueData::Bytes(Arc::new(data))),
@@ -12,14 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{ServerEvent, ServerTask, ZedisServerState};
-use crate::connection::get_connection_manager;
+use super::{
+    ServerEvent, ServerTask, ZedisServerState,
+    history::{record_history, string_inverse},
+    string::{compress_bytes, get_redis_value_page},
+};
+use crate::{
+    connection::get_connection_manager,
+    helpers::{encode_protobuf, escape_key, run_formatter_command, unescape_key},
+    states::{ValueFormatter, ZedisGlobalStore},
+};
 use bytes::Bytes;
 use chrono::Local;
-use gpui::{Action, Hsla, SharedString, prelude::*};
+use gpui::{Action, Hsla, Image, ImageFormat, SharedString, prelude::*};
+use prost_reflect::MessageDescriptor;
 use redis::cmd;
 use schemars::JsonSchema;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::io::Cursor;
 use std::sync::Arc;
 
@@ -99,10 +109,40 @@ pub enum DataFormat {
     Gif,
     Gzip,
     Zstd,
+    Lz4,
+    /// Snappy framing format
+    Snappy,
+    /// Snappy raw block without framing
+    SnappyRaw,
+    Brotli,
     MessagePack,
+    Protobuf,
+    /// Decoded by an external command formatter
+    External,
 }
 
 impl DataFormat {
+    pub fn is_image(&self) -> bool {
+        self.image_format().is_some()
+    }
+    /// The gpui image format used to render image data
+    pub fn image_format(&self) -> Option<ImageFormat> {
+        match self {
+            DataFormat::Png => Some(ImageFormat::Png),
+            DataFormat::Webp => Some(ImageFormat::Webp),
+            DataFormat::Gif => Some(ImageFormat::Gif),
+            DataFormat::Svg => Some(ImageFormat::Svg),
+            DataFormat::Jpeg => Some(ImageFormat::Jpeg),
+            _ => None,
+        }
+    }
+    /// Whether the format is a compression that can be applied again on save
+    pub fn is_compression(&self) -> bool {
+        matches!(
+            self,
+            DataFormat::Lz4 | DataFormat::Snappy | DataFormat::SnappyRaw | DataFormat::Brotli
+        )
+    }
     pub fn as_str(&self) -> &'static str {
         match self {
             DataFormat::Bytes => "bytes",
@@ -116,12 +156,18 @@ impl DataFormat {
             DataFormat::Gif => "gif",
             DataFormat::Gzip => "gzip",
             DataFormat::Zstd => "zstd",
+            DataFormat::Lz4 => "lz4",
+            DataFormat::Snappy => "snappy",
+            DataFormat::SnappyRaw => "snappy-raw",
+            DataFormat::Brotli => "brotli",
             DataFormat::MessagePack => "messagepack",
+            DataFormat::Protobuf => "protobuf",
+            DataFormat::External => "external",
         }
     }
 }
 
-fn is_valid_messagepack(bytes: &[u8]) -> bool {
+pub(crate) fn is_valid_messagepack(bytes: &[u8]) -> bool {
     if bytes.is_empty() {
         return false;
     }
@@ -149,6 +195,9 @@ fn is_valid_messagepack(bytes: &[u8]) -> bool {
     }
 }
 
+/// Snappy framing format starts with the stream identifier chunk
+const SNAPPY_STREAM_IDENTIFIER: &[u8] = b"\xff\x06\x00\x00sNaPpY";
+
 fn is_svg(bytes: &[u8]) -> bool {
     // only check 4kb
     let check_len = std::cmp::min(bytes.len(), 4096);
@@ -184,6 +233,11 @@ pub fn detect_format(bytes: &[u8]) -> (DataFormat, Option<SharedString>) {
     let Some(kind) = infer::get(bytes) else {
         return if is_svg(bytes) {
             (DataFormat::Svg, Some("image/svg+xml".to_string().into()))
+        } else if bytes.starts_with(SNAPPY_STREAM_IDENTIFIER) {
+            (
+                DataFormat::Snappy,
+                Some("application/x-snappy-framed".to_string().into()),
+            )
         } else if is_valid_messagepack(bytes) {
             (DataFormat::MessagePack, None)
         } else {
@@ -194,6 +248,7 @@ pub fn detect_format(bytes: &[u8]) -> (DataFormat, Option<SharedString>) {
     let format = match mime {
         "application/gzip" => DataFormat::Gzip,
         "application/zstd" => DataFormat::Zstd,
+        "application/x-lz4" => DataFormat::Lz4,
         "image/jpeg" => DataFormat::Jpeg,
         "image/png" => DataFormat::Png,
         "image/webp" => DataFormat::Webp,
@@ -203,6 +258,113 @@ pub fn detect_format(bytes: &[u8]) -> (DataFormat, Option<SharedString>) {
     (format, Some(mime.to_string().into()))
 }
 
+/// Max length of json strings in the preview of a collection element
+const ELEMENT_TRUNCATE_LENGTH: usize = 256;
+/// Number of bytes shown in the hex preview of a binary collection element
+const ELEMENT_HEX_PREVIEW_BYTES: usize = 64;
+
+/// A member of a collection value: list item, set or zset member, hash field or value.
+///
+/// The raw bytes are kept, so binary members are displayed and written back unchanged.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RedisElement {
+    pub bytes: Bytes,
+    pub format: DataFormat,
+    /// Text used for editing, non UTF-8 data is escaped (see `escape_key`)
+    pub text: SharedString,
+    /// Whether the bytes are not valid UTF-8, so `text` is the escaped form
+    pub binary: bool,
+    /// Single line rendering of decoded data (messagepack, decompressed text) or a hex dump
+    pub preview: Option<SharedString>,
+    /// Decoded image for image elements
+    pub image: Option<Arc<Image>>,
+}
+
+fn single_line(text: &str) -> SharedString {
+    text.lines().map(str::trim).collect::<Vec<_>>().join(" ").into()
+}
+
+fn hex_preview(bytes: &[u8]) -> SharedString {
+    let mut hex = bytes
+        .iter()
+        .take(ELEMENT_HEX_PREVIEW_BYTES)
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if bytes.len() > ELEMENT_HEX_PREVIEW_BYTES {
+        hex.push_str(&format!(" ...({} bytes)", bytes.len()));
+    }
+    hex.into()
+}
+
+impl RedisElement {
+    /// Create an element from raw bytes, detecting its format.
+    pub fn new(bytes: impl Into<Bytes>) -> Self {
+        let bytes: Bytes = bytes.into();
+        let (text, binary) = match std::str::from_utf8(&bytes) {
+            Ok(text) => (text.to_string(), false),
+            Err(_) => (escape_key(&bytes), true),
+        };
+        let mut value = RedisBytesValue {
+            bytes: bytes.clone(),
+            ..Default::default()
+        };
+        value.detect_and_update_element(ELEMENT_TRUNCATE_LENGTH);
+
+        let image = value
+            .format
+            .image_format()
+            .map(|format| Arc::new(Image::from_bytes(format, bytes.to_vec())));
+        let plain_text = !binary && matches!(value.format, DataFormat::Text | DataFormat::Json | DataFormat::Preview);
+        let preview = if image.is_some() || plain_text {
+            None
+        } else if let Some(text) = &value.text {
+            Some(single_line(text))
+        } else if binary {
+            Some(hex_preview(&bytes))
+        } else {
+            None
+        };
+        Self {
+            bytes,
+            format: value.format,
+            text: text.into(),
+            binary,
+            preview,
+            image,
+        }
+    }
+    /// Create an element from text entered by the user
+    pub fn from_text(text: &str) -> Self {
+        Self::new(text.as_bytes().to_vec())
+    }
+    /// Convert the edited text of the element back to bytes.
+    ///
+    /// Binary elements are edited in their escaped form, so the text is unescaped.
+    pub fn bytes_from_text(&self, text: &str) -> Vec<u8> {
+        if self.binary {
+            unescape_key(text).into_owned()
+        } else {
+            text.as_bytes().to_vec()
+        }
+    }
+    /// Text shown in the table cell
+    pub fn display_text(&self) -> SharedString {
+        self.preview.clone().unwrap_or_else(|| self.text.clone())
+    }
+    /// Short label of the detected format, shown beside decoded or binary elements
+    pub fn format_label(&self) -> Option<&'static str> {
+        if self.preview.is_none() && self.image.is_none() {
+            return None;
+        }
+        if self.binary && self.format == DataFormat::Bytes {
+            Some("hex")
+        } else {
+            Some(self.format.as_str())
+        }
+    }
+}
+
 /// Redis value data variants for different data types
 #[derive(Debug, Clone)]
 pub enum RedisValueData {
@@ -219,8 +381,49 @@ pub struct RedisSetValue {
     pub keyword: Option<SharedString>,
     pub cursor: u64,
     pub size: usize,
-    pub values: Vec<SharedString>,
+    pub values: Vec<RedisElement>,
     pub done: bool,
+    /// Set operation with other keys, the values are its result when present
+    pub algebra: Option<SetAlgebra>,
+}
+
+/// Operation between the set and other set keys
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub enum SetOperation {
+    /// Members in all sets (SINTER)
+    #[default]
+    Inter,
+    /// Members in any set (SUNION)
+    Union,
+    /// Members of the set that are not in the other sets (SDIFF)
+    Diff,
+}
+
+impl SetOperation {
+    /// Command returning the members of the result
+    pub fn command(&self) -> &'static str {
+        match self {
+            SetOperation::Inter => "SINTER",
+            SetOperation::Union => "SUNION",
+            SetOperation::Diff => "SDIFF",
+        }
+    }
+    /// Command storing the result as a new key
+    pub fn store_command(&self) -> &'static str {
+        match self {
+            SetOperation::Inter => "SINTERSTORE",
+            SetOperation::Union => "SUNIONSTORE",
+            SetOperation::Diff => "SDIFFSTORE",
+        }
+    }
+}
+
+/// Set operation between the selected set and other keys
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct SetAlgebra {
+    pub operation: SetOperation,
+    /// Other set keys (escaped, see `escape_key`)
+    pub keys: Vec<SharedString>,
 }
 
 /// Sort order for sorted sets
@@ -231,15 +434,48 @@ pub enum SortOrder {
     Desc, // Descending order
 }
 
+/// Kind of bounds of a sorted set range
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub enum ZsetRangeBy {
+    /// Ranks, e.g. `0` and `-1`
+    #[default]
+    Rank,
+    /// Scores, e.g. `1`, `(1` (exclusive), `-inf` and `+inf`
+    Score,
+    /// Members, e.g. `[a`, `(a` (exclusive), `-` and `+`
+    Lex,
+}
+
+impl ZsetRangeBy {
+    /// Bounds of the whole sorted set
+    pub fn default_bounds(&self) -> (&'static str, &'static str) {
+        match self {
+            ZsetRangeBy::Rank => ("0", "-1"),
+            ZsetRangeBy::Score => ("-inf", "+inf"),
+            ZsetRangeBy::Lex => ("-", "+"),
+        }
+    }
+}
+
+/// Range of a sorted set browsed instead of all members, bounds use the Redis syntax
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct ZsetRange {
+    pub by: ZsetRangeBy,
+    pub min: SharedString,
+    pub max: SharedString,
+}
+
 /// Redis Sorted Set value structure with pagination and sorting support
 #[derive(Debug, Clone, Default)]
 pub struct RedisZsetValue {
     pub keyword: Option<SharedString>,
     pub cursor: u64,
     pub size: usize,
-    pub values: Vec<(SharedString, f64)>,
+    pub values: Vec<(RedisElement, f64)>,
     pub done: bool,
     pub sort_order: SortOrder,
+    /// Range being browsed, `size` is the number of members in the range
+    pub range: Option<ZsetRange>,
 }
 
 /// Redis Hash value structure with pagination support
@@ -249,7 +485,28 @@ pub struct RedisHashValue {
     pub keyword: Option<SharedString>,
     pub size: usize,
     pub done: bool,
-    pub values: Vec<(SharedString, SharedString)>,
+    pub values: Vec<(RedisElement, RedisElement)>,
+    /// Whether the server supports hash field expiration (Redis 7.4+)
+    pub field_ttl: bool,
+    /// Expire time (unix timestamp in milliseconds) of the loaded fields having a TTL
+    pub expires: HashMap<Bytes, i64>,
+}
+
+impl RedisHashValue {
+    /// Remaining TTL of a field formatted for display, `--` if the field never expires
+    pub fn field_ttl_text(&self, field: &RedisElement) -> SharedString {
+        let Some(expire_at) = self.expires.get(&field.bytes) else {
+            return "--".into();
+        };
+        let remaining = (expire_at - Local::now().timestamp_millis()).max(0) as u64;
+        humantime::format_duration(std::time::Duration::from_secs(remaining.div_ceil(1000)))
+            .to_string()
+            .split_whitespace()
+            .take(2)
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .into()
+    }
 }
 
 /// Redis List value structure
@@ -257,7 +514,9 @@ pub struct RedisHashValue {
 pub struct RedisListValue {
     pub keyword: Option<SharedString>,
     pub size: usize,
-    pub values: Vec<SharedString>,
+    /// Index of the first loaded element, greater than 0 after jumping into a long list
+    pub offset: usize,
+    pub values: Vec<RedisElement>,
 }
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum ViewMode {
@@ -284,6 +543,24 @@ impl ViewMode {
     }
 }
 
+/// New content of a string value submitted by the editor
+#[derive(Debug, Clone)]
+pub enum EditedValue {
+    /// Text from the code editor, encoded back with the format of the value
+    Text(SharedString),
+    /// Exact bytes from the hex editor
+    Bytes(Bytes),
+}
+
+/// Page of a huge string value loaded with GETRANGE
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PartialValue {
+    /// Offset of the first loaded byte
+    pub offset: usize,
+    /// Length of the whole value (STRLEN)
+    pub total: usize,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct RedisBytesValue {
     pub format: DataFormat,
@@ -291,17 +568,34 @@ pub struct RedisBytesValue {
     pub mime: Option<SharedString>,
     pub text: Option<SharedString>,
     pub view_mode: ViewMode,
+    /// Protobuf message type mapped to the key by the protobuf rules
+    pub protobuf: Option<MessageDescriptor>,
+    /// External command formatter the text was decoded with
+    pub formatter: Option<ValueFormatter>,
+    /// Set if only a page of the value is loaded
+    pub partial: Option<PartialValue>,
 }
 
 impl RedisBytesValue {
+    /// Whether only a page of the value is loaded, such values can not be edited
+    pub fn is_partial(&self) -> bool {
+        self.partial.is_some()
+    }
     pub fn is_image(&self) -> bool {
-        matches!(
-            self.format,
-            DataFormat::Jpeg | DataFormat::Png | DataFormat::Webp | DataFormat::Gif | DataFormat::Svg
-        )
+        self.format.is_image()
     }
     pub fn is_utf8_text(&self) -> bool {
-        matches!(self.format, DataFormat::Text | DataFormat::Json)
+        match self.format {
+            DataFormat::Text | DataFormat::Json | DataFormat::Protobuf => true,
+            // decompressed text, compressed again on save
+            DataFormat::Lz4 | DataFormat::Snappy | DataFormat::SnappyRaw | DataFormat::Brotli => self.text.is_some(),
+            // editable only if the formatter can encode the text back
+            DataFormat::External => self
+                .formatter
+                .as_ref()
+                .is_some_and(|formatter| formatter.encode.is_some()),
+            _ => false,
+        }
     }
 }
 
@@ -493,7 +787,7 @@ impl ZedisServerState {
     /// This method updates the UI immediately with the new value and then
     /// asynchronously persists it to Redis. If the save fails, the original
     /// value is restored.
-    pub fn save_value(&mut self, key: SharedString, new_value: SharedString, cx: &mut Context<Self>) {
+    pub fn save_value(&mut self, key: SharedString, new_value: EditedValue, cx: &mut Context<Self>) {
         let server_id = self.server_id.clone();
         let db = self.db;
         let Some(value) = self.value.as_mut() else {
@@ -503,17 +797,64 @@ impl ZedisServerState {
         let Some(original_bytes_value) = value.bytes_value() else {
             return;
         };
+        // saving a page would truncate the value
+        if original_bytes_value.is_partial() {
+            return;
+        }
         let format = original_bytes_value.format;
         let original_size = value.size;
 
+        let (bytes_value, encoder) = match new_value {
+            // Bytes from the hex editor are saved as is
+            EditedValue::Bytes(bytes) => (
+                RedisBytesValue {
+                    bytes,
+                    view_mode: original_bytes_value.view_mode.clone(),
+                    ..Default::default()
+                },
+                None,
+            ),
+            EditedValue::Text(new_value) => {
+                // Protobuf values are edited as JSON and encoded back to the message,
+                // compressed values are compressed again with the same compression
+                let bytes = match (format, &original_bytes_value.protobuf) {
+                    (DataFormat::Protobuf, Some(desc)) => encode_protobuf(desc, &new_value),
+                    (format, _) if format.is_compression() => compress_bytes(format, new_value.as_bytes()),
+                    _ => Ok(new_value.as_bytes().to_vec()),
+                };
+                let bytes = match bytes {
+                    Ok(bytes) => Bytes::from(bytes),
+                    Err(e) => {
+                        self.add_error_message(ServerTask::SaveValue.as_str().to_string(), e.to_string(), cx);
+                        return;
+                    }
+                };
+                // External formatters encode the text in the background task
+                let encoder = original_bytes_value
+                    .formatter
+                    .as_ref()
+                    .filter(|_| format == DataFormat::External)
+                    .and_then(|formatter| formatter.encode.clone());
+                (
+                    RedisBytesValue {
+                        bytes,
+                        text: Some(new_value),
+                        format,
+                        protobuf: original_bytes_value.protobuf.clone(),
+                        formatter: original_bytes_value.formatter.clone(),
+                        ..Default::default()
+                    },
+                    encoder,
+                )
+            }
+        };
+        let bytes = bytes_value.bytes.clone();
+        // the inverse command restores the loaded value if its DUMP is too large to keep
+        let original_bytes = original_bytes_value.bytes.clone();
+
         value.status = RedisValueStatus::Updating;
-        value.size = new_value.len();
-        value.data = Some(RedisValueData::Bytes(Arc::new(RedisBytesValue {
-            bytes: Bytes::from(new_value.clone().to_string().into_bytes()),
-            text: Some(new_value.clone()),
-            format,
-            ..Default::default()
-        })));
+        value.size = bytes.len();
+        value.data = Some(RedisValueData::Bytes(Arc::new(bytes_value)));
         let current_key = key.clone();
         let ttl = value.ttl().map(|ttl| ttl.num_milliseconds()).unwrap_or_default();
 
@@ -523,8 +864,14 @@ impl ZedisServerState {
             move || async move {
                 let client = get_connection_manager().get_client(&server_id, db).await?;
                 let mut conn = client.connection();
+                let bytes = match encoder {
+                    Some(command) => Bytes::from(run_formatter_command(&command, &bytes)?),
+                    None => bytes,
+                };
+                let inverse = string_inverse(&key, &original_bytes, client.is_at_least_version("6.0.0"));
+                record_history(&mut conn, &server_id, db, &key, ServerTask::SaveValue, Some(inverse)).await;
                 let mut binding = cmd("SET");
-                let mut cmd = binding.arg(key.as_str()).arg(new_value.as_str());
+                let mut cmd = binding.arg(unescape_key(&key)).arg(bytes.as_ref());
                 // keep ttl if the version is at least 6.0.0
                 cmd = if client.is_at_least_version("6.0.0") {
                     cmd.arg("KEEPTTL")
@@ -534,15 +881,24 @@ impl ZedisServerState {
                     cmd
                 };
                 let _: () = cmd.query_async(&mut conn).await?;
-                Ok(new_value)
+                Ok(bytes)
             },
             move |this, result, cx| {
                 if let Some(value) = this.value.as_mut() {
                     value.status = RedisValueStatus::Idle;
-                    // Recover original value if save failed
-                    if result.is_err() {
-                        value.size = original_size;
-                        value.data = Some(RedisValueData::Bytes(original_bytes_value.clone()));
+                    match result {
+                        // Keep the bytes actually saved (encoded by the external formatter)
+                        Ok(bytes) => {
+                            if let Some(RedisValueData::Bytes(bytes_value)) = &mut value.data {
+                                value.size = bytes.len();
+                                Arc::make_mut(bytes_value).bytes = bytes;
+                            }
+                        }
+                        // Recover original value if save failed
+                        Err(_) => {
+                            value.size = original_size;
+                            value.data = Some(RedisValueData::Bytes(original_bytes_value.clone()));
+                        }
                     }
                     cx.emit(ServerEvent::ValueUpdated(current_key));
                 }
@@ -566,4 +922,102 @@ impl ZedisServerState {
             cx.notify();
         }
     }
+
+    /// Decode the current string value with an external command formatter.
+    ///
+    /// `None` restores the automatically detected format.
+    pub fn apply_value_formatter(&mut self, name: Option<SharedString>, cx: &mut Context<Self>) {
+        let Some(bytes_value) = self
+            .value
+            .as_ref()
+            .and_then(|value| value.bytes_value())
+            .filter(|value| !value.is_partial())
+        else {
+            return;
+        };
+        let key = self.key.clone().unwrap_or_default();
+        let app_state = cx.global::<ZedisGlobalStore>().read(cx);
+        let max_truncate_length = app_state.max_truncate_length();
+        let formatter = name.and_then(|name| app_state.formatter(&name).cloned());
+
+        let mut data = RedisBytesValue {
+            bytes: bytes_value.bytes.clone(),
+            view_mode: bytes_value.view_mode.clone(),
+            protobuf: bytes_value.protobuf.clone(),
+            ..Default::default()
+        };
+        let current_key = key.clone();
+        self.spawn(
+            ServerTask::FormatValue,
+            move || async move {
+                data.detect_and_update(max_truncate_length);
+                if let Some(formatter) = formatter {
+                    data.format_with(formatter)?;
+                }
+                Ok(data)
+            },
+            move |this, result, cx| {
+                // the selected key has been changed
+                if this.key.as_ref() != Some(&current_key) {
+                    return;
+                }
+                if let Ok(data) = result
+                    && let Some(value) = this.value.as_mut()
+                {
+                    value.data = Some(RedisValueData::Bytes(Arc::new(data)));
+                    cx.emit(ServerEvent::ValueModeViewUpdated(key));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Load the page of a partially loaded string value starting at the offset.
+    pub fn load_value_page(&mut self, offset: usize, cx: &mut Context<Self>) {
+        let Some(value) = self.value.as_mut() else {
+            return;
+        };
+        let Some(bytes_value) = value.bytes_value() else {
+            return;
+        };
+        let Some(partial) = bytes_value.partial else {
+            return;
+        };
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        value.status = RedisValueStatus::Loading;
+        cx.notify();
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let written = self.is_key_written(&key);
+        let view_mode = bytes_value.view_mode.clone();
+        let current_key = key.clone();
+        self.spawn(
+            ServerTask::LoadValuePage,
+            move || async move {
+                let mut conn = get_connection_manager()
+                    .get_value_connection(&server_id, db, written)
+                    .await?;
+                get_redis_value_page(&mut conn, &key, offset.min(partial.total), partial.total).await
+            },
+            move |this, result, cx| {
+                // the selected key has been changed
+                if this.key.as_ref() != Some(&current_key) {
+                    return;
+                }
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                    if let Ok(mut data) = result {
+                        data.view_mode = view_mode;
+                        value.data = Some(RedisValueData::Bytes(Arc::new(data)));
+                    }
+                }
+                cx.emit(ServerEvent::ValueLoaded(current_key));
+                cx.notify();
+            },
+            cx,
+        );
+    }
 }
@@ -19,24 +19,173 @@
 //! - Adding/updating members with scores (ZADD)
 //! - Removing members from a ZSET (ZREM)
 //! - Filtering ZSET members with pattern matching (ZSCAN)
+//! - Browsing ranges by rank, score or member (ZRANGE BYSCORE/BYLEX) with ZCOUNT/ZLEXCOUNT totals
+//! - Incrementing scores (ZINCRBY) and removing ranges (ZREMRANGEBYRANK/BYSCORE/BYLEX)
 //! - Support for ascending and descending sort orders
 //! - Efficient incremental loading for large ZSETs
 
 use super::{
     KeyType, RedisValueData, ServerTask, ZedisServerState,
-    value::{RedisValue, RedisValueStatus, RedisZsetValue, SortOrder},
+    history::record_history,
+    staging::StagedCommand,
+    value::{RedisElement, RedisValue, RedisValueStatus, RedisZsetValue, SortOrder, ZsetRange, ZsetRangeBy},
 };
 use crate::{
     connection::{RedisAsyncConn, get_connection_manager},
     error::Error,
+    helpers::{escape_key, unescape_key},
     states::{NotificationAction, ServerEvent, i18n_zset_editor},
 };
 use gpui::{SharedString, prelude::*};
-use redis::cmd;
+use redis::{cmd, pipe};
 use std::sync::Arc;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// First version supporting ZRANGE with BYSCORE, BYLEX, REV and LIMIT
+const RANGE_BY_VERSION: &str = "6.2.0";
+/// Number of members loaded per page
+const PAGE_SIZE: usize = 100;
+
+/// Checks that the bounds use the Redis syntax of the range kind.
+fn validate_zset_range(range: &ZsetRange) -> Result<()> {
+    for bound in [&range.min, &range.max] {
+        let valid = match range.by {
+            ZsetRangeBy::Rank => bound.parse::<i64>().is_ok(),
+            // f64 parsing accepts `inf`, `+inf` and `-inf`
+            ZsetRangeBy::Score => bound.strip_prefix('(').unwrap_or(bound).parse::<f64>().is_ok(),
+            ZsetRangeBy::Lex => bound == "-" || bound == "+" || bound.starts_with('[') || bound.starts_with('('),
+        };
+        if !valid {
+            return Err(Error::Invalid {
+                message: format!("Invalid range bound: {bound}"),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn parse_rank(bound: &str) -> i64 {
+    bound.parse::<i64>().unwrap_or_default()
+}
+
+/// Counts the members in the range (ZCOUNT / ZLEXCOUNT).
+///
+/// Negative ranks are resolved with ZCARD, so a rank range can be paged.
+async fn count_zset_range(conn: &mut RedisAsyncConn, key: &str, range: ZsetRange) -> Result<(ZsetRange, usize)> {
+    match range.by {
+        ZsetRangeBy::Rank => {
+            let card: i64 = cmd("ZCARD").arg(unescape_key(key)).query_async(conn).await?;
+            let resolve = |bound: &str| {
+                let rank = parse_rank(bound);
+                if rank < 0 { rank + card } else { rank }
+            };
+            let start = resolve(&range.min).max(0);
+            let stop = resolve(&range.max).min(card - 1);
+            let count = if start > stop { 0 } else { (stop - start + 1) as usize };
+            Ok((
+                ZsetRange {
+                    by: range.by,
+                    min: start.to_string().into(),
+                    max: stop.to_string().into(),
+                },
+                count,
+            ))
+        }
+        ZsetRangeBy::Score | ZsetRangeBy::Lex => {
+            let name = if range.by == ZsetRangeBy::Score {
+                "ZCOUNT"
+            } else {
+                "ZLEXCOUNT"
+            };
+            let count: usize = cmd(name)
+                .arg(unescape_key(key))
+                .arg(range.min.as_str())
+                .arg(range.max.as_str())
+                .query_async(conn)
+                .await?;
+            Ok((range, count))
+        }
+    }
+}
+
+/// Retrieves a page of the members in the range, skipping the first `offset` members.
+///
+/// ZRANGE BYSCORE/BYLEX is used on Redis 6.2+, ZRANGEBYSCORE/ZRANGEBYLEX before.
+async fn get_zset_range_value(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    range: &ZsetRange,
+    sort_order: SortOrder,
+    offset: usize,
+    range_by_command: bool,
+) -> Result<Vec<(RedisElement, f64)>> {
+    if range.by == ZsetRangeBy::Rank {
+        let (start, stop) = (parse_rank(&range.min) + offset as i64, parse_rank(&range.max));
+        if start > stop {
+            return Ok(vec![]);
+        }
+        let page_stop = (start + PAGE_SIZE as i64 - 1).min(stop);
+        return get_redis_zset_value(conn, key, sort_order, start as usize, page_stop as usize).await;
+    }
+
+    let rev = sort_order == SortOrder::Desc;
+    // reversed ranges start from the max bound
+    let (start, stop) = if rev {
+        (&range.max, &range.min)
+    } else {
+        (&range.min, &range.max)
+    };
+    let by_score = range.by == ZsetRangeBy::Score;
+    let mut command = if range_by_command {
+        let mut command = cmd("ZRANGE");
+        command
+            .arg(unescape_key(key))
+            .arg(start.as_str())
+            .arg(stop.as_str())
+            .arg(if by_score { "BYSCORE" } else { "BYLEX" });
+        if rev {
+            command.arg("REV");
+        }
+        command
+    } else {
+        let name = match (by_score, rev) {
+            (true, false) => "ZRANGEBYSCORE",
+            (true, true) => "ZREVRANGEBYSCORE",
+            (false, false) => "ZRANGEBYLEX",
+            (false, true) => "ZREVRANGEBYLEX",
+        };
+        let mut command = cmd(name);
+        command.arg(unescape_key(key)).arg(start.as_str()).arg(stop.as_str());
+        command
+    };
+    command.arg("LIMIT").arg(offset).arg(PAGE_SIZE);
+
+    if by_score {
+        let raw_values: Vec<(Vec<u8>, f64)> = command.arg("WITHSCORES").query_async(conn).await?;
+        return Ok(raw_values
+            .into_iter()
+            .map(|(member, score)| (RedisElement::new(member), score))
+            .collect());
+    }
+
+    // WITHSCORES can not be combined with BYLEX, fetch the scores with ZSCORE
+    let members: Vec<Vec<u8>> = command.query_async(conn).await?;
+    if members.is_empty() {
+        return Ok(vec![]);
+    }
+    let mut pipeline = pipe();
+    for member in &members {
+        pipeline.cmd("ZSCORE").arg(unescape_key(key)).arg(member.as_slice());
+    }
+    let scores: Vec<Option<f64>> = pipeline.query_async(conn).await?;
+    Ok(members
+        .into_iter()
+        .zip(scores)
+        .map(|(member, score)| (RedisElement::new(member), score.unwrap_or_default()))
+        .collect())
+}
+
 /// Retrieves ZSET members using range-based commands (ZRANGE or ZREVRANGE).
 ///
 /// This function is used for non-filtered pagination, loading members by their
@@ -57,7 +206,7 @@ async fn get_redis_zset_value(
     sort_order: SortOrder,
     start: usize,
     stop: usize,
-) -> Result<Vec<(SharedString, f64)>> {
+) -> Result<Vec<(RedisElement, f64)>> {
     // Choose command based on sort order
     let cmd_name = if sort_order == SortOrder::Asc {
         "ZRANGE"
@@ -67,7 +216,7 @@ async fn get_redis_zset_value(
 
     // Execute range query with scores
     let raw_values: Vec<(Vec<u8>, f64)> = cmd(cmd_name)
-        .arg(key)
+        .arg(unescape_key(key))
         .arg(start)
         .arg(stop)
         .arg("WITHSCORES")
@@ -79,13 +228,10 @@ async fn get_redis_zset_value(
         return Ok(vec![]);
     }
 
-    // Convert bytes to UTF-8 strings (lossy conversion for non-UTF8 data)
+    // Keep raw bytes, so binary members are not corrupted
     let values = raw_values
-        .iter()
-        .map(|(name, score)| {
-            let name = String::from_utf8_lossy(name).to_string();
-            (name.into(), *score)
-        })
+        .into_iter()
+        .map(|(name, score)| (RedisElement::new(name), score))
         .collect();
 
     Ok(values)
@@ -111,10 +257,10 @@ async fn search_redis_zset_value(
     cursor: u64,
     pattern: &str,
     count: u64,
-) -> Result<(u64, Vec<(SharedString, f64)>)> {
+) -> Result<(u64, Vec<(RedisElement, f64)>)> {
     // Execute ZSCAN with MATCH and COUNT options
     let (next_cursor, raw_values): (u64, Vec<Vec<u8>>) = cmd("ZSCAN")
-        .arg(key)
+        .arg(unescape_key(key))
         .arg(cursor)
         .arg("MATCH")
         .arg(pattern)
@@ -138,9 +284,7 @@ async fn search_redis_zset_value(
         let score_str = String::from_utf8_lossy(score_bytes).to_string();
         let score = score_str.parse::<f64>().unwrap_or_default();
 
-        // Convert member to string
-        let name = String::from_utf8_lossy(member).to_string();
-        values.push((name.into(), score));
+        values.push((RedisElement::new(member.clone()), score));
     }
 
     Ok((next_cursor, values))
@@ -164,7 +308,7 @@ pub(crate) async fn first_load_zset_value(
     sort_order: SortOrder,
 ) -> Result<RedisValue> {
     // Get total number of members in the ZSET
-    let size: usize = cmd("ZCARD").arg(key).query_async(conn).await?;
+    let size: usize = cmd("ZCARD").arg(unescape_key(key)).query_async(conn).await?;
 
     // Load first batch (ranks 0-99, i.e., 100 members)
     let values = get_redis_zset_value(conn, key, sort_order, 0, 99).await?;
@@ -182,6 +326,41 @@ pub(crate) async fn first_load_zset_value(
     })
 }
 
+/// Updates the loaded members after ZINCRBY: the member is moved to the position of its
+/// new score, or inserted if it was created by the increment.
+///
+/// Members are only inserted when their position is in the loaded part of the sorted set,
+/// a filtered list or a browsed range is left as is except for the score.
+fn update_incremented_member(zset: &mut RedisZsetValue, member: RedisElement, score: f64, created: bool) {
+    if created && zset.range.is_none() {
+        zset.size += 1;
+    }
+    let position = zset.values.iter().position(|(item, _)| item.bytes == member.bytes);
+    if zset.keyword.is_some() || zset.range.is_some() {
+        if let Some(index) = position {
+            zset.values[index].1 = score;
+        }
+        return;
+    }
+    if let Some(index) = position {
+        zset.values.remove(index);
+    } else if !created {
+        // the member is in the part of the sorted set which is not loaded
+        return;
+    }
+    let index = zset.values.partition_point(|(_, value)| {
+        if zset.sort_order == SortOrder::Asc {
+            *value < score
+        } else {
+            *value > score
+        }
+    });
+    // a member after the loaded members is shown once the next page is loaded
+    if index < zset.values.len() || zset.done {
+        zset.values.insert(index, (member, score));
+    }
+}
+
 impl ZedisServerState {
     /// Adds or updates a member in the Redis ZSET with the specified score.
     ///
@@ -195,20 +374,27 @@ impl ZedisServerState {
     /// * `score` - The score to assign to the member
     /// * `cx` - GPUI context for spawning async tasks and UI updates
     pub fn add_zset_value(&mut self, new_value: SharedString, score: f64, cx: &mut Context<Self>) {
-        self.add_or_update_zset_value(new_value, score, cx);
+        self.add_or_update_zset_value(RedisElement::from_text(&new_value), score, cx);
     }
     /// Updates a member in the Redis ZSET with the specified score.
     ///
     /// Uses ZADD command to update the score of the specified member.
     ///
     /// # Arguments
-    /// * `new_value` - The member name to update
+    /// * `member` - The member to update
     /// * `score` - The score to assign to the member
     /// * `cx` - GPUI context for spawning async tasks and UI updates
-    pub fn update_zset_value(&mut self, new_value: SharedString, score: f64, cx: &mut Context<Self>) {
-        self.add_or_update_zset_value(new_value, score, cx);
+    pub fn update_zset_value(&mut self, member: RedisElement, score: f64, cx: &mut Context<Self>) {
+        self.add_or_update_zset_value(member, score, cx);
     }
-    fn add_or_update_zset_value(&mut self, new_value: SharedString, score: f64, cx: &mut Context<Self>) {
+    fn add_or_update_zset_value(&mut self, new_value: RedisElement, score: f64, cx: &mut Context<Self>) {
+        if let Some(key) = self.staging_key() {
+            let command = StagedCommand::new(&key, "ZADD")
+                .arg(score.to_string())
+                .arg(new_value.bytes.as_ref());
+            self.stage_commands(vec![command], cx);
+            return;
+        }
         // Early return if no key/value is selected
         let Some((key, value)) = self.try_get_mut_key_value() else {
             return;
@@ -228,12 +414,13 @@ impl ZedisServerState {
             // Async operation: execute ZADD on Redis
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                record_history(&mut conn, &server_id, db, &key, ServerTask::AddZsetValue, None).await;
 
                 // ZADD returns number of new elements added (0 if updating existing)
                 let count: usize = cmd("ZADD")
-                    .arg(key.as_str())
+                    .arg(unescape_key(&key))
                     .arg(score)
-                    .arg(new_value.as_str())
+                    .arg(new_value.bytes.as_ref())
                     .query_async(&mut conn)
                     .await?;
                 Ok(count)
@@ -259,8 +446,8 @@ impl ZedisServerState {
 
                     // Check if member already exists and update its score
                     for item in zset.values.iter_mut() {
-                        if item.0 == new_value_clone {
-                            *item = (new_value_clone.clone(), score);
+                        if item.0.bytes == new_value_clone.bytes {
+                            item.1 = score;
                             exists_value = true;
                             break;
                         }
@@ -336,9 +523,10 @@ impl ZedisServerState {
     }
     /// Loads the next batch of ZSET members using appropriate pagination strategy.
     ///
-    /// Uses two different strategies based on whether filtering is active:
+    /// Uses different strategies based on whether a filter or a range is active:
     /// - **No filter**: Range-based pagination (ZRANGE/ZREVRANGE) for efficient rank access
     /// - **With filter**: Cursor-based ZSCAN for pattern matching support
+    /// - **With range**: ZRANGE BYSCORE/BYLEX with LIMIT, paged by offset
     ///
     /// When filtering, automatically loads more batches until at least 50 items are
     /// collected or scan is complete.
@@ -362,9 +550,12 @@ impl ZedisServerState {
         let sort_order = zset.sort_order;
         let keyword = zset.keyword.clone().unwrap_or_default();
         let cursor = zset.cursor;
+        let range = zset.range.clone();
+        let is_range = range.is_some();
 
         let server_id = self.server_id.clone();
         let db = self.db;
+        let written = self.is_key_written(&key);
 
         // Calculate range for pagination (load 100 items)
         let start = current_len;
@@ -378,9 +569,16 @@ impl ZedisServerState {
             ServerTask::LoadMoreValue,
             // Async operation: fetch next batch using appropriate strategy
             move || async move {
-                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
-
-                if keyword.is_empty() {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = client.value_connection(written);
+
+                if let Some(range) = range {
+                    // Range: page through the members of the range
+                    let range_by_command = client.is_at_least_version(RANGE_BY_VERSION);
+                    let values =
+                        get_zset_range_value(&mut conn, &key, &range, sort_order, start, range_by_command).await?;
+                    Ok((0, values))
+                } else if keyword.is_empty() {
                     // No filter: use range-based pagination
                     let values = get_redis_zset_value(&mut conn, &key, sort_order, start, stop).await?;
                     Ok((0, values)) // Cursor is irrelevant for range queries
@@ -400,6 +598,11 @@ impl ZedisServerState {
                 {
                     let zset = Arc::make_mut(zset_data);
 
+                    // A short page is the end of the range
+                    if is_range && new_values.len() < PAGE_SIZE {
+                        zset.done = true;
+                    }
+
                     // Append new members to existing list
                     if !new_values.is_empty() {
                         zset.values.extend(new_values);
@@ -446,10 +649,26 @@ impl ZedisServerState {
     /// # Arguments
     /// * `remove_value` - The member name to remove from the ZSET
     /// * `cx` - GPUI context for spawning async tasks and UI updates
-    pub fn remove_zset_value(&mut self, remove_value: SharedString, cx: &mut Context<Self>) {
+    pub fn remove_zset_value(&mut self, remove_value: RedisElement, cx: &mut Context<Self>) {
+        if let Some(key) = self.staging_key() {
+            self.stage_commands(
+                vec![StagedCommand::new(&key, "ZREM").arg(remove_value.bytes.as_ref())],
+                cx,
+            );
+            return;
+        }
         let Some((key, value)) = self.try_get_mut_key_value() else {
             return;
         };
+        // score of the removed member, used to build the inverse operation
+        let remove_score = value
+            .zset_value()
+            .and_then(|zset| {
+                zset.values
+                    .iter()
+                    .find(|(member, _)| member.bytes == remove_value.bytes)
+            })
+            .map(|(_, score)| *score);
 
         // Update UI state to show loading
         value.status = RedisValueStatus::Loading;
@@ -457,7 +676,7 @@ impl ZedisServerState {
 
         let server_id = self.server_id.clone();
         let db = self.db;
-        let remove_value_clone = remove_value.clone();
+        let remove_bytes = remove_value.bytes.clone();
         let key_clone = key.clone();
 
         self.spawn(
@@ -465,11 +684,20 @@ impl ZedisServerState {
             // Async operation: execute ZREM on Redis
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let inverse = remove_score.map(|score| {
+                    vec![
+                        "ZADD".to_string(),
+                        key.to_string(),
+                        score.to_string(),
+                        escape_key(&remove_value.bytes),
+                    ]
+                });
+                record_history(&mut conn, &server_id, db, &key, ServerTask::RemoveZsetValue, inverse).await;
 
                 // ZREM removes the member and returns success
                 let _: () = cmd("ZREM")
-                    .arg(key.as_str())
-                    .arg(remove_value.as_str())
+                    .arg(unescape_key(&key))
+                    .arg(remove_value.bytes.as_ref())
                     .query_async(&mut conn)
                     .await?;
                 Ok(())
@@ -482,7 +710,7 @@ impl ZedisServerState {
                     let zset = Arc::make_mut(zset_data);
 
                     // Remove from local values list
-                    zset.values.retain(|(name, _)| name != &remove_value_clone);
+                    zset.values.retain(|(name, _)| name.bytes != remove_bytes);
                     zset.size -= 1;
                 }
 
@@ -497,4 +725,218 @@ impl ZedisServerState {
             cx,
         );
     }
+    /// Browses a range of the ZSET by rank, score or member, `None` browses all members.
+    ///
+    /// The members in the range are counted with ZCOUNT / ZLEXCOUNT and paged
+    /// with the existing load more pagination.
+    pub fn set_zset_range(&mut self, range: Option<ZsetRange>, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let Some(zset) = value.zset_value() else {
+            return;
+        };
+        let sort_order = zset.sort_order;
+        if let Some(Err(e)) = range.as_ref().map(validate_zset_range) {
+            self.add_error_message(ServerTask::LoadZsetRange.as_str().to_string(), e.to_string(), cx);
+            return;
+        }
+
+        value.status = RedisValueStatus::Loading;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let written = self.is_key_written(&key);
+        let key_clone = key.clone();
+
+        self.spawn(
+            ServerTask::LoadZsetRange,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = client.value_connection(written);
+                match range {
+                    Some(range) => {
+                        let (range, size) = count_zset_range(&mut conn, &key, range).await?;
+                        let range_by_command = client.is_at_least_version(RANGE_BY_VERSION);
+                        let values =
+                            get_zset_range_value(&mut conn, &key, &range, sort_order, 0, range_by_command).await?;
+                        Ok((Some(range), size, values))
+                    }
+                    None => {
+                        let size: usize = cmd("ZCARD").arg(unescape_key(&key)).query_async(&mut conn).await?;
+                        let values = get_redis_zset_value(&mut conn, &key, sort_order, 0, PAGE_SIZE - 1).await?;
+                        Ok((None, size, values))
+                    }
+                }
+            },
+            move |this, result, cx| {
+                // the selected key has been changed
+                if this.key.as_ref() != Some(&key_clone) {
+                    return;
+                }
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                    if let Ok((range, size, values)) = result {
+                        let done = range.is_some() && values.len() < PAGE_SIZE;
+                        value.data = Some(RedisValueData::Zset(Arc::new(RedisZsetValue {
+                            size,
+                            values,
+                            sort_order,
+                            range,
+                            done,
+                            ..Default::default()
+                        })));
+                    }
+                }
+                cx.emit(ServerEvent::ValueLoaded(key_clone));
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Increments the score of a member with ZINCRBY, the member is added if it does not exist.
+    pub fn incr_zset_value(&mut self, member: SharedString, increment: f64, cx: &mut Context<Self>) {
+        if let Some(key) = self.staging_key() {
+            let command = StagedCommand::new(&key, "ZINCRBY")
+                .arg(increment.to_string())
+                .arg(member.as_bytes());
+            self.stage_commands(vec![command], cx);
+            return;
+        }
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        value.status = RedisValueStatus::Updating;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        let member = RedisElement::from_text(&member);
+        let member_element = member.clone();
+
+        self.spawn(
+            ServerTask::IncrZsetValue,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let inverse = vec![
+                    "ZINCRBY".to_string(),
+                    key.to_string(),
+                    (-increment).to_string(),
+                    escape_key(&member.bytes),
+                ];
+                record_history(
+                    &mut conn,
+                    &server_id,
+                    db,
+                    &key,
+                    ServerTask::IncrZsetValue,
+                    Some(inverse),
+                )
+                .await;
+                // ZSCORE tells whether ZINCRBY creates the member
+                let (previous, score): (Option<f64>, f64) = pipe()
+                    .atomic()
+                    .cmd("ZSCORE")
+                    .arg(unescape_key(&key))
+                    .arg(member.bytes.as_ref())
+                    .cmd("ZINCRBY")
+                    .arg(unescape_key(&key))
+                    .arg(increment)
+                    .arg(member.bytes.as_ref())
+                    .query_async(&mut conn)
+                    .await?;
+                Ok((score, previous.is_none()))
+            },
+            move |this, result, cx| {
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                    if let Ok((score, created)) = result {
+                        if let Some(RedisValueData::Zset(zset_data)) = value.data.as_mut() {
+                            let zset = Arc::make_mut(zset_data);
+                            update_incremented_member(zset, member_element, score, created);
+                        }
+                        let message = format!("{} {score}", i18n_zset_editor(cx, "incr_score_success"));
+                        cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                            message.into(),
+                        )));
+                        cx.emit(ServerEvent::ValueUpdated(key_clone));
+                    }
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Removes all members of the browsed range (ZREMRANGEBYRANK / BYSCORE / BYLEX),
+    /// then reloads the range.
+    pub fn remove_zset_range(&mut self, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let Some(zset) = value.zset_value() else {
+            return;
+        };
+        let Some(range) = zset.range.clone() else {
+            return;
+        };
+        let sort_order = zset.sort_order;
+        value.status = RedisValueStatus::Updating;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let reload_range = range.clone();
+
+        self.spawn(
+            ServerTask::RemoveZsetRange,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                record_history(&mut conn, &server_id, db, &key, ServerTask::RemoveZsetRange, None).await;
+                let command = match range.by {
+                    ZsetRangeBy::Rank => {
+                        let (mut start, mut stop) = (parse_rank(&range.min), parse_rank(&range.max));
+                        // ranks of a descending range count from the highest score
+                        if sort_order == SortOrder::Desc {
+                            let card: i64 = cmd("ZCARD").arg(unescape_key(&key)).query_async(&mut conn).await?;
+                            (start, stop) = (card - 1 - stop, card - 1 - start);
+                        }
+                        let mut command = cmd("ZREMRANGEBYRANK");
+                        command.arg(unescape_key(&key)).arg(start).arg(stop);
+                        command
+                    }
+                    ZsetRangeBy::Score | ZsetRangeBy::Lex => {
+                        let name = if range.by == ZsetRangeBy::Score {
+                            "ZREMRANGEBYSCORE"
+                        } else {
+                            "ZREMRANGEBYLEX"
+                        };
+                        let mut command = cmd(name);
+                        command
+                            .arg(unescape_key(&key))
+                            .arg(range.min.as_str())
+                            .arg(range.max.as_str());
+                        command
+                    }
+                };
+                let count: usize = command.query_async(&mut conn).await?;
+                Ok(count)
+            },
+            move |this, result, cx| {
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                }
+                if let Ok(count) = result {
+                    let message = format!("{} {count}", i18n_zset_editor(cx, "remove_range_success"));
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                        message.into(),
+                    )));
+                    this.set_zset_range(Some(reload_range), cx);
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
 }
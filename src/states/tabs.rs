@@ -0,0 +1,252 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Editor tabs module.
+//!
+//! Every tab owns a `ZedisServerState` (server, db, key and value), so tasks
+//! of inactive tabs keep running and update their own tab. The tabs state:
+//! - Forwards the events of the active tab (and notifications of all tabs)
+//! - Keeps the server list and bookmarks in sync between tabs
+//! - Persists the tabs in the app state, restoring them lazily on the next start
+
+use crate::states::{
+    EditorTabConfig, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_sidebar, update_app_state_and_save,
+};
+use gpui::{App, AppContext, Context, Entity, EventEmitter, SharedString, Subscription};
+
+/// Maximum number of open tabs
+const MAX_TABS: usize = 20;
+
+struct EditorTab {
+    state: Entity<ZedisServerState>,
+    /// Config of the last session, applied when the tab is first shown
+    pending: Option<EditorTabConfig>,
+    _subscription: Subscription,
+}
+
+pub struct ZedisTabsState {
+    tabs: Vec<EditorTab>,
+    active: usize,
+}
+
+impl EventEmitter<ServerEvent> for ZedisTabsState {}
+
+impl ZedisTabsState {
+    /// Create the tabs of the last session, the first one uses the initial state
+    pub fn new(state: ZedisServerState, cx: &mut Context<Self>) -> Self {
+        let app_state = cx.global::<ZedisGlobalStore>().read(cx);
+        let configs = app_state.tabs().to_vec();
+        let active = app_state.active_tab();
+
+        let mut this = Self {
+            tabs: Vec::with_capacity(configs.len().max(1)),
+            active: 0,
+        };
+        if configs.is_empty() {
+            this.push_tab(state, None, cx);
+        } else {
+            for config in configs.into_iter().take(MAX_TABS) {
+                this.push_tab(Self::new_state(&state), Some(config), cx);
+            }
+        }
+        this.active = active.min(this.tabs.len() - 1);
+        this
+    }
+    /// A new state sharing the server list and bookmarks of the state
+    fn new_state(state: &ZedisServerState) -> ZedisServerState {
+        let mut new_state = ZedisServerState::new();
+        new_state.set_servers(state.servers().unwrap_or_default().to_vec());
+        new_state.set_bookmarks(state.bookmarks().clone());
+        new_state
+    }
+    fn push_tab(&mut self, state: ZedisServerState, pending: Option<EditorTabConfig>, cx: &mut Context<Self>) {
+        let state = cx.new(|_| state);
+        let subscription = cx.subscribe(&state, |this, state, event, cx| {
+            this.handle_tab_event(state, event, cx);
+        });
+        self.tabs.push(EditorTab {
+            state,
+            pending,
+            _subscription: subscription,
+        });
+    }
+    fn handle_tab_event(&mut self, state: Entity<ZedisServerState>, event: &ServerEvent, cx: &mut Context<Self>) {
+        let is_active = self.active().entity_id() == state.entity_id();
+        match event {
+            ServerEvent::ServerListUpdated => {
+                let servers = state.read(cx).servers().unwrap_or_default().to_vec();
+                self.update_other_tabs(&state, cx, |other| other.set_servers(servers.clone()));
+                cx.emit(event.clone());
+            }
+            ServerEvent::BookmarksUpdated => {
+                let bookmarks = state.read(cx).bookmarks().clone();
+                self.update_other_tabs(&state, cx, |other| other.set_bookmarks(bookmarks.clone()));
+                cx.emit(event.clone());
+            }
+            ServerEvent::Notification(_) | ServerEvent::ErrorOccurred(_) => {
+                cx.emit(event.clone());
+            }
+            ServerEvent::ServerSelected(..) | ServerEvent::KeySelected(_) => {
+                if let Some(tab) = self.tabs.iter_mut().find(|tab| tab.state == state) {
+                    tab.pending = None;
+                }
+                self.save(cx);
+                if is_active {
+                    cx.emit(event.clone());
+                }
+                cx.notify();
+            }
+            _ => {}
+        }
+    }
+    fn update_other_tabs(
+        &self,
+        state: &Entity<ZedisServerState>,
+        cx: &mut Context<Self>,
+        update: impl Fn(&mut ZedisServerState),
+    ) {
+        for tab in self.tabs.iter().filter(|tab| &tab.state != state) {
+            tab.state.update(cx, |other, _cx| update(other));
+        }
+    }
+    /// Persist the server, db and key of the tabs
+    fn save(&self, cx: &mut Context<Self>) {
+        let configs: Vec<EditorTabConfig> = self
+            .tabs
+            .iter()
+            .map(|tab| {
+                if let Some(pending) = &tab.pending {
+                    return pending.clone();
+                }
+                let state = tab.state.read(cx);
+                EditorTabConfig {
+                    server_id: state.server_id().to_string(),
+                    db: state.db(),
+                    key: state.key().map(|key| key.to_string()),
+                }
+            })
+            .collect();
+        let active = self.active;
+        update_app_state_and_save(cx, "save_tabs", move |state, _cx| {
+            state.set_tabs(configs.clone(), active);
+        });
+    }
+    /// Get the state of the active tab
+    pub fn active(&self) -> Entity<ZedisServerState> {
+        // there is always at least one tab
+        self.tabs[self.active].state.clone()
+    }
+    /// Get the index of the active tab
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+    /// Get the states of all tabs
+    pub fn states(&self) -> Vec<Entity<ZedisServerState>> {
+        self.tabs.iter().map(|tab| tab.state.clone()).collect()
+    }
+    /// Get the title of the tab, e.g. `local/0 user:1`
+    pub fn title(&self, index: usize, cx: &App) -> SharedString {
+        let Some(tab) = self.tabs.get(index) else {
+            return SharedString::default();
+        };
+        let (server_id, db, key) = if let Some(pending) = &tab.pending {
+            (pending.server_id.clone(), pending.db, pending.key.clone())
+        } else {
+            let state = tab.state.read(cx);
+            (
+                state.server_id().to_string(),
+                state.db(),
+                state.key().map(|key| key.to_string()),
+            )
+        };
+        if server_id.is_empty() {
+            return i18n_sidebar(cx, "home");
+        }
+        let state = tab.state.read(cx);
+        let server_name = state
+            .server(&server_id)
+            .map(|server| server.name.clone())
+            .unwrap_or(server_id);
+        match key {
+            Some(key) if !key.is_empty() => format!("{server_name}/{db} {key}").into(),
+            _ => format!("{server_name}/{db}").into(),
+        }
+    }
+    /// Select the server and key of the active tab restored from the last session
+    pub fn restore_active(&mut self, cx: &mut Context<Self>) {
+        let Some(tab) = self.tabs.get_mut(self.active) else {
+            return;
+        };
+        let Some(pending) = tab.pending.take() else {
+            return;
+        };
+        if pending.server_id.is_empty() {
+            return;
+        }
+        tab.state.update(cx, |state, cx| {
+            state.select(pending.server_id.into(), pending.db, cx);
+            if let Some(key) = pending.key {
+                state.select_key(key.into(), cx);
+            }
+        });
+    }
+    /// Switch to the tab
+    pub fn activate(&mut self, index: usize, cx: &mut Context<Self>) {
+        if index >= self.tabs.len() || index == self.active {
+            return;
+        }
+        self.active = index;
+        self.restore_active(cx);
+        let state = self.active().read(cx);
+        cx.emit(ServerEvent::ServerSelected(
+            state.server_id().to_string().into(),
+            state.db(),
+        ));
+        self.save(cx);
+        cx.notify();
+    }
+    /// Open a new tab with the server and db of the active tab
+    pub fn new_tab(&mut self, cx: &mut Context<Self>) {
+        if self.tabs.len() >= MAX_TABS {
+            return;
+        }
+        let active = self.active().read(cx);
+        let (server_id, db) = (active.server_id().to_string(), active.db());
+        let state = Self::new_state(active);
+        self.push_tab(state, None, cx);
+        let index = self.tabs.len() - 1;
+        self.tabs[index].state.update(cx, |state, cx| {
+            state.select(server_id.into(), db, cx);
+        });
+        self.activate(index, cx);
+    }
+    /// Close the tab, the last tab is kept
+    pub fn close_tab(&mut self, index: usize, cx: &mut Context<Self>) {
+        if self.tabs.len() <= 1 || index >= self.tabs.len() {
+            return;
+        }
+        self.tabs.remove(index);
+        if self.active > index || self.active >= self.tabs.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        self.restore_active(cx);
+        let state = self.active().read(cx);
+        cx.emit(ServerEvent::ServerSelected(
+            state.server_id().to_string().into(),
+            state.db(),
+        ));
+        self.save(cx);
+        cx.notify();
+    }
+}
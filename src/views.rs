@@ -15,11 +15,15 @@
 mod about;
 mod bytes_editor;
 mod content;
+mod diff_viewer;
 mod editor;
 mod hash_editor;
+mod hex_editor;
+mod history_viewer;
 mod key_tree;
 mod kv_table;
 mod list_editor;
+mod script_editor;
 mod servers;
 mod set_editor;
 mod setting_editor;
@@ -31,11 +35,14 @@ mod zset_editor;
 pub use about::open_about_window;
 pub use bytes_editor::ZedisBytesEditor;
 pub use content::ZedisContent;
+pub use diff_viewer::ZedisDiffViewer;
 pub use editor::ZedisEditor;
 pub use hash_editor::ZedisHashEditor;
+pub use history_viewer::ZedisHistoryViewer;
 pub use key_tree::ZedisKeyTree;
 pub use kv_table::{KvTableColumn, KvTableColumnType, ZedisKvTable};
 pub use list_editor::ZedisListEditor;
+pub use script_editor::ZedisScriptEditor;
 pub use servers::ZedisServers;
 pub use set_editor::ZedisSetEditor;
 pub use setting_editor::ZedisSettingEditor;
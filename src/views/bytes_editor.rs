@@ -12,17 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::hex_editor::{HexEditorEvent, ZedisHexEditor};
 use crate::helpers::get_font_family;
-use crate::states::{DataFormat, RedisBytesValue, ServerEvent, ViewMode, ZedisGlobalStore, ZedisServerState};
-use gpui::{App, Entity, Image, ObjectFit, SharedString, Subscription, Window, img, px};
-use gpui::{div, hsla, prelude::*};
+use crate::states::{
+    EditedValue, PARTIAL_PAGE_SIZE, PartialValue, RedisBytesValue, ServerEvent, ViewMode, ZedisGlobalStore,
+    ZedisServerState, i18n_editor,
+};
+use bytes::Bytes;
+use gpui::{App, Entity, Image, ObjectFit, SharedString, Subscription, Window, div, img, prelude::*, px};
+use gpui_component::button::Button;
 use gpui_component::highlighter::Language;
 use gpui_component::input::{Input, InputEvent, InputState, TabSize};
 use gpui_component::label::Label;
-use gpui_component::list::{List, ListDelegate, ListItem, ListState};
-use gpui_component::{ActiveTheme, IndexPath, h_flex};
-use pretty_hex::HexConfig;
-use pretty_hex::config_hex;
+use gpui_component::{ActiveTheme, Disableable, Sizable, h_flex, v_flex};
+use humansize::{DECIMAL, format_size};
 use std::sync::Arc;
 use tracing::info;
 
@@ -42,7 +45,8 @@ const VIEWPORT_MEDIUM: f32 = 1000.0; // Pixel width to switch hex display width
 /// - Line numbers and indent guides
 /// - Search functionality
 /// - Soft wrap support
-/// - Automatic hex display for binary data
+/// - Hex editor for binary data
+/// - Page by page viewing of huge values
 /// - Tracks modification state
 pub struct ZedisBytesEditor {
     /// Reference to server state for Redis operations
@@ -51,8 +55,11 @@ pub struct ZedisBytesEditor {
     /// Flag indicating if the value has been modified from original
     value_modified: bool,
 
-    /// State for hex viewer list
-    hex_viewer_state: Option<Entity<ListState<HexViewerListDelegate>>>,
+    /// Hex editor for binary data
+    hex_editor: Option<Entity<ZedisHexEditor>>,
+
+    /// Subscription to the changes of the hex editor
+    _hex_editor_subscription: Option<Subscription>,
 
     /// Code editor state with input handling
     editor: Entity<InputState>,
@@ -72,6 +79,9 @@ pub struct ZedisBytesEditor {
     /// The data to display in the editor
     data: ByteEditorData,
 
+    /// Set if only a page of the value is loaded
+    partial: Option<PartialValue>,
+
     /// Event subscriptions for reactive updates
     _subscriptions: Vec<Subscription>,
 }
@@ -79,7 +89,8 @@ pub struct ZedisBytesEditor {
 enum ByteEditorData {
     Image(Arc<Image>),
     Text(SharedString),
-    Hex(HexViewerListDelegate),
+    /// Bytes edited in the hex editor, with the number of bytes per row
+    Hex(Bytes, usize),
 }
 
 impl ByteEditorData {
@@ -93,8 +104,8 @@ impl ByteEditorData {
 /// Extract string value from Redis value, with hex fallback for binary data
 ///
 /// If the value is a string, returns Text(SharedString).
-/// If the value is binary data, formats it as a hex dump with appropriate width
-/// based on viewport size and returns Hex(SharedString).
+/// If the value is binary data, returns Hex with the bytes and a row width
+/// based on viewport size.
 ///
 /// # Arguments
 /// * `value` - Optional Redis value to extract string from
@@ -120,15 +131,7 @@ fn format_byte_editor_data(value: &Arc<RedisBytesValue>, cx: &App) -> ByteEditor
             _ => HEX_WIDTH_WIDE,
         };
 
-        let cfg = HexConfig {
-            title: false,
-            width: hex_width,
-            group: 0,
-            ..Default::default()
-        };
-
-        let hex_data = config_hex(&value.bytes, cfg);
-        ByteEditorData::Hex(HexViewerListDelegate::new(&hex_data))
+        ByteEditorData::Hex(value.bytes.clone(), hex_width)
     };
 
     match value.view_mode {
@@ -140,14 +143,8 @@ fn format_byte_editor_data(value: &Arc<RedisBytesValue>, cx: &App) -> ByteEditor
         }
 
         _ => {
-            if value.is_image() {
-                let format = match value.format {
-                    DataFormat::Png => gpui::ImageFormat::Png,
-                    DataFormat::Webp => gpui::ImageFormat::Webp,
-                    DataFormat::Gif => gpui::ImageFormat::Gif,
-                    DataFormat::Svg => gpui::ImageFormat::Svg,
-                    _ => gpui::ImageFormat::Jpeg,
-                };
+            // a page of an image can not be decoded
+            if let Some(format) = value.format.image_format().filter(|_| !value.is_partial()) {
                 let data = Image::from_bytes(format, value.bytes.to_vec());
                 return ByteEditorData::Image(Arc::new(data));
             }
@@ -160,70 +157,6 @@ fn format_byte_editor_data(value: &Arc<RedisBytesValue>, cx: &App) -> ByteEditor
         }
     }
 }
-#[derive(Clone)]
-struct HexViewerListDelegate {
-    items: Vec<(SharedString, SharedString, SharedString)>,
-    selected_index: Option<IndexPath>,
-}
-
-impl HexViewerListDelegate {
-    fn new(data: &str) -> Self {
-        let items = data
-            .split("\n")
-            .flat_map(|item| {
-                let (address, value) = item.split_once(":")?;
-                let (hex_data, ascii_data) = value.trim_start().split_once("   ")?;
-                Some((
-                    address.to_uppercase().into(),
-                    hex_data.to_string().into(),
-                    ascii_data.to_string().into(),
-                ))
-            })
-            .collect::<Vec<_>>();
-        Self {
-            items,
-            selected_index: None,
-        }
-    }
-}
-
-impl ListDelegate for HexViewerListDelegate {
-    type Item = ListItem;
-
-    fn items_count(&self, _section: usize, _cx: &App) -> usize {
-        self.items.len()
-    }
-
-    fn render_item(
-        &mut self,
-        ix: IndexPath,
-        _window: &mut Window,
-        cx: &mut Context<ListState<Self>>,
-    ) -> Option<Self::Item> {
-        let address_color = if cx.theme().is_dark() {
-            hsla(0.108, 0.66, 0.69, 1.0)
-        } else {
-            hsla(0.0892, 0.9462, 0.4373, 1.0)
-        };
-        self.items.get(ix.row).map(|(address, hex_data, ascii_data)| {
-            ListItem::new(ix).py_0().px_2().child(
-                h_flex()
-                    .child(Label::new(address.clone()).text_color(address_color).mr_4())
-                    .child(
-                        Label::new(hex_data.clone())
-                            .text_color(cx.theme().muted_foreground)
-                            .mr_6(),
-                    )
-                    .child(Label::new(ascii_data.clone())),
-            )
-        })
-    }
-
-    fn set_selected_index(&mut self, ix: Option<IndexPath>, _window: &mut Window, _cx: &mut Context<ListState<Self>>) {
-        self.selected_index = ix;
-    }
-}
-
 impl ZedisBytesEditor {
     /// Create a new string editor with code editing capabilities
     ///
@@ -291,7 +224,9 @@ impl ZedisBytesEditor {
             soft_wrap,
             soft_wrap_changed: false,
             data: ByteEditorData::Text(SharedString::default()),
-            hex_viewer_state: None,
+            partial: None,
+            hex_editor: None,
+            _hex_editor_subscription: None,
             editor,
             should_update_editor: true,
             server_state,
@@ -325,14 +260,30 @@ impl ZedisBytesEditor {
 
         let redis_bytes_value = server_state.read(cx).value().and_then(|v| v.bytes_value());
         if let Some(redis_bytes_value) = &redis_bytes_value {
-            self.readonly = !redis_bytes_value.is_utf8_text();
             self.data = format_byte_editor_data(redis_bytes_value, cx);
+            self.partial = redis_bytes_value.partial;
+            // the hex editor writes the exact bytes, so every value can be edited,
+            // except a partially loaded value or a value of a browsed replica
+            self.readonly = server_state.read(cx).is_readonly()
+                || redis_bytes_value.is_partial()
+                || (!redis_bytes_value.is_utf8_text() && !matches!(self.data, ByteEditorData::Hex(..)));
         } else {
             self.data = ByteEditorData::Text(SharedString::default());
+            self.partial = None;
         }
 
-        if !matches!(self.data, ByteEditorData::Hex(_)) {
-            self.hex_viewer_state = None;
+        if let ByteEditorData::Hex(bytes, width) = &self.data {
+            if let Some(hex_editor) = &self.hex_editor {
+                let (bytes, width) = (bytes.clone(), *width);
+                let (offset, total, readonly) = (self.page_offset(), self.page_total(), self.readonly);
+                hex_editor.update(cx, |editor, cx| {
+                    editor.set_bytes(bytes, width, cx);
+                    editor.set_page(offset, total, readonly, cx);
+                });
+            }
+        } else {
+            self.hex_editor = None;
+            self._hex_editor_subscription = None;
         }
     }
 
@@ -346,9 +297,72 @@ impl ZedisBytesEditor {
         self.readonly
     }
 
-    /// Get the current editor value
-    pub fn value(&self, cx: &mut Context<Self>) -> SharedString {
-        self.editor.read(cx).value()
+    fn page_offset(&self) -> usize {
+        self.partial.map(|partial| partial.offset).unwrap_or_default()
+    }
+
+    fn page_total(&self) -> Option<usize> {
+        self.partial.map(|partial| partial.total)
+    }
+
+    /// Render the bar of a partially loaded value, with paging and loading the whole value
+    fn render_partial_bar(&self, partial: PartialValue, cx: &mut Context<Self>) -> impl IntoElement {
+        let end = (partial.offset + PARTIAL_PAGE_SIZE).min(partial.total);
+        let message = format!(
+            "{} {}-{} / {}",
+            i18n_editor(cx, "partial_value"),
+            format_size(partial.offset as u64, DECIMAL),
+            format_size(end as u64, DECIMAL),
+            format_size(partial.total as u64, DECIMAL),
+        );
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(Label::new(message).flex_1().text_sm().text_color(cx.theme().warning))
+            .child(
+                Button::new("zedis-bytes-editor-prev-page")
+                    .outline()
+                    .small()
+                    .label(i18n_editor(cx, "previous_page"))
+                    .disabled(partial.offset == 0)
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        let offset = partial.offset.saturating_sub(PARTIAL_PAGE_SIZE);
+                        this.server_state
+                            .update(cx, |state, cx| state.load_value_page(offset, cx));
+                    })),
+            )
+            .child(
+                Button::new("zedis-bytes-editor-next-page")
+                    .outline()
+                    .small()
+                    .label(i18n_editor(cx, "next_page"))
+                    .disabled(end >= partial.total)
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.server_state.update(cx, |state, cx| state.load_value_page(end, cx));
+                    })),
+            )
+            .child(
+                Button::new("zedis-bytes-editor-load-all")
+                    .outline()
+                    .small()
+                    .label(i18n_editor(cx, "load_full_value"))
+                    .tooltip(i18n_editor(cx, "load_full_value_tooltip"))
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.server_state.update(cx, |state, cx| state.load_full_value(cx));
+                    })),
+            )
+    }
+
+    /// Get the current editor value, the exact bytes for the hex editor
+    pub fn value(&self, cx: &mut Context<Self>) -> EditedValue {
+        if let Some(hex_editor) = &self.hex_editor {
+            return EditedValue::Bytes(hex_editor.read(cx).bytes());
+        }
+        EditedValue::Text(self.editor.read(cx).value())
     }
 }
 
@@ -366,7 +380,7 @@ impl Render for ZedisBytesEditor {
             });
             self.soft_wrap_changed = false;
         }
-        match &self.data {
+        let content = match &self.data {
             ByteEditorData::Image(value) => div()
                 .size_full()
                 .flex()
@@ -375,12 +389,34 @@ impl Render for ZedisBytesEditor {
                 .overflow_hidden()
                 .child(img(value.clone()).object_fit(ObjectFit::Contain).flex_shrink_0())
                 .into_any_element(),
-            ByteEditorData::Hex(value) => {
-                let state = self
-                    .hex_viewer_state
-                    .get_or_insert_with(|| cx.new(|cx| ListState::new(value.clone(), window, cx)))
-                    .clone();
-                List::new(&state).font_family(get_font_family()).into_any_element()
+            ByteEditorData::Hex(bytes, width) => {
+                let hex_editor = match &self.hex_editor {
+                    Some(hex_editor) => hex_editor.clone(),
+                    None => {
+                        let (bytes, width) = (bytes.clone(), *width);
+                        let (offset, total, readonly) = (self.page_offset(), self.page_total(), self.readonly);
+                        let hex_editor = cx.new(|cx| {
+                            let mut editor = ZedisHexEditor::new(bytes, width, window, cx);
+                            editor.set_page(offset, total, readonly, cx);
+                            editor
+                        });
+                        self._hex_editor_subscription =
+                            Some(cx.subscribe(&hex_editor, |this, hex_editor, event, cx| match event {
+                                HexEditorEvent::Change => {
+                                    this.value_modified = hex_editor.read(cx).is_modified();
+                                    cx.notify();
+                                }
+                                HexEditorEvent::LoadPage(offset) => {
+                                    let offset = *offset;
+                                    this.server_state
+                                        .update(cx, |state, cx| state.load_value_page(offset, cx));
+                                }
+                            }));
+                        self.hex_editor = Some(hex_editor.clone());
+                        hex_editor
+                    }
+                };
+                div().size_full().child(hex_editor).into_any_element()
             }
             _ => {
                 if self.should_update_editor {
@@ -402,6 +438,14 @@ impl Render for ZedisBytesEditor {
                     .focus_bordered(false)
                     .into_any_element()
             }
-        }
+        };
+        let Some(partial) = self.partial else {
+            return content;
+        };
+        v_flex()
+            .size_full()
+            .child(self.render_partial_bar(partial, cx))
+            .child(div().flex_1().w_full().overflow_hidden().child(content))
+            .into_any_element()
     }
 }
@@ -14,15 +14,18 @@
 
 use crate::{
     helpers::get_key_tree_widths,
-    states::{Route, ZedisGlobalStore, ZedisServerState, i18n_common, save_app_state},
-    views::{ZedisEditor, ZedisKeyTree, ZedisServers, ZedisSettingEditor, ZedisStatusBar},
+    states::{Route, ZedisGlobalStore, ZedisServerState, ZedisTabsState, i18n_common, i18n_editor, save_app_state},
+    views::{ZedisEditor, ZedisKeyTree, ZedisScriptEditor, ZedisServers, ZedisSettingEditor, ZedisStatusBar},
 };
-use gpui::{Entity, Pixels, Subscription, Window, div, prelude::*, px};
+use ahash::AHashMap;
+use gpui::{Entity, EntityId, Pixels, Subscription, Window, div, prelude::*, px};
 use gpui_component::{
-    ActiveTheme,
+    ActiveTheme, IconName, Sizable,
+    button::{Button, ButtonVariants},
     label::Label,
     resizable::{ResizableState, h_resizable, resizable_panel},
     skeleton::Skeleton,
+    tab::{Tab, TabBar},
     v_flex,
 };
 use tracing::{debug, error, info};
@@ -34,24 +37,35 @@ const LOADING_SKELETON_MEDIUM_WIDTH: f32 = 220.0;
 const LOADING_SKELETON_LARGE_WIDTH: f32 = 420.0;
 const SERVERS_MARGIN: f32 = 8.0;
 
+/// Key tree and value editor of an editor tab
+struct TabViews {
+    value_editor: Entity<ZedisEditor>,
+    key_tree: Entity<ZedisKeyTree>,
+}
+
 /// Main content area component for the Zedis application
 ///
 /// Manages the application's main views and routing:
 /// - Server list view (Route::Home): Display and manage Redis server connections
-/// - Editor view (Route::Editor): Display key tree and value editor for selected server
+/// - Editor view (Route::Editor): Display editor tabs, each with the key tree and
+///   value editor of its own server state
+/// - Scripts view (Route::Scripts): Lua script and Redis Functions workbench of the active tab
 ///
 /// Views are lazily initialized and cached for performance, but cleared when
 /// no longer needed to conserve memory.
 pub struct ZedisContent {
-    /// Reference to the server state containing Redis connection and data
-    server_state: Entity<ZedisServerState>,
+    /// Editor tabs, each owning a server state
+    tabs: Entity<ZedisTabsState>,
 
     /// Cached views - lazily initialized and cleared when switching routes
     servers: Option<Entity<ZedisServers>>,
     settings: Option<Entity<ZedisSettingEditor>>,
-    value_editor: Option<Entity<ZedisEditor>>,
-    key_tree: Option<Entity<ZedisKeyTree>>,
-    status_bar: Entity<ZedisStatusBar>,
+    /// Key tree and value editor of the tabs, keyed by the tab state entity
+    tab_views: AHashMap<EntityId, TabViews>,
+    /// Script workbench of the tabs, keyed by the tab state entity
+    script_editors: AHashMap<EntityId, Entity<ZedisScriptEditor>>,
+    /// Status bar of the tabs (kept across routes for the heartbeat)
+    status_bars: AHashMap<EntityId, Entity<ZedisStatusBar>>,
 
     /// Persisted width of the key tree panel (resizable by user)
     key_tree_width: Pixels,
@@ -68,9 +82,18 @@ impl ZedisContent {
     ///
     /// Sets up subscriptions to automatically clean up cached views when
     /// switching routes to optimize memory usage.
-    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+    pub fn new(tabs: Entity<ZedisTabsState>, _window: &mut Window, cx: &mut Context<Self>) -> Self {
         let mut subscriptions = Vec::new();
-        let status_bar = cx.new(|cx| ZedisStatusBar::new(server_state.clone(), window, cx));
+
+        // Re-render when tabs are opened, closed, switched or renamed
+        subscriptions.push(cx.observe(&tabs, |this, tabs, cx| {
+            let states = tabs.read(cx).states();
+            let ids: Vec<EntityId> = states.iter().map(|state| state.entity_id()).collect();
+            this.tab_views.retain(|id, _| ids.contains(id));
+            this.script_editors.retain(|id, _| ids.contains(id));
+            this.status_bars.retain(|id, _| ids.contains(id));
+            cx.notify();
+        }));
 
         // Subscribe to global state changes for automatic view cleanup
         // This ensures we only keep views in memory that are currently relevant
@@ -87,15 +110,21 @@ impl ZedisContent {
                 let _ = this.servers.take();
             }
 
+            // Clean up script workbench views when not on scripts route
+            if route != Route::Scripts && !this.script_editors.is_empty() {
+                info!("Cleaning up script editor view (route changed)");
+                this.script_editors.clear();
+            }
+
             // Clean up editor views when not on editor route
             if route != Route::Editor {
                 info!("Cleaning up key tree and value editor view (route changed)");
-                if this.value_editor.is_some() {
-                    let _ = this.value_editor.take();
-                }
-                if this.key_tree.is_some() {
-                    let _ = this.key_tree.take();
-                }
+                this.tab_views.clear();
+            } else {
+                // Tabs of the last session connect when first shown
+                this.tabs.update(cx, |tabs, cx| {
+                    tabs.restore_active(cx);
+                });
             }
 
             cx.notify();
@@ -108,13 +137,13 @@ impl ZedisContent {
         info!("Creating new content view");
 
         Self {
-            server_state,
-            status_bar,
+            tabs,
+            status_bars: AHashMap::new(),
             current_route: route,
             servers: None,
-            value_editor: None,
             settings: None,
-            key_tree: None,
+            tab_views: AHashMap::new(),
+            script_editors: AHashMap::new(),
             key_tree_width,
             _subscriptions: subscriptions,
         }
@@ -129,7 +158,7 @@ impl ZedisContent {
             .servers
             .get_or_insert_with(|| {
                 debug!("Creating new servers view");
-                cx.new(|cx| ZedisServers::new(self.server_state.clone(), window, cx))
+                cx.new(|cx| ZedisServers::new(self.tabs.read(cx).active(), window, cx))
             })
             .clone();
 
@@ -145,6 +174,23 @@ impl ZedisContent {
             .clone();
         div().child(settings)
     }
+    /// Render the script workbench of the tab
+    fn render_scripts(
+        &mut self,
+        server_state: Entity<ZedisServerState>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let script_editor = self
+            .script_editors
+            .entry(server_state.entity_id())
+            .or_insert_with(|| {
+                debug!("Creating new script editor view");
+                cx.new(|cx| ZedisScriptEditor::new(server_state.clone(), window, cx))
+            })
+            .clone();
+        div().size_full().child(script_editor)
+    }
     /// Render a loading skeleton screen with animated placeholders
     ///
     /// Displayed when the application is busy (e.g., connecting to Redis server,
@@ -176,26 +222,22 @@ impl ZedisContent {
     /// - Right panel: Value editor for viewing/editing selected key
     ///
     /// The key tree width is user-adjustable and persisted to disk.
-    fn render_editor(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let server_state = self.server_state.clone();
-
-        // Lazily initialize value editor - reuse existing or create new
-        let value_editor = self
-            .value_editor
-            .get_or_insert_with(|| {
-                debug!("Creating new value editor view");
-                cx.new(|cx| ZedisEditor::new(server_state.clone(), window, cx))
-            })
-            .clone();
-
-        // Lazily initialize key tree - reuse existing or create new
-        let key_tree = self
-            .key_tree
-            .get_or_insert_with(|| {
-                debug!("Creating new key tree view");
-                cx.new(|cx| ZedisKeyTree::new(server_state.clone(), window, cx))
-            })
-            .clone();
+    fn render_editor(
+        &mut self,
+        server_state: Entity<ZedisServerState>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        // Lazily initialize key tree and value editor of the tab - reuse existing or create new
+        let tab_views = self.tab_views.entry(server_state.entity_id()).or_insert_with(|| {
+            debug!("Creating new key tree and value editor view");
+            TabViews {
+                value_editor: cx.new(|cx| ZedisEditor::new(server_state.clone(), window, cx)),
+                key_tree: cx.new(|cx| ZedisKeyTree::new(server_state.clone(), window, cx)),
+            }
+        });
+        let value_editor = tab_views.value_editor.clone();
+        let key_tree = tab_views.key_tree.clone();
 
         let mut right_panel = resizable_panel();
         if let Some(content_width) = cx.global::<ZedisGlobalStore>().read(cx).content_width() {
@@ -203,7 +245,7 @@ impl ZedisContent {
         }
         let (key_tree_width, min_width, max_width) = get_key_tree_widths(self.key_tree_width);
 
-        h_resizable("editor-container")
+        h_resizable(("editor-container", server_state.entity_id().as_u64()))
             .child(
                 // Left panel: Resizable key tree
                 resizable_panel()
@@ -239,6 +281,54 @@ impl ZedisContent {
                 .detach();
             }))
     }
+
+    /// Render the editor tabs with buttons to close a tab and open a new one
+    fn render_tab_bar(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let tabs = self.tabs.read(cx);
+        let count = tabs.states().len();
+        let active = tabs.active_index();
+        let titles: Vec<_> = (0..count).map(|index| tabs.title(index, cx)).collect();
+
+        TabBar::new("editor-tabs")
+            .underline()
+            .small()
+            .selected_index(active)
+            .children(titles.into_iter().enumerate().map(|(index, title)| {
+                Tab::new().label(title).when(count > 1, |this| {
+                    this.suffix(
+                        Button::new(("editor-tab-close", index))
+                            .ghost()
+                            .xsmall()
+                            .icon(IconName::Close)
+                            .tooltip(i18n_editor(cx, "close_tab_tooltip"))
+                            .on_click(cx.listener(move |this, _, _window, cx| {
+                                cx.stop_propagation();
+                                this.tabs.update(cx, |tabs, cx| {
+                                    tabs.close_tab(index, cx);
+                                });
+                            })),
+                    )
+                })
+            }))
+            .suffix(
+                Button::new("editor-tab-new")
+                    .ghost()
+                    .small()
+                    .icon(IconName::Plus)
+                    .tooltip(i18n_editor(cx, "new_tab_tooltip"))
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.tabs.update(cx, |tabs, cx| {
+                            tabs.new_tab(cx);
+                        });
+                    })),
+            )
+            .on_click(cx.listener(|this, index: &usize, _window, cx| {
+                let index = *index;
+                this.tabs.update(cx, |tabs, cx| {
+                    tabs.activate(index, cx);
+                });
+            }))
+    }
 }
 
 impl Render for ZedisContent {
@@ -247,7 +337,8 @@ impl Render for ZedisContent {
     /// Rendering logic:
     /// 1. If on home route -> show server list
     /// 2. If server is busy (connecting/loading) -> show loading skeleton
-    /// 3. Otherwise -> show editor interface (key tree + value editor)
+    /// 3. Otherwise -> show editor tabs and the editor interface (key tree + value editor)
+    ///    or the script workbench of the active tab
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let route = cx.global::<ZedisGlobalStore>().read(cx).route();
         let base = v_flex().id("main-container").flex_1().h_full();
@@ -257,23 +348,30 @@ impl Render for ZedisContent {
             Route::Home => base.child(self.render_servers(window, cx)).into_any_element(),
             Route::Settings => base.child(self.render_settings(window, cx)).into_any_element(),
             _ => {
+                let server_state = self.tabs.read(cx).active();
+                let status_bar = self
+                    .status_bars
+                    .entry(server_state.entity_id())
+                    .or_insert_with(|| cx.new(|cx| ZedisStatusBar::new(server_state.clone(), window, cx)))
+                    .clone();
                 // Route 2: Loading state (show skeleton while connecting/loading)
-                let is_busy = self.server_state.read(cx).is_busy();
+                let is_busy = server_state.read(cx).is_busy();
 
                 // Route 3: Main editor interface
-                base.when(is_busy, |this| this.child(self.render_loading(window, cx)))
+                base.child(self.render_tab_bar(window, cx))
+                    .when(is_busy, |this| this.child(self.render_loading(window, cx)))
                     .when(!is_busy, |this| {
-                        this.child(
-                            div().flex_1().w_full().relative().child(
-                                div()
-                                    .absolute()
-                                    .inset_0()
-                                    .size_full()
-                                    .child(self.render_editor(window, cx)),
-                            ),
-                        )
+                        this.child(div().flex_1().w_full().relative().child(
+                            div().absolute().inset_0().size_full().map(|this| {
+                                if route == Route::Scripts {
+                                    this.child(self.render_scripts(server_state, window, cx))
+                                } else {
+                                    this.child(self.render_editor(server_state, window, cx))
+                                }
+                            }),
+                        ))
                     })
-                    .child(self.status_bar.clone())
+                    .child(status_bar)
                     .into_any_element()
             }
         }
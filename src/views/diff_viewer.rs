@@ -0,0 +1,367 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    assets::CustomIconName,
+    states::{
+        DiffKind, DiffTarget, ServerEvent, ValueDiff, ValueDiffData, ZedisServerState, i18n_common, i18n_diff_viewer,
+    },
+};
+use gpui::{App, Entity, Hsla, SharedString, Subscription, Window, div, prelude::*, px, uniform_list};
+use gpui_component::{
+    ActiveTheme, Disableable, IndexPath, Sizable,
+    button::{Button, ButtonVariants},
+    checkbox::Checkbox,
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    label::Label,
+    select::{Select, SelectItem, SelectState},
+    v_flex,
+};
+use std::sync::Arc;
+use tracing::info;
+
+// Constants for UI layout
+const DIFF_VIEWER_HEIGHT: f32 = 520.0;
+const DB_INPUT_WIDTH: f32 = 60.0;
+const SERVER_SELECT_WIDTH: f32 = 180.0;
+const LINE_NO_WIDTH: f32 = 48.0;
+const DIFF_BG_OPACITY: f32 = 0.15;
+
+#[derive(Debug, Clone)]
+struct ServerItem {
+    id: SharedString,
+    name: SharedString,
+}
+
+impl SelectItem for ServerItem {
+    type Value = SharedString;
+    fn title(&self) -> SharedString {
+        self.name.clone()
+    }
+    fn value(&self) -> &Self::Value {
+        &self.id
+    }
+}
+
+/// Input states for one side (server + db + key) of the comparison
+struct DiffSide {
+    server_state: Entity<SelectState<Vec<ServerItem>>>,
+    db_state: Entity<InputState>,
+    key_state: Entity<InputState>,
+}
+
+impl DiffSide {
+    fn new(
+        servers: Vec<ServerItem>,
+        target: &DiffTarget,
+        window: &mut Window,
+        cx: &mut Context<ZedisDiffViewer>,
+    ) -> Self {
+        let selected = servers.iter().position(|item| item.id == target.server_id).unwrap_or(0);
+        let server_state = cx.new(|cx| SelectState::new(servers, Some(IndexPath::new(selected)), window, cx));
+        let db = target.db.to_string();
+        let db_state = cx.new(|cx| InputState::new(window, cx).default_value(db));
+        let key = target.key.clone();
+        let key_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .default_value(key)
+                .placeholder(i18n_diff_viewer(cx, "key_placeholder"))
+        });
+        Self {
+            server_state,
+            db_state,
+            key_state,
+        }
+    }
+    fn target(&self, cx: &App) -> Option<DiffTarget> {
+        let server_id = self.server_state.read(cx).selected_value()?.clone();
+        let key = self.key_state.read(cx).value();
+        if key.is_empty() {
+            return None;
+        }
+        Some(DiffTarget {
+            server_id,
+            db: self.db_state.read(cx).value().parse::<usize>().unwrap_or_default(),
+            key,
+        })
+    }
+}
+
+/// Diff viewer comparing two values (server + db + key on each side).
+///
+/// Strings are shown as a line based text diff, collections as a structural
+/// diff of fields/members.
+pub struct ZedisDiffViewer {
+    server_state: Entity<ZedisServerState>,
+    left: DiffSide,
+    right: DiffSide,
+    diff: Option<Arc<ValueDiff>>,
+    /// Hide unchanged lines/entries
+    only_changes: bool,
+    loading: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisDiffViewer {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let state = server_state.read(cx);
+        let servers: Vec<ServerItem> = state
+            .servers()
+            .unwrap_or_default()
+            .iter()
+            .map(|server| ServerItem {
+                id: server.id.clone().into(),
+                name: server.name.clone().into(),
+            })
+            .collect();
+        let target = DiffTarget {
+            server_id: state.server_id().to_string().into(),
+            db: state.db(),
+            key: state.key().unwrap_or_default(),
+        };
+
+        let left = DiffSide::new(servers.clone(), &target, window, cx);
+        let right = DiffSide::new(servers, &target, window, cx);
+
+        let mut subscriptions = vec![cx.subscribe(&server_state, |this, server_state, event, cx| {
+            if let ServerEvent::ValueDiffLoaded = event {
+                this.loading = false;
+                this.diff = server_state.read(cx).value_diff();
+                cx.notify();
+            }
+        })];
+        for key_state in [&left.key_state, &right.key_state] {
+            subscriptions.push(cx.subscribe(key_state, |this, _, event, cx| {
+                if matches!(event, InputEvent::PressEnter { .. }) {
+                    this.compare(cx);
+                }
+            }));
+        }
+
+        info!("Creating new diff viewer view");
+
+        Self {
+            server_state,
+            left,
+            right,
+            diff: None,
+            only_changes: false,
+            loading: false,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn compare(&mut self, cx: &mut Context<Self>) {
+        let (Some(left), Some(right)) = (self.left.target(cx), self.right.target(cx)) else {
+            return;
+        };
+        self.loading = true;
+        self.diff = None;
+        self.server_state.update(cx, move |state, cx| {
+            state.diff_values(left, right, cx);
+        });
+        cx.notify();
+    }
+
+    fn render_side(&self, side: &DiffSide, label: SharedString) -> impl IntoElement {
+        h_flex()
+            .gap_2()
+            .w_full()
+            .child(Label::new(label).w(px(LINE_NO_WIDTH)).text_sm())
+            .child(Select::new(&side.server_state).w(px(SERVER_SELECT_WIDTH)).small())
+            .child(Input::new(&side.db_state).w(px(DB_INPUT_WIDTH)).small())
+            .child(Input::new(&side.key_state).flex_1().small())
+    }
+
+    fn kind_color(kind: DiffKind, cx: &App) -> Option<Hsla> {
+        let theme = cx.theme();
+        match kind {
+            DiffKind::Unchanged => None,
+            DiffKind::Added => Some(theme.green),
+            DiffKind::Removed => Some(theme.red),
+            DiffKind::Changed => Some(theme.yellow),
+        }
+    }
+
+    fn render_diff(&self, diff: Arc<ValueDiff>, cx: &mut Context<Self>) -> impl IntoElement {
+        let only_changes = self.only_changes;
+        let visible = |kind: DiffKind| !only_changes || kind != DiffKind::Unchanged;
+        let indexes: Vec<usize> = match &diff.data {
+            ValueDiffData::Text(lines) => lines
+                .iter()
+                .enumerate()
+                .filter_map(|(i, line)| visible(line.kind).then_some(i))
+                .collect(),
+            ValueDiffData::Structural(entries) => entries
+                .iter()
+                .enumerate()
+                .filter_map(|(i, entry)| visible(entry.kind).then_some(i))
+                .collect(),
+        };
+        let muted = cx.theme().muted_foreground;
+        let border = cx.theme().border;
+
+        uniform_list("diff-viewer-rows", indexes.len(), move |range, _window, cx| {
+            range
+                .filter_map(|i| indexes.get(i).copied())
+                .map(|index| {
+                    let row = h_flex()
+                        .w_full()
+                        .px_2()
+                        .gap_2()
+                        .text_sm()
+                        .border_b_1()
+                        .border_color(border);
+                    match &diff.data {
+                        ValueDiffData::Text(lines) => {
+                            let Some(line) = lines.get(index) else {
+                                return row;
+                            };
+                            let sign = match line.kind {
+                                DiffKind::Added => "+",
+                                DiffKind::Removed => "-",
+                                _ => " ",
+                            };
+                            let format_no = |no: Option<usize>| no.map(|n| n.to_string()).unwrap_or_default();
+                            row.when_some(Self::kind_color(line.kind, cx), |this, color| {
+                                this.bg(color.opacity(DIFF_BG_OPACITY))
+                            })
+                            .child(
+                                Label::new(format_no(line.left_no))
+                                    .w(px(LINE_NO_WIDTH))
+                                    .text_color(muted),
+                            )
+                            .child(
+                                Label::new(format_no(line.right_no))
+                                    .w(px(LINE_NO_WIDTH))
+                                    .text_color(muted),
+                            )
+                            .child(Label::new(sign).w(px(12.)))
+                            .child(
+                                Label::new(line.text.clone())
+                                    .flex_1()
+                                    .whitespace_nowrap()
+                                    .text_ellipsis(),
+                            )
+                        }
+                        ValueDiffData::Structural(entries) => {
+                            let Some(entry) = entries.get(index) else {
+                                return row;
+                            };
+                            let sign = match entry.kind {
+                                DiffKind::Added => "+",
+                                DiffKind::Removed => "-",
+                                DiffKind::Changed => "~",
+                                DiffKind::Unchanged => " ",
+                            };
+                            let cell = |value: Option<SharedString>| {
+                                div().flex_1().w_0().overflow_hidden().child(
+                                    Label::new(value.unwrap_or_default())
+                                        .whitespace_nowrap()
+                                        .text_ellipsis(),
+                                )
+                            };
+                            row.when_some(Self::kind_color(entry.kind, cx), |this, color| {
+                                this.bg(color.opacity(DIFF_BG_OPACITY))
+                            })
+                            .child(Label::new(sign).w(px(12.)))
+                            .child(cell(Some(entry.name.clone())))
+                            .child(cell(entry.left.clone()))
+                            .child(cell(entry.right.clone()))
+                        }
+                    }
+                })
+                .collect()
+        })
+        .flex_1()
+        .w_full()
+    }
+}
+
+impl Render for ZedisDiffViewer {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let text_color = cx.theme().muted_foreground;
+        let summary = self.diff.as_ref().map(|diff| {
+            if diff.is_same() {
+                i18n_diff_viewer(cx, "same")
+            } else {
+                format!(
+                    "+{} / -{} / ~{}  ({} : {})",
+                    diff.added,
+                    diff.removed,
+                    diff.changed,
+                    diff.left_type.as_str(),
+                    diff.right_type.as_str()
+                )
+                .into()
+            }
+        });
+        let is_structural = matches!(
+            self.diff.as_ref().map(|diff| &diff.data),
+            Some(ValueDiffData::Structural(_))
+        );
+
+        v_flex()
+            .w_full()
+            .h(px(DIFF_VIEWER_HEIGHT))
+            .gap_2()
+            .child(self.render_side(&self.left, i18n_diff_viewer(cx, "left")))
+            .child(self.render_side(&self.right, i18n_diff_viewer(cx, "right")))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("diff-viewer-compare")
+                            .primary()
+                            .small()
+                            .icon(CustomIconName::FileDiff)
+                            .label(i18n_diff_viewer(cx, "compare"))
+                            .loading(self.loading)
+                            .disabled(self.loading)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.compare(cx);
+                            })),
+                    )
+                    .child(
+                        Checkbox::new("diff-viewer-only-changes")
+                            .label(i18n_diff_viewer(cx, "only_changes"))
+                            .checked(self.only_changes)
+                            .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                this.only_changes = *checked;
+                                cx.notify();
+                            })),
+                    )
+                    .child(div().flex_1())
+                    .when_some(summary, |this, summary| {
+                        this.child(Label::new(summary).text_sm().text_color(text_color))
+                    }),
+            )
+            .when(is_structural, |this| {
+                this.child(
+                    h_flex()
+                        .w_full()
+                        .px_2()
+                        .gap_2()
+                        .text_sm()
+                        .text_color(text_color)
+                        .child(div().w(px(12.)))
+                        .child(div().flex_1().child(i18n_common(cx, "name")))
+                        .child(div().flex_1().child(i18n_diff_viewer(cx, "left")))
+                        .child(div().flex_1().child(i18n_diff_viewer(cx, "right"))),
+                )
+            })
+            .when_some(self.diff.clone(), |this, diff| this.child(self.render_diff(diff, cx)))
+    }
+}
@@ -14,14 +14,14 @@
 
 use crate::{
     assets::CustomIconName,
-    helpers::{EditorAction, humanize_keystroke, validate_ttl},
+    helpers::{EditorAction, get_default_file_dir, humanize_keystroke, sanitize_file_name, validate_ttl},
     states::{KeyType, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_editor},
-    views::{ZedisBytesEditor, ZedisHashEditor, ZedisListEditor, ZedisSetEditor, ZedisZsetEditor},
+    views::{ZedisBytesEditor, ZedisDiffViewer, ZedisHashEditor, ZedisListEditor, ZedisSetEditor, ZedisZsetEditor},
 };
-use gpui::{ClipboardItem, Entity, SharedString, Subscription, Window, div, prelude::*, px};
+use gpui::{ClipboardItem, Entity, PathPromptOptions, SharedString, Subscription, Window, div, prelude::*, px};
 use gpui_component::{
-    ActiveTheme, Disableable, Icon, IconName, WindowExt,
-    button::Button,
+    ActiveTheme, Disableable, Icon, IconName, Selectable, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
     h_flex,
     input::{Input, InputEvent, InputState},
     label::Label,
@@ -37,6 +37,7 @@ use tracing::{debug, info};
 // Constants
 const RECENTLY_SELECTED_THRESHOLD_MS: u64 = 300;
 const TTL_INPUT_MAX_WIDTH: f32 = 130.0;
+const DIFF_DIALOG_WIDTH: f32 = 960.0;
 
 /// Main editor component for displaying and editing Redis key values
 /// Supports different key types (String, List, etc.) with type-specific editors
@@ -76,11 +77,22 @@ impl ZedisEditor {
         });
 
         // Subscribe to server events to track when keys are selected
-        subscriptions.push(cx.subscribe(&server_state, |this, _server_state, event, _cx| {
-            if let ServerEvent::KeySelected(_) = event {
-                this.selected_key_at = Some(Instant::now());
-            }
-        }));
+        subscriptions.push(
+            cx.subscribe(&server_state, |this, _server_state, event, cx| match event {
+                ServerEvent::KeySelected(_) => {
+                    this.selected_key_at = Some(Instant::now());
+                }
+                // refresh the pin button
+                ServerEvent::BookmarksUpdated => {
+                    cx.notify();
+                }
+                // refresh the staged changes panel
+                ServerEvent::StagedChangesUpdated => {
+                    cx.notify();
+                }
+                _ => {}
+            }),
+        );
 
         // Subscribe to TTL input events for Enter key and blur
         subscriptions.push(cx.subscribe_in(
@@ -163,6 +175,67 @@ impl ZedisEditor {
                 })
         });
     }
+    /// Open the diff viewer, prefilled with the current server, db and key
+    fn open_diff_viewer(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let viewer = cx.new(|cx| ZedisDiffViewer::new(server_state, window, cx));
+        window.open_dialog(cx, move |dialog, _, cx| {
+            dialog
+                .title(i18n_editor(cx, "diff_title"))
+                .w(px(DIFF_DIALOG_WIDTH))
+                .overlay_closable(true)
+                .child(viewer.clone())
+        });
+    }
+    /// Replace the string value with the content of a file picked by the user
+    fn import_value(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        let Some(key) = self.server_state.read(cx).key() else {
+            return;
+        };
+        let server_state = self.server_state.clone();
+        let paths = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some(i18n_editor(cx, "import_value_tooltip")),
+        });
+        cx.spawn(async move |_, cx| {
+            let Ok(Ok(Some(paths))) = paths.await else {
+                return;
+            };
+            let Some(path) = paths.into_iter().next() else {
+                return;
+            };
+            let _ = server_state.update(cx, move |state, cx| {
+                state.import_value_from_file(key, path, cx);
+            });
+        })
+        .detach();
+    }
+    /// Write the string value to a file, the name is suggested from the key and detected format
+    fn export_value(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.read(cx);
+        let Some(key) = server_state.key() else {
+            return;
+        };
+        let extension = server_state
+            .value()
+            .and_then(|value| value.bytes_value())
+            .map(|value| value.file_extension())
+            .unwrap_or("bin");
+        let file_name = format!("{}.{extension}", sanitize_file_name(&key));
+        let path = cx.prompt_for_new_path(&get_default_file_dir(), Some(&file_name));
+        let server_state = self.server_state.clone();
+        cx.spawn(async move |_, cx| {
+            let Ok(Ok(Some(path))) = path.await else {
+                return;
+            };
+            let _ = server_state.update(cx, move |state, cx| {
+                state.export_value_to_file(key, path, cx);
+            });
+        })
+        .detach();
+    }
     fn reload(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
         let Some(key) = self.server_state.read(cx).key() else {
             return;
@@ -213,12 +286,16 @@ impl ZedisEditor {
         });
         cx.notify();
     }
-    /// Render the key information bar with actions (copy, save, TTL, delete)
+    /// Render the key information bar with actions (copy, save, TTL, pin, delete)
     fn render_select_key(&self, cx: &mut Context<Self>) -> impl IntoElement {
         let server_state = self.server_state.read(cx);
         let Some(key) = server_state.key() else {
             return h_flex();
         };
+        let pinned = server_state.is_key_pinned(&key);
+        let staging = server_state.is_staging();
+        // a browsed replica is read-only
+        let readonly = server_state.is_readonly();
 
         let mut is_busy = false;
         let mut btns = vec![];
@@ -293,6 +370,34 @@ impl ZedisEditor {
             );
         }
 
+        // Import / export buttons for string values
+        if self.bytes_editor.is_some() {
+            btns.push(
+                Button::new("zedis-editor-import-value")
+                    .ml_2()
+                    .outline()
+                    .disabled(readonly || should_show_loading)
+                    .tooltip(i18n_editor(cx, "import_value_tooltip"))
+                    .icon(CustomIconName::FileUp)
+                    .on_click(cx.listener(move |this, _event, window, cx| {
+                        this.import_value(window, cx);
+                    }))
+                    .into_any_element(),
+            );
+            btns.push(
+                Button::new("zedis-editor-export-value")
+                    .ml_2()
+                    .outline()
+                    .disabled(should_show_loading)
+                    .tooltip(i18n_editor(cx, "export_value_tooltip"))
+                    .icon(CustomIconName::FileDown)
+                    .on_click(cx.listener(move |this, _event, window, cx| {
+                        this.export_value(window, cx);
+                    }))
+                    .into_any_element(),
+            );
+        }
+
         // Add TTL button (or input field when in edit mode)
         if !ttl.is_empty() {
             let ttl_btn = if self.ttl_edit_mode {
@@ -320,7 +425,7 @@ impl ZedisEditor {
                     .ml_2()
                     .outline()
                     .w(px(TTL_INPUT_MAX_WIDTH))
-                    .disabled(should_show_loading)
+                    .disabled(readonly || should_show_loading)
                     .tooltip(ttl_tooltip)
                     .label(ttl.clone())
                     .icon(CustomIconName::Clock3)
@@ -352,12 +457,69 @@ impl ZedisEditor {
                 .into_any_element(),
         );
 
+        // pin the key to the bookmarks
+        let pinned_key = key.clone();
+        btns.push(
+            Button::new("zedis-editor-pin-key")
+                .ml_2()
+                .outline()
+                .selected(pinned)
+                .tooltip(if pinned {
+                    i18n_editor(cx, "unpin_key_tooltip")
+                } else {
+                    i18n_editor(cx, "pin_key_tooltip")
+                })
+                .icon(IconName::Star)
+                .on_click(cx.listener(move |this, _event, _window, cx| {
+                    let key = pinned_key.clone();
+                    this.server_state.update(cx, |state, cx| {
+                        state.toggle_pinned_key(key, cx);
+                    });
+                }))
+                .into_any_element(),
+        );
+
+        // queue the writes of the collection editors instead of sending them
+        btns.push(
+            Button::new("zedis-editor-staged-mode")
+                .ml_2()
+                .outline()
+                .selected(staging)
+                .disabled(readonly)
+                .tooltip(if staging {
+                    i18n_editor(cx, "disable_staged_mode_tooltip")
+                } else {
+                    i18n_editor(cx, "enable_staged_mode_tooltip")
+                })
+                .icon(CustomIconName::Layers)
+                .on_click(cx.listener(move |this, _event, _window, cx| {
+                    this.server_state.update(cx, |state, cx| {
+                        state.set_staging(!staging, cx);
+                    });
+                }))
+                .into_any_element(),
+        );
+
+        // compare with another key or server
+        btns.push(
+            Button::new("zedis-editor-diff-key")
+                .ml_2()
+                .outline()
+                .disabled(should_show_loading)
+                .tooltip(i18n_editor(cx, "diff_key_tooltip"))
+                .icon(CustomIconName::FileDiff)
+                .on_click(cx.listener(move |this, _event, window, cx| {
+                    this.open_diff_viewer(window, cx);
+                }))
+                .into_any_element(),
+        );
+
         // Add delete button
         btns.push(
             Button::new("zedis-editor-delete-key")
                 .ml_2()
                 .outline()
-                .disabled(should_show_loading)
+                .disabled(readonly || should_show_loading)
                 .tooltip(i18n_editor(cx, "delete_key_tooltip"))
                 .icon(IconName::CircleX)
                 .on_click(cx.listener(move |this, _event, window, cx| {
@@ -399,6 +561,115 @@ impl ZedisEditor {
             )
             .children(btns)
     }
+    /// Discard the staged commands with confirmation dialog
+    fn discard_staged_commands(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let server_state = server_state.clone();
+            dialog
+                .confirm()
+                .child(i18n_editor(cx, "discard_staged_changes_prompt"))
+                .on_ok(move |_, window, cx| {
+                    server_state.update(cx, |state, cx| {
+                        state.discard_staged_commands(cx);
+                    });
+                    window.close_dialog(cx);
+                    true
+                })
+        });
+    }
+    /// Render the staged commands, grouped by the edit which staged them
+    fn render_staged_changes(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let server_state = self.server_state.read(cx);
+        let commands = server_state.staged_commands();
+        let is_applying = server_state.is_applying_staged_commands();
+        let readonly = server_state.is_readonly();
+        let mut groups: Vec<(usize, Vec<SharedString>)> = vec![];
+        for command in commands.iter() {
+            let mut line = command.command_line();
+            if command.deferred {
+                line = format!("{line}  ({})", i18n_editor(cx, "staged_command_deferred"));
+            }
+            match groups.last_mut() {
+                Some((group, lines)) if *group == command.group => lines.push(line.into()),
+                _ => groups.push((command.group, vec![line.into()])),
+            }
+        }
+        let rows = groups.into_iter().map(|(group, lines)| {
+            h_flex()
+                .id(("zedis-editor-staged-group", group))
+                .w_full()
+                .px_2()
+                .py_1()
+                .gap_2()
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .child(v_flex().flex_1().w_0().children(lines.into_iter().map(|line| {
+                    Label::new(line)
+                        .text_sm()
+                        .font_family(cx.theme().mono_font_family.clone())
+                        .text_ellipsis()
+                        .whitespace_nowrap()
+                })))
+                .child(
+                    Button::new(("zedis-editor-staged-remove", group))
+                        .ghost()
+                        .small()
+                        .disabled(is_applying)
+                        .tooltip(i18n_editor(cx, "remove_staged_change_tooltip"))
+                        .icon(CustomIconName::X)
+                        .on_click(cx.listener(move |this, _event, _window, cx| {
+                            this.server_state.update(cx, |state, cx| {
+                                state.remove_staged_group(group, cx);
+                            });
+                        })),
+                )
+        });
+
+        v_flex()
+            .w_full()
+            .flex_none()
+            .max_h(px(240.0))
+            .border_t_1()
+            .border_color(cx.theme().border)
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_2()
+                    .items_center()
+                    .border_b_1()
+                    .border_color(cx.theme().border)
+                    .child(
+                        Label::new(format!("{} ({})", i18n_editor(cx, "staged_changes"), commands.len()))
+                            .flex_1()
+                            .text_sm(),
+                    )
+                    .child(
+                        Button::new("zedis-editor-staged-discard")
+                            .outline()
+                            .small()
+                            .disabled(is_applying)
+                            .label(i18n_editor(cx, "discard_staged_changes"))
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.discard_staged_commands(window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("zedis-editor-staged-apply")
+                            .primary()
+                            .small()
+                            .loading(is_applying)
+                            .disabled(readonly)
+                            .label(i18n_editor(cx, "apply_staged_changes"))
+                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                this.server_state.update(cx, |state, cx| {
+                                    state.apply_staged_commands(cx);
+                                });
+                            })),
+                    ),
+            )
+            .child(v_flex().flex_1().overflow_y_scrollbar().children(rows))
+    }
     /// Clean up unused editors when switching between key types
     fn reset_editors(&mut self, key_type: KeyType) {
         if key_type != KeyType::String {
@@ -481,17 +752,34 @@ impl Render for ZedisEditor {
     /// Main render method - displays key info bar and appropriate editor
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let server_state = self.server_state.read(cx);
+        let has_staged_changes = !server_state.staged_commands().is_empty();
 
-        // Don't render anything if no key is selected
+        // Only the staged changes are rendered if no key is selected
         if server_state.key().is_none() {
+            if has_staged_changes {
+                return v_flex()
+                    .w_full()
+                    .h_full()
+                    .justify_end()
+                    .child(self.render_staged_changes(cx))
+                    .into_any_element();
+            }
             return v_flex().into_any_element();
         }
 
+        let editor = self.render_editor(window, cx).into_any_element();
         v_flex()
             .w_full()
             .h_full()
             .child(self.render_select_key(cx))
-            .child(self.render_editor(window, cx))
+            .map(|this| {
+                if has_staged_changes {
+                    this.child(v_flex().flex_1().min_h_0().child(editor))
+                        .child(self.render_staged_changes(cx))
+                } else {
+                    this.child(editor)
+                }
+            })
             .on_action(cx.listener(move |this, event: &EditorAction, window, cx| match event {
                 EditorAction::Save => {
                     this.save(window, cx);
@@ -18,24 +18,29 @@
 //! It supports operations like:
 //! - Viewing HASH field-value pairs in a two-column table
 //! - Adding new fields with values via a dialog form
-//! - Updating values of existing fields (inline editing)
+//! - Updating values and TTLs of existing fields (inline editing)
 //! - Removing field-value pairs
 //! - Filtering fields with pattern matching
 //! - Incremental loading of large HASHes with pagination
 
 use crate::{
     components::{FormDialog, FormField, ZedisKvFetcher, open_add_form_dialog},
-    states::{RedisValue, ZedisServerState, i18n_common, i18n_hash_editor},
+    states::{FIELD_TTL_VERSION, RedisElement, RedisValue, ZedisServerState, i18n_common, i18n_hash_editor},
     views::{KvTableColumn, ZedisKvTable},
 };
 use gpui::{App, Entity, SharedString, Window, div, prelude::*};
 use gpui_component::WindowExt;
 use std::rc::Rc;
 
+/// Width of the field TTL column in pixels
+const TTL_COLUMN_WIDTH: f32 = 120.0;
+/// Column of the field TTL (after the index, field and value columns)
+const TTL_COLUMN_INDEX: usize = 3;
+
 /// Data adapter for Redis HASH values to work with the KV table component.
 ///
 /// This struct implements the `ZedisKvFetcher` trait to provide data access
-/// and operations for the two-column table view (field and value columns).
+/// and operations for the table view (field, value and field TTL columns).
 struct ZedisHashValues {
     /// Current Redis HASH value data
     value: RedisValue,
@@ -54,15 +59,26 @@ impl ZedisKvFetcher for ZedisHashValues {
     /// Column layout:
     /// - Column 1: Field name
     /// - Column 2: Field value
+    /// - Column 3: Field TTL (Redis 7.4+)
     fn get(&self, row_ix: usize, col_ix: usize) -> Option<SharedString> {
         let hash = self.value.hash_value()?;
         let (field, value) = hash.values.get(row_ix)?;
 
-        // Column 2 is the value, others show the field name
-        if col_ix == 2 {
-            Some(value.clone())
-        } else {
-            Some(field.clone())
+        // Column 2 is the value, column 3 the TTL, others show the field name
+        match col_ix {
+            2 => Some(value.text.clone()),
+            TTL_COLUMN_INDEX => Some(hash.field_ttl_text(field)),
+            _ => Some(field.text.clone()),
+        }
+    }
+
+    /// Retrieves the field or value element of a cell, rendered by its detected format.
+    fn get_element(&self, row_ix: usize, col_ix: usize) -> Option<RedisElement> {
+        let (field, value) = self.value.hash_value()?.values.get(row_ix)?;
+        match col_ix {
+            2 => Some(value.clone()),
+            TTL_COLUMN_INDEX => None,
+            _ => Some(field.clone()),
         }
     }
 
@@ -133,22 +149,43 @@ impl ZedisKvFetcher for ZedisHashValues {
         });
     }
 
-    /// Handles inline editing of a HASH field's value.
+    /// Handles inline editing of a HASH field's value and TTL.
     ///
-    /// Called when the user edits the value column directly in the table.
-    /// Updates the value for the existing field using Redis HSET.
-    fn handle_update_value(&self, _row_ix: usize, values: Vec<SharedString>, _window: &mut Window, cx: &mut App) {
-        // Extract field name and new value from values
-        let Some(field) = values.first() else {
+    /// Called when the user edits the value or TTL column directly in the table.
+    /// Updates the value for the existing field using Redis HSET (HSETEX on Redis 8.0+),
+    /// a TTL change only is applied with HPEXPIRE / HPERSIST (HGETEX on Redis 8.0+).
+    fn handle_update_value(
+        &self,
+        row_ix: usize,
+        values: Vec<SharedString>,
+        original_values: Vec<SharedString>,
+        _window: &mut Window,
+        cx: &mut App,
+    ) {
+        // The field column is readonly, use the loaded field to keep its raw bytes
+        let Some(hash) = self.value.hash_value() else {
+            return;
+        };
+        let Some((field, original_value)) = hash.values.get(row_ix) else {
             return;
         };
         let Some(value) = values.get(1) else {
             return;
         };
+        // the TTL column is only shown for servers supporting field TTLs, the rendered TTL
+        // is a countdown, so it is only applied if the user changed the text of the input
+        let ttl = values
+            .get(2)
+            .filter(|ttl| {
+                original_values
+                    .get(2)
+                    .is_none_or(|original| ttl.trim() != original.trim())
+            })
+            .cloned();
 
         // Execute update operation
         self.server_state.update(cx, |this, cx| {
-            this.update_hash_value(field.clone(), value.clone(), cx);
+            this.update_hash_value(field.clone(), original_value, value.clone(), ttl, cx);
         });
     }
 
@@ -217,20 +254,18 @@ impl ZedisHashEditor {
     /// * `cx` - GPUI context for component initialization
     ///
     /// # Returns
-    /// A new `ZedisHashEditor` instance with a two-column table (Field and Value)
+    /// A new `ZedisHashEditor` instance with a table of Field, Value and
+    /// (for Redis 7.4+) TTL columns
     pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
-        // Initialize the KV table with two columns: field and value
-        let table_state = cx.new(|cx| {
-            ZedisKvTable::<ZedisHashValues>::new(
-                vec![
-                    KvTableColumn::new("Field", None), // Field name column (flexible width)
-                    KvTableColumn::new("Value", None), // Field value column (flexible width)
-                ],
-                server_state,
-                window,
-                cx,
-            )
-        });
+        let mut columns = vec![
+            KvTableColumn::new("Field", None), // Field name column (flexible width)
+            KvTableColumn::new("Value", None), // Field value column (flexible width)
+        ];
+        if server_state.read(cx).is_at_least_version(FIELD_TTL_VERSION) {
+            columns.push(KvTableColumn::new("TTL", Some(TTL_COLUMN_WIDTH)));
+        }
+        // Initialize the KV table with the field, value and TTL columns
+        let table_state = cx.new(|cx| ZedisKvTable::<ZedisHashValues>::new(columns, server_state, window, cx));
 
         Self { table_state }
     }
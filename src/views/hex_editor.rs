@@ -0,0 +1,554 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hex editor for binary string values.
+//!
+//! Bytes can be overwritten, inserted and deleted from the hex panel
+//! (typing hex digits) or the ASCII panel (typing printable characters).
+//! The toolbar provides go to offset and byte pattern search.
+//! A page of a partially loaded value is shown readonly, with the addresses
+//! of the whole value.
+
+use crate::helpers::get_font_family;
+use crate::states::{PARTIAL_PAGE_SIZE, i18n_editor};
+use bytes::Bytes;
+use gpui::{
+    App, Entity, EventEmitter, FocusHandle, Focusable, KeyDownEvent, MouseButton, ScrollStrategy, SharedString,
+    Subscription, UniformListScrollHandle, Window, div, hsla, prelude::*, px, uniform_list,
+};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable,
+    button::Button,
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    label::Label,
+    v_flex,
+};
+use std::ops::Range;
+
+const HEX_CELL_WIDTH: f32 = 22.0;
+const ASCII_CELL_WIDTH: f32 = 9.0;
+const OFFSET_INPUT_WIDTH: f32 = 160.0;
+const SEARCH_INPUT_WIDTH: f32 = 240.0;
+
+/// Events emitted by the hex editor
+pub enum HexEditorEvent {
+    /// The bytes have been modified
+    Change,
+    /// The page of a partial value starting at the offset has to be loaded
+    LoadPage(usize),
+}
+
+/// Panel receiving the typed input
+#[derive(Clone, Copy, PartialEq)]
+enum HexPanel {
+    Hex,
+    Ascii,
+}
+
+/// Parse an offset, either decimal (`31`) or hexadecimal (`0x1f`)
+fn parse_offset(value: &str) -> Option<usize> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        usize::from_str_radix(hex, 16).ok()
+    } else {
+        value.parse::<usize>().ok()
+    }
+}
+
+/// Parse a search pattern.
+///
+/// Hex bytes (`de ad be ef` or `deadbeef`) are used as is,
+/// anything else is searched as text.
+fn parse_byte_pattern(value: &str) -> Option<Vec<u8>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    let digits: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+    if digits.len().is_multiple_of(2) && digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        let bytes = (0..digits.len())
+            .step_by(2)
+            .filter_map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+            .collect();
+        return Some(bytes);
+    }
+    Some(value.as_bytes().to_vec())
+}
+
+/// Find the next occurrence of the pattern starting at `from`, wrapping around to the start
+fn find_pattern(data: &[u8], pattern: &[u8], from: usize) -> Option<usize> {
+    if pattern.is_empty() || pattern.len() > data.len() {
+        return None;
+    }
+    let positions = data.windows(pattern.len()).enumerate();
+    positions
+        .clone()
+        .skip(from)
+        .chain(positions.take(from))
+        .find(|(_, window)| *window == pattern)
+        .map(|(index, _)| index)
+}
+
+pub struct ZedisHexEditor {
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    /// Bytes loaded from redis
+    original: Bytes,
+    /// Bytes being edited
+    bytes: Vec<u8>,
+    /// Bytes per row
+    width: usize,
+    /// Position of the cursor, `bytes.len()` is the append position
+    cursor: usize,
+    /// Whether the high nibble of the byte at the cursor has been typed
+    low_nibble: bool,
+    /// Insert new bytes instead of overwriting
+    insert_mode: bool,
+    /// Offset of the bytes in the whole value (for a page of a partial value)
+    base_offset: usize,
+    /// Size of the whole value, only for a page of a partial value
+    total: Option<usize>,
+    /// Offset to go to once its page is loaded
+    pending_offset: Option<usize>,
+    /// Whether the bytes can only be viewed
+    readonly: bool,
+    panel: HexPanel,
+    offset_state: Entity<InputState>,
+    search_state: Entity<InputState>,
+    /// Range of the last search match
+    matched: Option<Range<usize>>,
+    /// Whether the last search found nothing
+    search_missed: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl EventEmitter<HexEditorEvent> for ZedisHexEditor {}
+
+impl Focusable for ZedisHexEditor {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl ZedisHexEditor {
+    pub fn new(bytes: Bytes, width: usize, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let offset_state =
+            cx.new(|cx| InputState::new(window, cx).placeholder(i18n_editor(cx, "hex_offset_placeholder")));
+        let search_state =
+            cx.new(|cx| InputState::new(window, cx).placeholder(i18n_editor(cx, "hex_search_placeholder")));
+        let subscriptions = vec![
+            cx.subscribe_in(&offset_state, window, |this, state, event, window, cx| {
+                if let InputEvent::PressEnter { .. } = event
+                    && let Some(offset) = parse_offset(&state.read(cx).value())
+                {
+                    this.go_to_offset(offset, window, cx);
+                }
+            }),
+            cx.subscribe_in(&search_state, window, |this, _state, event, window, cx| {
+                if let InputEvent::PressEnter { .. } = event {
+                    this.search_next(window, cx);
+                }
+            }),
+        ];
+        Self {
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            bytes: bytes.to_vec(),
+            original: bytes,
+            width: width.max(1),
+            cursor: 0,
+            low_nibble: false,
+            insert_mode: false,
+            base_offset: 0,
+            total: None,
+            pending_offset: None,
+            readonly: false,
+            panel: HexPanel::Hex,
+            offset_state,
+            search_state,
+            matched: None,
+            search_missed: false,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Replace the edited bytes with a newly loaded value
+    pub fn set_bytes(&mut self, bytes: Bytes, width: usize, cx: &mut Context<Self>) {
+        self.bytes = bytes.to_vec();
+        self.original = bytes;
+        self.width = width.max(1);
+        self.cursor = self.cursor.min(self.bytes.len());
+        self.low_nibble = false;
+        self.matched = None;
+        cx.notify();
+    }
+
+    /// Show the bytes as a page starting at the offset of the whole value,
+    /// `total` is the size of the whole value if only a page is loaded
+    pub fn set_page(&mut self, base_offset: usize, total: Option<usize>, readonly: bool, cx: &mut Context<Self>) {
+        self.base_offset = base_offset;
+        self.total = total;
+        self.readonly = readonly;
+        // go to the offset whose page has just been loaded
+        if let Some(offset) = self.pending_offset.take()
+            && offset >= base_offset
+            && offset < base_offset + self.bytes.len()
+        {
+            self.set_cursor(offset - base_offset);
+        }
+        cx.notify();
+    }
+
+    /// The edited bytes
+    pub fn bytes(&self) -> Bytes {
+        Bytes::from(self.bytes.clone())
+    }
+
+    /// Whether the bytes differ from the loaded value
+    pub fn is_modified(&self) -> bool {
+        self.bytes != self.original.as_ref()
+    }
+
+    fn rows_count(&self) -> usize {
+        // one more slot for the append position
+        self.bytes.len() / self.width + 1
+    }
+
+    fn set_cursor(&mut self, cursor: usize) {
+        self.cursor = cursor.min(self.bytes.len());
+        self.low_nibble = false;
+        self.scroll_handle
+            .scroll_to_item(self.cursor / self.width, ScrollStrategy::Center);
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        self.set_cursor(self.cursor.saturating_add_signed(delta));
+    }
+
+    fn changed(&mut self, cx: &mut Context<Self>) {
+        self.matched = None;
+        self.scroll_handle
+            .scroll_to_item(self.cursor / self.width, ScrollStrategy::Center);
+        cx.emit(HexEditorEvent::Change);
+    }
+
+    /// Write a byte at the cursor and move to the next one
+    fn write_byte(&mut self, value: u8, cx: &mut Context<Self>) {
+        if self.insert_mode || self.cursor >= self.bytes.len() {
+            self.bytes.insert(self.cursor, value);
+        } else {
+            self.bytes[self.cursor] = value;
+        }
+        self.cursor += 1;
+        self.low_nibble = false;
+        self.changed(cx);
+    }
+
+    /// Write a hex digit, the high nibble first, then the low nibble
+    fn write_nibble(&mut self, value: u8, cx: &mut Context<Self>) {
+        if self.low_nibble && self.cursor < self.bytes.len() {
+            let byte = &mut self.bytes[self.cursor];
+            *byte = (*byte & 0xf0) | value;
+            self.cursor += 1;
+            self.low_nibble = false;
+        } else {
+            if self.insert_mode || self.cursor >= self.bytes.len() {
+                self.bytes.insert(self.cursor, value << 4);
+            } else {
+                let byte = &mut self.bytes[self.cursor];
+                *byte = (*byte & 0x0f) | (value << 4);
+            }
+            self.low_nibble = true;
+        }
+        self.changed(cx);
+    }
+
+    fn delete_backward(&mut self, cx: &mut Context<Self>) {
+        if self.cursor == 0 {
+            return;
+        }
+        self.cursor -= 1;
+        self.bytes.remove(self.cursor);
+        self.low_nibble = false;
+        self.changed(cx);
+    }
+
+    fn delete_forward(&mut self, cx: &mut Context<Self>) {
+        if self.cursor >= self.bytes.len() {
+            return;
+        }
+        self.bytes.remove(self.cursor);
+        self.low_nibble = false;
+        self.changed(cx);
+    }
+
+    /// Go to the offset of the whole value, loading its page first if it is not loaded
+    fn go_to_offset(&mut self, offset: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let loaded = self.base_offset..self.base_offset + self.bytes.len();
+        if let Some(total) = self.total
+            && !loaded.contains(&offset)
+            && offset < total
+        {
+            self.pending_offset = Some(offset);
+            cx.emit(HexEditorEvent::LoadPage(offset / PARTIAL_PAGE_SIZE * PARTIAL_PAGE_SIZE));
+            return;
+        }
+        self.set_cursor(offset.saturating_sub(self.base_offset));
+        window.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    /// Select the next match of the search pattern after the cursor
+    fn search_next(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(pattern) = parse_byte_pattern(&self.search_state.read(cx).value()) else {
+            return;
+        };
+        let from = if self.matched.is_some() {
+            self.cursor + 1
+        } else {
+            self.cursor
+        };
+        match find_pattern(&self.bytes, &pattern, from) {
+            Some(index) => {
+                self.set_cursor(index);
+                self.matched = Some(index..index + pattern.len());
+                self.search_missed = false;
+                window.focus(&self.focus_handle);
+            }
+            None => {
+                self.matched = None;
+                self.search_missed = true;
+            }
+        }
+        cx.notify();
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, _window: &mut Window, cx: &mut Context<Self>) {
+        let keystroke = &event.keystroke;
+        let modifiers = keystroke.modifiers;
+        // leave shortcuts (e.g. cmd-s to save) to the parent views
+        if modifiers.control || modifiers.platform || modifiers.alt || modifiers.function {
+            return;
+        }
+        let width = self.width as isize;
+        let key = keystroke.key.as_str();
+        if self.readonly && !matches!(key, "left" | "right" | "up" | "down" | "home" | "end" | "tab") {
+            return;
+        }
+        match key {
+            "left" => self.move_cursor(-1),
+            "right" => self.move_cursor(1),
+            "up" => self.move_cursor(-width),
+            "down" => self.move_cursor(width),
+            "home" => self.set_cursor(self.cursor - self.cursor % self.width),
+            "end" => self.set_cursor(self.cursor - self.cursor % self.width + self.width - 1),
+            "backspace" => self.delete_backward(cx),
+            "delete" => self.delete_forward(cx),
+            "insert" => self.insert_mode = !self.insert_mode,
+            "tab" => {
+                self.panel = match self.panel {
+                    HexPanel::Hex => HexPanel::Ascii,
+                    HexPanel::Ascii => HexPanel::Hex,
+                };
+                self.low_nibble = false;
+            }
+            _ => {
+                let Some(ch) = keystroke.key_char.as_deref().and_then(|s| {
+                    let mut chars = s.chars();
+                    chars.next().filter(|_| chars.next().is_none())
+                }) else {
+                    return;
+                };
+                match self.panel {
+                    HexPanel::Hex => {
+                        let Some(value) = ch.to_digit(16) else {
+                            return;
+                        };
+                        self.write_nibble(value as u8, cx);
+                    }
+                    HexPanel::Ascii => {
+                        if !ch.is_ascii() || ch.is_ascii_control() {
+                            return;
+                        }
+                        self.write_byte(ch as u8, cx);
+                    }
+                }
+            }
+        }
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let mode = if self.insert_mode {
+            i18n_editor(cx, "hex_insert_mode")
+        } else {
+            i18n_editor(cx, "hex_overwrite_mode")
+        };
+        let mut status = format!(
+            "{}: 0x{:X} ({}) / {}",
+            i18n_editor(cx, "hex_offset"),
+            self.base_offset + self.cursor,
+            self.base_offset + self.cursor,
+            self.base_offset + self.bytes.len()
+        );
+        if self.search_missed {
+            status = format!("{status}, {}", i18n_editor(cx, "hex_pattern_not_found"));
+        }
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                Button::new("zedis-hex-editor-mode")
+                    .outline()
+                    .small()
+                    .label(mode)
+                    .disabled(self.readonly)
+                    .tooltip(i18n_editor(cx, "hex_mode_tooltip"))
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.insert_mode = !this.insert_mode;
+                        cx.notify();
+                    })),
+            )
+            .child(Input::new(&self.offset_state).small().w(px(OFFSET_INPUT_WIDTH)))
+            .child(
+                Input::new(&self.search_state)
+                    .small()
+                    .w(px(SEARCH_INPUT_WIDTH))
+                    .cleanable(true),
+            )
+            .child(
+                Button::new("zedis-hex-editor-search")
+                    .outline()
+                    .small()
+                    .label(i18n_editor(cx, "hex_find_next"))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.search_next(window, cx);
+                    })),
+            )
+            .child(Label::new(status).text_sm().text_color(cx.theme().muted_foreground))
+    }
+
+    fn render_rows(&mut self, range: Range<usize>, window: &Window, cx: &mut Context<Self>) -> Vec<gpui::AnyElement> {
+        let address_color = if cx.theme().is_dark() {
+            hsla(0.108, 0.66, 0.69, 1.0)
+        } else {
+            hsla(0.0892, 0.9462, 0.4373, 1.0)
+        };
+        let cursor_bg = cx.theme().selection;
+        let cursor_border = cx.theme().primary;
+        let matched_bg = cx.theme().warning.opacity(0.3);
+        let muted = cx.theme().muted_foreground;
+        let focused = self.focus_handle.is_focused(window);
+        let len = self.bytes.len();
+
+        range
+            .map(|row| {
+                let start = row * self.width;
+                let end = (start + self.width).min(len);
+                // the row holding the append position shows one more empty cell
+                let cells_end = if end == len { len + 1 } else { end };
+
+                let cell = |index: usize, panel: HexPanel, text: SharedString, width: f32| {
+                    let is_cursor = index == self.cursor;
+                    let is_matched = self.matched.as_ref().is_some_and(|range| range.contains(&index));
+                    div()
+                        .id(("zedis-hex-editor-cell", index * 2 + panel as usize))
+                        .w(px(width))
+                        .flex_none()
+                        .text_center()
+                        .when(is_matched, |this| this.bg(matched_bg))
+                        .when(is_cursor && focused && self.panel == panel, |this| this.bg(cursor_bg))
+                        .when(is_cursor && self.panel != panel, |this| {
+                            this.border_b_1().border_color(cursor_border)
+                        })
+                        .child(text)
+                        .on_mouse_down(
+                            MouseButton::Left,
+                            cx.listener(move |this, _, window, cx| {
+                                this.panel = panel;
+                                this.set_cursor(index);
+                                window.focus(&this.focus_handle);
+                                cx.notify();
+                            }),
+                        )
+                };
+
+                let hex_cells = (start..cells_end).map(|index| {
+                    let text: SharedString = match self.bytes.get(index) {
+                        // show the typed high nibble of the byte being edited
+                        Some(byte) if index == self.cursor && self.low_nibble => format!("{:X}_", byte >> 4).into(),
+                        Some(byte) => format!("{byte:02X}").into(),
+                        None => "".into(),
+                    };
+                    cell(index, HexPanel::Hex, text, HEX_CELL_WIDTH)
+                });
+                let ascii_cells = (start..cells_end).map(|index| {
+                    let text: SharedString = match self.bytes.get(index) {
+                        Some(byte) if byte.is_ascii_graphic() || *byte == b' ' => (*byte as char).to_string().into(),
+                        Some(_) => ".".into(),
+                        None => "".into(),
+                    };
+                    cell(index, HexPanel::Ascii, text, ASCII_CELL_WIDTH)
+                });
+
+                h_flex()
+                    .px_2()
+                    .child(
+                        Label::new(format!("{:08X}", self.base_offset + start))
+                            .text_color(address_color)
+                            .mr_4(),
+                    )
+                    .child(
+                        h_flex()
+                            .w(px(HEX_CELL_WIDTH * self.width as f32))
+                            .text_color(muted)
+                            .children(hex_cells)
+                            .mr_6(),
+                    )
+                    .child(h_flex().children(ascii_cells))
+                    .into_any_element()
+            })
+            .collect()
+    }
+}
+
+impl Render for ZedisHexEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let list = uniform_list(
+            "zedis-hex-editor-rows",
+            self.rows_count(),
+            cx.processor(|this, range, window, cx| this.render_rows(range, window, cx)),
+        )
+        .track_scroll(self.scroll_handle.clone())
+        .flex_1()
+        .w_full();
+
+        v_flex().size_full().child(self.render_toolbar(cx)).child(
+            v_flex()
+                .id("zedis-hex-editor")
+                .track_focus(&self.focus_handle)
+                .on_key_down(cx.listener(Self::on_key_down))
+                .flex_1()
+                .w_full()
+                .font_family(get_font_family())
+                .child(list),
+        )
+    }
+}
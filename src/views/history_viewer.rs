@@ -0,0 +1,176 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    assets::CustomIconName,
+    states::{HistoryRecord, ServerEvent, ZedisServerState, i18n_common, i18n_history},
+};
+use chrono::{Local, TimeZone};
+use gpui::{Entity, SharedString, Subscription, Window, div, prelude::*, px, uniform_list};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    v_flex,
+};
+use std::sync::Arc;
+use tracing::info;
+
+// Constants for UI layout
+const HISTORY_VIEWER_HEIGHT: f32 = 480.0;
+const TIME_COLUMN_WIDTH: f32 = 150.0;
+const USER_COLUMN_WIDTH: f32 = 90.0;
+const DB_COLUMN_WIDTH: f32 = 40.0;
+const OPERATION_COLUMN_WIDTH: f32 = 150.0;
+const ACTION_COLUMN_WIDTH: f32 = 40.0;
+
+fn format_time(ts: i64) -> SharedString {
+    Local
+        .timestamp_opt(ts, 0)
+        .single()
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+        .into()
+}
+
+/// Edit history of the current server with one-click revert.
+pub struct ZedisHistoryViewer {
+    server_state: Entity<ZedisServerState>,
+    records: Arc<Vec<HistoryRecord>>,
+    loading: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisHistoryViewer {
+    pub fn new(server_state: Entity<ZedisServerState>, _window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let subscriptions = vec![cx.subscribe(&server_state, |this, server_state, event, cx| {
+            if let ServerEvent::HistoryUpdated = event {
+                this.loading = false;
+                this.records = server_state.read(cx).history();
+                cx.notify();
+            }
+        })];
+        server_state.update(cx, |state, cx| {
+            state.load_history(cx);
+        });
+        info!("Creating new history viewer view");
+
+        Self {
+            records: server_state.read(cx).history(),
+            server_state,
+            loading: true,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn revert(&mut self, id: SharedString, cx: &mut Context<Self>) {
+        self.loading = true;
+        self.server_state.update(cx, move |state, cx| {
+            state.revert_history(id, cx);
+        });
+        cx.notify();
+    }
+}
+
+impl Render for ZedisHistoryViewer {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let text_color = cx.theme().muted_foreground;
+        let border = cx.theme().border;
+        let records = self.records.clone();
+        // a browsed replica is read-only
+        let loading = self.loading || self.server_state.read(cx).is_readonly();
+        let revert_tooltip = i18n_history(cx, "revert_tooltip");
+        let not_revertible_tooltip = i18n_history(cx, "not_revertible_tooltip");
+        let view = cx.entity();
+
+        let header = h_flex()
+            .w_full()
+            .px_2()
+            .gap_2()
+            .text_sm()
+            .text_color(text_color)
+            .child(div().w(px(TIME_COLUMN_WIDTH)).child(i18n_history(cx, "time")))
+            .child(div().w(px(USER_COLUMN_WIDTH)).child(i18n_history(cx, "user")))
+            .child(div().w(px(DB_COLUMN_WIDTH)).child("DB"))
+            .child(div().flex_1().child(i18n_common(cx, "key")))
+            .child(div().w(px(OPERATION_COLUMN_WIDTH)).child(i18n_history(cx, "operation")))
+            .child(div().w(px(ACTION_COLUMN_WIDTH)));
+
+        let list = uniform_list("history-viewer-records", records.len(), move |range, _window, _cx| {
+            range
+                .filter_map(|index| records.get(index).map(|record| (index, record)))
+                .map(|(index, record)| {
+                    let revertible = record.revertible();
+                    let id: SharedString = record.id.clone().into();
+                    let view = view.clone();
+                    h_flex()
+                        .w_full()
+                        .px_2()
+                        .gap_2()
+                        .text_sm()
+                        .border_b_1()
+                        .border_color(border)
+                        .child(Label::new(format_time(record.created_at)).w(px(TIME_COLUMN_WIDTH)))
+                        .child(Label::new(record.user.clone()).w(px(USER_COLUMN_WIDTH)).text_ellipsis())
+                        .child(Label::new(record.db.to_string()).w(px(DB_COLUMN_WIDTH)))
+                        .child(
+                            div()
+                                .flex_1()
+                                .w_0()
+                                .overflow_hidden()
+                                .child(Label::new(record.key.clone()).whitespace_nowrap().text_ellipsis()),
+                        )
+                        .child(Label::new(record.operation.clone()).w(px(OPERATION_COLUMN_WIDTH)))
+                        .child(
+                            Button::new(("history-viewer-revert", index))
+                                .ghost()
+                                .xsmall()
+                                .w(px(ACTION_COLUMN_WIDTH))
+                                .icon(CustomIconName::RotateCw)
+                                .disabled(loading || !revertible)
+                                .tooltip(if revertible {
+                                    revert_tooltip.clone()
+                                } else {
+                                    not_revertible_tooltip.clone()
+                                })
+                                .on_click(move |_, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |this, cx| {
+                                        this.revert(id.clone(), cx);
+                                    });
+                                }),
+                        )
+                })
+                .collect()
+        })
+        .flex_1()
+        .w_full();
+
+        v_flex()
+            .w_full()
+            .h(px(HISTORY_VIEWER_HEIGHT))
+            .gap_1()
+            .child(header)
+            .when(self.records.is_empty(), |this| {
+                this.child(
+                    Label::new(i18n_history(cx, "empty"))
+                        .p_2()
+                        .text_sm()
+                        .text_color(text_color),
+                )
+            })
+            .child(list)
+    }
+}
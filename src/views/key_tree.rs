@@ -15,25 +15,25 @@
 use crate::{
     assets::CustomIconName,
     components::{FormDialog, FormField, open_add_form_dialog},
-    connection::QueryMode,
-    helpers::{EditorAction, validate_long_string, validate_ttl},
-    states::{KeyType, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_key_tree},
+    connection::{KeyFilter, QueryMode},
+    helpers::{EditorAction, KeyTree, parse_byte_size, validate_byte_size, validate_long_string, validate_ttl},
+    states::{KeyChange, KeyType, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_key_tree},
 };
-use ahash::{AHashMap, AHashSet};
+use ahash::AHashSet;
 use gpui::{
     App, AppContext, Corner, Entity, Hsla, ScrollStrategy, SharedString, Subscription, Window, div, prelude::*, px,
 };
 use gpui_component::IndexPath;
 use gpui_component::list::{List, ListDelegate, ListEvent, ListItem, ListState};
 use gpui_component::{
-    ActiveTheme, Disableable, Icon, IconName, StyledExt, WindowExt,
+    ActiveTheme, Disableable, Icon, IconName, Selectable, StyledExt, WindowExt,
     button::{Button, ButtonVariants, DropdownButton},
     h_flex,
     input::{Input, InputEvent, InputState},
     label::Label,
     v_flex,
 };
-use std::rc::Rc;
+use std::{rc::Rc, time::Duration};
 use tracing::info;
 
 // Constants for tree layout and behavior
@@ -55,7 +55,7 @@ struct KeyTreeState {
     key_tree_id: SharedString,
     /// Whether the tree is empty (no keys found)
     is_empty: bool,
-    /// Current query mode (All/Prefix/Exact)
+    /// Current query mode (All/Prefix/Exact/Suffix/Regex)
     query_mode: QueryMode,
     /// Error message to display if key loading fails
     error: Option<SharedString>,
@@ -63,114 +63,24 @@ struct KeyTreeState {
     expanded_items: AHashSet<SharedString>,
     /// Index path to scroll to when the tree is updated
     scroll_to_index: Option<IndexPath>,
+    /// Generation of the keys the tree was built from
+    keys_generation: usize,
+    /// Number of key changes of the server state merged into the tree
+    synced_changes: usize,
+    /// Whether the tree is being rebuilt in the background
+    rebuilding: bool,
 }
 
-#[derive(Default, Debug, Clone)]
-struct KeyTreeItem {
-    id: SharedString,
-    label: SharedString,
-    depth: usize,
-    key_type: KeyType,
-    expanded: bool,
-    children_count: usize,
-    is_folder: bool,
-}
-
-fn new_key_tree_items(
-    mut keys: Vec<(SharedString, KeyType)>,
-    keyword: SharedString,
-    expand_all: bool,
-    expanded_items: AHashSet<SharedString>,
-    separator: &str,
-    max_key_tree_depth: usize,
-) -> Vec<KeyTreeItem> {
-    keys.sort_unstable_by_key(|(k, _)| k.clone());
-    let expanded_items_set = expanded_items.iter().map(|s| s.as_str()).collect::<AHashSet<&str>>();
-    let mut items: AHashMap<SharedString, KeyTreeItem> = AHashMap::with_capacity(100);
-
-    for (key, key_type) in keys {
-        if !keyword.is_empty() && !key.contains(keyword.as_str()) {
-            continue;
-        }
-        // no colon in the key, it's a simple key
-        if !key.contains(separator) {
-            items.insert(
-                key.clone(),
-                KeyTreeItem {
-                    id: key.clone(),
-                    label: key.clone(),
-                    key_type,
-                    ..Default::default()
-                },
-            );
-            continue;
-        }
-
-        let mut dir = String::with_capacity(50);
-        let mut key_tree_item: Option<KeyTreeItem> = None;
-        // max levels of depth
-        for (index, k) in key.splitn(max_key_tree_depth, separator).enumerate() {
-            // if key_tre_item is not None, it means we are in a folder
-            // because it's not the last part of the key
-            if let Some(key_tree_item) = key_tree_item.take() {
-                let entry = items.entry(key_tree_item.id.clone()).or_insert_with(|| key_tree_item);
-                entry.is_folder = true;
-                entry.children_count += 1;
-            }
-
-            let expanded = expand_all || index == 0 || expanded_items_set.contains(dir.as_str());
-            if !expanded {
-                break;
-            }
-            let name: SharedString = k.to_string().into();
-            if index != 0 {
-                dir.push_str(separator);
-            };
-            dir.push_str(k);
-
-            key_tree_item = Some(KeyTreeItem {
-                id: dir.clone().into(),
-                label: name.clone(),
-                key_type,
-                depth: index,
-                expanded,
-                ..Default::default()
-            });
-        }
-        if let Some(key_tree_item) = key_tree_item.take() {
-            items.insert(key_tree_item.id.clone(), key_tree_item);
-        }
-    }
-
-    let mut children_map: AHashMap<String, Vec<KeyTreeItem>> = AHashMap::new();
-
-    let mut result = Vec::with_capacity(items.len());
-
-    for item in items.into_values() {
-        let size = item.id.len() - item.label.len();
-        let parent_id = if size == 0 { "" } else { &item.id[..(size - 1)] };
-        children_map.entry(parent_id.to_string()).or_default().push(item);
-    }
-
-    fn build_sorted_list(parent_id: &str, map: &mut AHashMap<String, Vec<KeyTreeItem>>, result: &mut Vec<KeyTreeItem>) {
-        if let Some(mut children) = map.remove(parent_id) {
-            children.sort_unstable_by(|a, b| b.is_folder.cmp(&a.is_folder).then_with(|| a.label.cmp(&b.label)));
-
-            for child in children {
-                let child_id = child.id.to_string();
-                result.push(child);
-                build_sorted_list(&child_id, map, result);
-            }
-        }
-    }
-
-    build_sorted_list("", &mut children_map, &mut result);
-
-    result
+/// Whether the key passes the keyword of the tree.
+/// Regex keywords are matched by the scan, keys of expanded folders are scanned by prefix.
+fn is_key_visible(key: &str, keyword: &str, query_mode: QueryMode) -> bool {
+    keyword.is_empty() || query_mode == QueryMode::Regex || key.contains(keyword)
 }
 
 struct KeyTreeDelegate {
-    items: Vec<KeyTreeItem>,
+    tree: KeyTree,
+    /// Used to get the key types of the rendered rows
+    server_state: Entity<ZedisServerState>,
     selected_index: Option<IndexPath>,
 }
 
@@ -203,7 +113,7 @@ impl ListDelegate for KeyTreeDelegate {
     type Item = ListItem;
 
     fn items_count(&self, _section: usize, _cx: &App) -> usize {
-        self.items.len()
+        self.tree.rows()
     }
 
     fn render_item(
@@ -213,10 +123,16 @@ impl ListDelegate for KeyTreeDelegate {
         cx: &mut Context<ListState<Self>>,
     ) -> Option<Self::Item> {
         let yellow = cx.theme().colors.yellow;
-        let entry = self.items.get(ix.row)?;
+        let entry = self.tree.row(ix.row)?;
         let icon = if !entry.is_folder {
             // Key item: Show type badge (String, List, etc.)
-            self.render_key_type_badge(&entry.key_type).into_any_element()
+            let key_type = self
+                .server_state
+                .read(cx)
+                .key_type(&entry.id)
+                .copied()
+                .unwrap_or_default();
+            self.render_key_type_badge(&key_type).into_any_element()
         } else if entry.expanded {
             // Expanded folder: Show open folder icon
             Icon::new(IconName::FolderOpen).text_color(yellow).into_any_element()
@@ -256,7 +172,7 @@ impl ListDelegate for KeyTreeDelegate {
                     h_flex()
                         .gap_2()
                         .child(icon)
-                        .child(div().flex_1().text_ellipsis().child(entry.label.clone()))
+                        .child(div().flex_1().text_ellipsis().child(entry.label))
                         .child(count_label),
                 ),
         )
@@ -310,7 +226,12 @@ impl ZedisKeyTree {
         subscriptions.push(cx.subscribe(&server_state, |this, _server_state, event, cx| {
             if let ServerEvent::KeyCollapseAll = event {
                 this.state.expanded_items.clear();
-                this.update_key_tree(true, cx);
+                this.key_tree_list_state.update(cx, |state, cx| {
+                    let tree = &mut state.delegate_mut().tree;
+                    tree.collapse_all();
+                    tree.prepare();
+                    cx.notify();
+                });
             }
         }));
 
@@ -329,6 +250,20 @@ impl ZedisKeyTree {
         let server_id = server_state_value.server_id().to_string();
         let query_mode = server_state_value.query_mode();
 
+        // Show the keyword of a saved search opened from the sidebar
+        subscriptions.push(
+            cx.subscribe_in(&server_state, window, |view, server_state, event, window, cx| {
+                if let ServerEvent::SearchApplied(keyword) = event {
+                    // keep the input from being cleared as a server switch on the next render
+                    view.state.server_id = server_state.read(cx).server_id().to_string().into();
+                    view.state.keyword = keyword.clone();
+                    view.keyword_state.update(cx, |state, cx| {
+                        state.set_value(keyword.clone(), window, cx);
+                    });
+                }
+            }),
+        );
+
         // Subscribe to search input events (Enter key triggers filter)
         subscriptions.push(cx.subscribe_in(&keyword_state, window, |view, _, event, _, cx| {
             if let InputEvent::PressEnter { .. } = &event {
@@ -338,8 +273,10 @@ impl ZedisKeyTree {
 
         info!(server_id, "Creating new key tree view");
 
+        let app_state = cx.global::<ZedisGlobalStore>().value(cx);
         let delegate = KeyTreeDelegate {
-            items: Vec::new(),
+            tree: KeyTree::new(app_state.key_separator(), app_state.max_key_tree_depth()),
+            server_state: server_state.clone(),
             selected_index: None,
         };
         let key_tree_list_state = cx.new(|cx| ListState::new(delegate, window, cx));
@@ -379,9 +316,10 @@ impl ZedisKeyTree {
 
     /// Update the key tree structure when server state changes
     ///
-    /// Rebuilds the tree only if the tree ID has changed (indicating new keys loaded).
-    /// Preserves expanded folder state across rebuilds. Auto-expands all folders
-    /// if the total key count is below the threshold.
+    /// Keys added or removed since the last update are merged into the tree, it is
+    /// only rebuilt (in the background) when keys were cleared, or the
+    /// separator settings changed. Auto-expands all folders if the total key
+    /// count is below the threshold.
     fn update_key_tree(&mut self, force_update: bool, cx: &mut Context<Self>) {
         let server_state = self.server_state.read(cx);
         let key_tree_id = server_state.key_tree_id();
@@ -394,53 +332,122 @@ impl ZedisKeyTree {
 
         self.state.query_mode = server_state.query_mode();
 
-        // Skip rebuild if tree ID hasn't changed (same keys)
+        // Skip update if tree ID hasn't changed (same keys)
         if !force_update && self.state.key_tree_id == key_tree_id {
             return;
         }
         self.state.key_tree_id = key_tree_id.to_string().into();
+        // the keys added meanwhile are merged when the rebuild is done
+        if self.state.rebuilding {
+            return;
+        }
+
+        let keys_generation = server_state.keys_generation();
+        let app_state = cx.global::<ZedisGlobalStore>().value(cx);
+        let separator = app_state.key_separator().to_string();
+        let max_key_tree_depth = app_state.max_key_tree_depth();
+        let tree = &self.key_tree_list_state.read(cx).delegate().tree;
+        let settings_changed = tree.separator() != separator || tree.max_depth() != max_key_tree_depth.max(1);
+        if force_update || settings_changed || keys_generation != self.state.keys_generation {
+            self.rebuild_key_tree(keys_generation, separator, max_key_tree_depth, cx);
+        } else {
+            self.merge_key_changes(cx);
+        }
+    }
+
+    /// Merge the keys added to and removed from the server state since the last update
+    fn merge_key_changes(&mut self, cx: &mut Context<Self>) {
+        let server_state = self.server_state.read(cx);
+        let key_changes = server_state.key_changes();
+        let keyword = self.state.keyword.as_str();
+        let query_mode = self.state.query_mode;
+        let changes: Vec<KeyChange> = key_changes
+            .get(self.state.synced_changes..)
+            .unwrap_or_default()
+            .iter()
+            .filter(|change| match change {
+                KeyChange::Added(key) => is_key_visible(key, keyword, query_mode),
+                KeyChange::Removed(_) => true,
+            })
+            .cloned()
+            .collect();
+        self.state.synced_changes = key_changes.len();
+        let expand_all = server_state.scan_count() < AUTO_EXPAND_THRESHOLD;
+
+        self.key_tree_list_state.update(cx, |state, cx| {
+            let tree = &mut state.delegate_mut().tree;
+            if !changes.is_empty() || expand_all {
+                for change in changes {
+                    match change {
+                        KeyChange::Added(key) => tree.extend([key]),
+                        KeyChange::Removed(key) => {
+                            tree.remove(&key);
+                        }
+                    }
+                }
+                if expand_all {
+                    tree.expand_all();
+                }
+                tree.prepare();
+            }
+            // key types may have changed
+            cx.notify();
+        });
+    }
 
-        // Auto-expand all folders if key count is small
+    /// Rebuild the tree from all keys of the server state in the background,
+    /// restoring the expanded folders
+    fn rebuild_key_tree(
+        &mut self,
+        keys_generation: usize,
+        separator: String,
+        max_key_tree_depth: usize,
+        cx: &mut Context<Self>,
+    ) {
+        let server_state = self.server_state.read(cx);
+        let keys: Vec<SharedString> = server_state.keys().keys().cloned().collect();
+        let synced_changes = server_state.key_changes().len();
         let expand_all = server_state.scan_count() < AUTO_EXPAND_THRESHOLD;
-        let keys_snapshot: Vec<(SharedString, KeyType)> =
-            server_state.keys().iter().map(|(k, v)| (k.clone(), *v)).collect();
         let expanded_items = self.state.expanded_items.clone();
-
-        let view_handle = cx.entity().downgrade();
         let keyword = self.state.keyword.clone();
+        let query_mode = self.state.query_mode;
+        self.state.keys_generation = keys_generation;
+        self.state.synced_changes = synced_changes;
+        self.state.rebuilding = true;
+
+        cx.spawn(async move |handle, cx| {
+            let task = cx.background_spawn(async move {
+                let start = std::time::Instant::now();
+                let mut tree = KeyTree::new(&separator, max_key_tree_depth);
+                tree.extend(keys.into_iter().filter(|key| is_key_visible(key, &keyword, query_mode)));
+                if expand_all {
+                    tree.expand_all();
+                } else {
+                    for id in expanded_items.iter() {
+                        tree.expand(id);
+                    }
+                }
+                tree.prepare();
+                tracing::debug!("Key tree build time: {:?}", start.elapsed());
+                tree
+            });
 
-        self.key_tree_list_state.update(cx, move |_state, cx| {
-            let app_state = cx.global::<ZedisGlobalStore>().value(cx);
-            let separator = app_state.key_separator().to_string();
-            let max_key_tree_depth = app_state.max_key_tree_depth();
-            cx.spawn(async move |handle, cx| {
-                let task = cx.background_spawn(async move {
-                    let start = std::time::Instant::now();
-                    let items = new_key_tree_items(
-                        keys_snapshot,
-                        keyword,
-                        expand_all,
-                        expanded_items,
-                        &separator,
-                        max_key_tree_depth,
-                    );
-                    tracing::debug!("Key tree build time: {:?}", start.elapsed());
-                    items
-                });
-
-                let result = task.await;
-                if result.is_empty() {
-                    let _ = view_handle.update(cx, |view: &mut ZedisKeyTree, cx| {
-                        view.reset(cx);
-                    });
+            let tree = task.await;
+            handle.update(cx, |this, cx| {
+                this.state.rebuilding = false;
+                if tree.is_empty() {
+                    this.reset(cx);
                 }
-                handle.update(cx, |this, cx| {
-                    this.delegate_mut().items = result;
+                this.key_tree_list_state.update(cx, |state, cx| {
+                    state.delegate_mut().tree = tree;
                     cx.notify();
-                })
+                });
+                // merge the keys added during the rebuild
+                this.state.key_tree_id = SharedString::default();
+                this.update_key_tree(false, cx);
             })
-            .detach();
-        });
+        })
+        .detach();
     }
 
     /// Handle filter/search action when user submits keyword
@@ -499,6 +506,103 @@ impl ZedisKeyTree {
         );
     }
 
+    /// Opens the dialog of the key filters (type, TTL, memory usage)
+    fn handle_key_filter(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let key_types = ["", "string", "list", "set", "zset", "hash", "stream"];
+        let filter = self.server_state.read(cx).key_filter().clone();
+        let type_index = key_types
+            .iter()
+            .position(|t| filter.key_type.as_deref() == Some(*t))
+            .unwrap_or_default();
+        let ttl_index = if filter.no_ttl {
+            1
+        } else if filter.expires_within.is_some() {
+            2
+        } else {
+            0
+        };
+        let expires_within = filter
+            .expires_within
+            .map(|secs| humantime::format_duration(Duration::from_secs(secs)).to_string())
+            .unwrap_or_default();
+        let min_memory = filter.min_memory.map(|bytes| bytes.to_string()).unwrap_or_default();
+
+        let type_options = vec![
+            i18n_key_tree(cx, "filter_type_all"),
+            "String".into(),
+            "List".into(),
+            "Set".into(),
+            "Zset".into(),
+            "Hash".into(),
+            "Stream".into(),
+        ];
+        let ttl_options = vec![
+            i18n_key_tree(cx, "filter_ttl_any"),
+            i18n_key_tree(cx, "filter_no_ttl"),
+            i18n_key_tree(cx, "filter_expires_within"),
+        ];
+        let fields = vec![
+            FormField::new(i18n_key_tree(cx, "filter_type"))
+                .with_options(type_options)
+                .with_value(type_index.to_string().into()),
+            FormField::new(i18n_common(cx, "ttl"))
+                .with_options(ttl_options)
+                .with_value(ttl_index.to_string().into()),
+            FormField::new(i18n_key_tree(cx, "filter_expires_within"))
+                .with_placeholder(i18n_key_tree(cx, "filter_expires_within_placeholder"))
+                .with_value(expires_within.into())
+                .with_validate(validate_ttl),
+            FormField::new(i18n_key_tree(cx, "filter_min_memory"))
+                .with_placeholder(i18n_key_tree(cx, "filter_min_memory_placeholder"))
+                .with_value(min_memory.into())
+                .with_validate(validate_byte_size),
+        ];
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            if values.len() != 4 {
+                return false;
+            }
+            let type_index = values[0].parse::<usize>().unwrap_or(0);
+            let ttl_index = values[1].parse::<usize>().unwrap_or(0);
+            let expires_within = if values[2].is_empty() {
+                None
+            } else if let Ok(secs) = values[2].parse::<u64>() {
+                Some(secs)
+            } else {
+                humantime::parse_duration(&values[2]).ok().map(|d| d.as_secs())
+            };
+            let expires_within = (ttl_index == 2).then_some(expires_within).flatten();
+            if ttl_index == 2 && expires_within.is_none() {
+                return false;
+            }
+            let filter = KeyFilter {
+                key_type: key_types
+                    .get(type_index)
+                    .filter(|t| !t.is_empty())
+                    .map(|t| t.to_string()),
+                no_ttl: ttl_index == 1,
+                expires_within,
+                min_memory: parse_byte_size(&values[3]),
+            };
+
+            server_state.update(cx, |this, cx| {
+                this.set_key_filter(filter, cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_key_tree(cx, "filter_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
     fn get_tree_status_view(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
         let server_state = self.server_state.read(cx);
         // if scanning, return None
@@ -548,14 +652,10 @@ impl ZedisKeyTree {
     }
 
     fn select_item_by_index(&mut self, ix: &IndexPath, toggle: bool, cx: &mut Context<Self>) {
-        let Some((id, is_folder)) = self.key_tree_list_state.update(cx, |state, _cx| {
-            let item = state.delegate().items.get(ix.row)?;
-            let id = item.id.clone();
-            let is_folder = item.is_folder;
-            Some((id, is_folder))
-        }) else {
+        let Some(row) = self.key_tree_list_state.read(cx).delegate().tree.row(ix.row) else {
             return;
         };
+        let (id, is_folder) = (row.id, row.is_folder);
         self.select_item(id, is_folder, toggle, cx);
     }
 
@@ -567,14 +667,25 @@ impl ZedisKeyTree {
                 }
                 // User clicked an expanded folder -> collapse it
                 self.state.expanded_items.remove(&item_id);
+                self.key_tree_list_state.update(cx, |state, cx| {
+                    let tree = &mut state.delegate_mut().tree;
+                    tree.collapse(&item_id);
+                    tree.prepare();
+                    cx.notify();
+                });
             } else {
                 // User clicked a collapsed folder -> expand it and load data
                 self.state.expanded_items.insert(item_id.clone());
+                self.key_tree_list_state.update(cx, |state, cx| {
+                    let tree = &mut state.delegate_mut().tree;
+                    tree.expand(&item_id);
+                    tree.prepare();
+                    cx.notify();
+                });
                 self.server_state.update(cx, |state, cx| {
                     state.scan_prefix(format!("{}:", item_id.as_str()).into(), cx);
                 });
             }
-            self.update_key_tree(true, cx);
         } else {
             let is_selected = self.server_state.read(cx).key().as_ref() == Some(&item_id);
             // Select Key
@@ -607,13 +718,17 @@ impl ZedisKeyTree {
     /// Render the search/filter input bar with query mode selector
     ///
     /// Features:
-    /// - Query mode dropdown (All/Prefix/Exact) with visual indicators
+    /// - Query mode dropdown (All/Prefix/Exact/Suffix/Regex) with visual indicators
     /// - Search input field with placeholder
     /// - Search button (with loading state during scan)
     /// - Clearable input (X button appears when text entered)
+    /// - Key filter button (type, TTL, memory usage), highlighted when active
     fn render_keyword_input(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let server_state = self.server_state.read(cx);
         let scaning = server_state.scaning();
+        let has_key_filter = !server_state.key_filter().is_empty();
+        let readonly = server_state.is_readonly();
+        let search_saved = server_state.is_search_saved(&self.keyword_state.read(cx).value());
         let server_id = server_state.server_id();
         if server_id != self.state.server_id.as_str() {
             self.state.server_id = server_id.to_string().into();
@@ -628,6 +743,8 @@ impl ZedisKeyTree {
             QueryMode::All => Icon::new(IconName::Asterisk), // * for all keys
             QueryMode::Prefix => Icon::new(CustomIconName::ChevronUp), // ~ for prefix
             QueryMode::Exact => Icon::new(CustomIconName::Equal), // = for exact match
+            QueryMode::Suffix => Icon::new(IconName::ChevronDown), // $ for suffix
+            QueryMode::Regex => Icon::new(CustomIconName::Regex), // ~ for regex
         };
         let query_mode_dropdown = DropdownButton::new("dropdown")
             .button(Button::new("key-tree-query-mode-btn").ghost().px_2().icon(icon))
@@ -639,10 +756,16 @@ impl ZedisKeyTree {
                 .menu_element_with_check(query_mode == QueryMode::Prefix, Box::new(QueryMode::Prefix), |_, cx| {
                     Label::new(i18n_key_tree(cx, "query_mode_prefix")).ml_2().text_xs()
                 })
+                .menu_element_with_check(query_mode == QueryMode::Exact, Box::new(QueryMode::Exact), |_, cx| {
+                    Label::new(i18n_key_tree(cx, "query_mode_exact")).ml_2().text_xs()
+                })
+                .menu_element_with_check(query_mode == QueryMode::Suffix, Box::new(QueryMode::Suffix), |_, cx| {
+                    Label::new(i18n_key_tree(cx, "query_mode_suffix")).ml_2().text_xs()
+                })
                 .menu_element_with_check(
-                    query_mode == QueryMode::Exact,
-                    Box::new(QueryMode::Exact),
-                    |_, cx| Label::new(i18n_key_tree(cx, "query_mode_exact")).ml_2().text_xs(),
+                    query_mode == QueryMode::Regex,
+                    Box::new(QueryMode::Regex),
+                    |_, cx| Label::new(i18n_key_tree(cx, "query_mode_regex")).ml_2().text_xs(),
                 )
             });
         // Search button (shows loading spinner during scan)
@@ -669,9 +792,39 @@ impl ZedisKeyTree {
             .border_b_1()
             .border_color(cx.theme().border)
             .child(keyword_input)
+            .child(
+                Button::new("key-tree-save-search-btn")
+                    .outline()
+                    .mr_2()
+                    .icon(CustomIconName::Bookmark)
+                    .tooltip(if search_saved {
+                        i18n_key_tree(cx, "remove_saved_search_tooltip")
+                    } else {
+                        i18n_key_tree(cx, "save_search_tooltip")
+                    })
+                    .selected(search_saved)
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        let keyword = this.keyword_state.read(cx).value();
+                        this.server_state.update(cx, |state, cx| {
+                            state.toggle_saved_search(keyword, cx);
+                        });
+                    })),
+            )
+            .child(
+                Button::new("key-tree-filter-btn")
+                    .outline()
+                    .mr_2()
+                    .icon(CustomIconName::Funnel)
+                    .tooltip(i18n_key_tree(cx, "filter_tooltip"))
+                    .selected(has_key_filter)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.handle_key_filter(window, cx);
+                    })),
+            )
             .child(
                 Button::new("key-tree-add-btn")
                     .outline()
+                    .disabled(readonly)
                     .icon(CustomIconName::FilePlusCorner)
                     .on_click(cx.listener(|this, _, window, cx| {
                         this.handle_add_key(window, cx);
@@ -17,7 +17,7 @@ use crate::{
     components::{INDEX_COLUMN_NAME, ZedisKvDelegate, ZedisKvFetcher},
     states::{ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_kv_table},
 };
-use gpui::{Entity, SharedString, Subscription, TextAlign, Window, div, prelude::*, px};
+use gpui::{App, Entity, SharedString, Subscription, TextAlign, Window, div, prelude::*, px};
 use gpui_component::{
     ActiveTheme, Disableable, Icon, IconName, PixelsExt,
     button::{Button, ButtonVariants},
@@ -27,6 +27,7 @@ use gpui_component::{
     table::{Table, TableState},
     v_flex,
 };
+use std::sync::Arc;
 use tracing::info;
 
 /// Width of the keyword search input field in pixels
@@ -89,6 +90,8 @@ pub struct ZedisKvTable<T: ZedisKvFetcher> {
     loading: bool,
     /// Flag indicating the selected key has changed (triggers input reset)
     key_changed: bool,
+    /// Whether the values can not be written (a replica is browsed)
+    readonly: bool,
     /// Event subscriptions for server state and input changes
     _subscriptions: Vec<Subscription>,
 }
@@ -184,12 +187,16 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
                 | ServerEvent::ValueAdded(_)
                 | ServerEvent::ValueUpdated(_) => {
                     let fetcher = Self::new_values(server_state.clone(), cx);
+                    let readonly = server_state.read(cx).is_readonly();
+                    this.readonly = readonly;
                     this.loading = false;
                     this.done = fetcher.is_done();
                     this.items_count = fetcher.rows_count();
                     this.total_count = fetcher.count();
                     this.table_state.update(cx, |state, _| {
-                        state.delegate_mut().set_fetcher(fetcher);
+                        let delegate = state.delegate_mut();
+                        delegate.set_fetcher(fetcher);
+                        delegate.set_readonly(readonly);
                     });
                 }
                 // Clear search when key selection changes
@@ -215,11 +222,13 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
         }));
 
         // Initialize table data and state
+        let readonly = server_state.read(cx).is_readonly();
         let fetcher = Self::new_values(server_state, cx);
         let done = fetcher.is_done();
         let items_count = fetcher.rows_count();
         let total_count = fetcher.count();
-        let delegate = ZedisKvDelegate::new(Self::new_columns(columns, window, cx), fetcher, window, cx);
+        let mut delegate = ZedisKvDelegate::new(Self::new_columns(columns, window, cx), fetcher, window, cx);
+        delegate.set_readonly(readonly);
         let table_state = cx.new(|cx| TableState::new(delegate, window, cx));
 
         info!("Creating new key value table view");
@@ -232,10 +241,24 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
             done,
             loading: false,
             key_changed: false,
+            readonly,
             _subscriptions: subscriptions,
         }
     }
 
+    /// Returns the current data fetcher of the table.
+    pub fn fetcher(&self, cx: &App) -> Arc<T> {
+        self.table_state.read(cx).delegate().fetcher()
+    }
+
+    /// Scrolls to a row and selects it.
+    pub fn scroll_to_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        self.table_state.update(cx, |state, cx| {
+            state.scroll_to_row(row_ix, cx);
+            state.set_selected_row(row_ix, cx);
+        });
+    }
+
     /// Triggers a filter operation using the current keyword from the input field.
     fn handle_filter(&mut self, cx: &mut Context<Self>) {
         let keyword = self.keyword_state.read(cx).value();
@@ -307,6 +330,7 @@ impl<T: ZedisKvFetcher> Render for ZedisKvTable<T> {
                                 Button::new("add-value-btn")
                                     .icon(CustomIconName::FilePlusCorner)
                                     .tooltip(i18n_kv_table(cx, "add_value_tooltip"))
+                                    .disabled(self.readonly)
                                     .on_click(handle_add_value),
                             )
                             .child(
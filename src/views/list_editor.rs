@@ -15,21 +15,34 @@
 use crate::{
     components::{FormDialog, FormField, ZedisKvFetcher, open_add_form_dialog},
     helpers::fast_contains_ignore_case,
-    states::{RedisValue, ZedisServerState, i18n_common, i18n_list_editor},
+    states::{
+        MAX_REORDER_LIST_SIZE, RedisElement, RedisValue, ServerEvent, ZedisServerState, i18n_common, i18n_list_editor,
+    },
     views::{KvTableColumn, ZedisKvTable},
 };
-use gpui::{App, Entity, SharedString, Window, div, prelude::*};
-use gpui_component::WindowExt;
+use gpui::{App, Entity, SharedString, Subscription, Window, div, prelude::*, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    v_flex,
+};
 use std::rc::Rc;
 use tracing::info;
 
+/// Width of the go to index input field in pixels
+const INDEX_INPUT_WIDTH: f32 = 120.0;
+/// Width of the find element input field in pixels
+const FIND_INPUT_WIDTH: f32 = 200.0;
+
 /// Manages Redis List values and their display state.
 ///
 /// Handles both filtered and unfiltered views of list data, maintaining
 /// a mapping between visible items and their original indices when filtering.
 struct ZedisListValues {
     /// Currently visible items (filtered subset or all items)
-    visible_items: Vec<SharedString>,
+    visible_items: Vec<RedisElement>,
     /// Maps visible item indices to original list indices (Some when filtered, None otherwise)
     visible_item_indexes: Option<Vec<usize>>,
     /// The underlying Redis value data
@@ -69,7 +82,7 @@ impl ZedisListValues {
         let mut visible_items = Vec::with_capacity(capacity);
 
         for (index, item) in value.values.iter().enumerate() {
-            if fast_contains_ignore_case(item.as_str(), &keyword) {
+            if fast_contains_ignore_case(item.text.as_str(), &keyword) {
                 visible_item_indexes.push(index);
                 visible_items.push(item.clone());
             }
@@ -78,21 +91,41 @@ impl ZedisListValues {
         self.visible_items = visible_items;
         self.visible_item_indexes = Some(visible_item_indexes);
     }
+
+    /// Returns the visible row of the loaded element at `index`, `None` if it is filtered out.
+    fn visible_row(&self, index: usize) -> Option<usize> {
+        match &self.visible_item_indexes {
+            Some(indexes) => indexes.iter().position(|item| *item == index),
+            None => Some(index),
+        }
+    }
 }
 
-impl ZedisKvFetcher for ZedisListValues {
-    /// Retrieves the value at the specified row index.
+impl ZedisListValues {
+    /// Returns the element at the specified row index.
     ///
     /// Returns from the filtered visible items when a keyword filter is active,
     /// otherwise returns directly from the original list values.
-    fn get(&self, row_ix: usize, _col_ix: usize) -> Option<SharedString> {
+    fn element(&self, row_ix: usize) -> Option<&RedisElement> {
         let value = self.value.list_value()?;
         if value.keyword.is_some() {
-            self.visible_items.get(row_ix).cloned()
+            self.visible_items.get(row_ix)
         } else {
-            value.values.get(row_ix).cloned()
+            value.values.get(row_ix)
         }
     }
+}
+
+impl ZedisKvFetcher for ZedisListValues {
+    /// Retrieves the editable text of the value at the specified row index.
+    fn get(&self, row_ix: usize, _col_ix: usize) -> Option<SharedString> {
+        self.element(row_ix).map(|item| item.text.clone())
+    }
+
+    /// Retrieves the value at the specified row index.
+    fn get_element(&self, row_ix: usize, _col_ix: usize) -> Option<RedisElement> {
+        self.element(row_ix).cloned()
+    }
 
     /// Indicates that list values support in-place updates (LSET command).
     fn can_update(&self) -> bool {
@@ -117,7 +150,32 @@ impl ZedisKvFetcher for ZedisListValues {
 
     /// Checks whether all list items have been loaded from Redis.
     fn is_done(&self) -> bool {
-        self.value.list_value().is_some_and(|v| v.values.len() == v.size)
+        self.value
+            .list_value()
+            .is_some_and(|v| v.offset + v.values.len() == v.size)
+    }
+
+    /// Returns the list index of the first loaded item, row numbers are positions when filtered.
+    fn row_offset(&self) -> usize {
+        if self.visible_item_indexes.is_some() {
+            return 0;
+        }
+        self.value.list_value().map_or(0, |v| v.offset)
+    }
+
+    /// Small, fully loaded and unfiltered lists can be reordered by dragging.
+    fn can_reorder(&self) -> bool {
+        self.visible_item_indexes.is_none()
+            && self.value.list_value().is_some_and(|v| {
+                v.offset == 0 && v.values.len() == v.size && v.size > 1 && v.size <= MAX_REORDER_LIST_SIZE
+            })
+    }
+
+    /// Moves an item to another position (LSET in one MULTI/EXEC transaction).
+    fn reorder(&self, from: usize, to: usize, cx: &mut App) {
+        self.server_state.update(cx, |state, cx| {
+            state.reorder_list_value(from, to, cx);
+        });
     }
 
     /// Triggers loading more list items from Redis (pagination).
@@ -197,7 +255,14 @@ impl ZedisKvFetcher for ZedisListValues {
     ///
     /// When a filter is active, maps the visible index to the real index
     /// in the underlying list. Requires the original value for optimistic updates.
-    fn handle_update_value(&self, index: usize, values: Vec<SharedString>, _window: &mut Window, cx: &mut App) {
+    fn handle_update_value(
+        &self,
+        index: usize,
+        values: Vec<SharedString>,
+        _original_values: Vec<SharedString>,
+        _window: &mut Window,
+        cx: &mut App,
+    ) {
         let Some(new_value) = values.first() else {
             return;
         };
@@ -242,14 +307,25 @@ impl ZedisKvFetcher for ZedisListValues {
 /// supporting operations like LRANGE, LSET, LREM, LPUSH, and RPUSH.
 ///
 /// Features:
-/// - Paginated loading of large lists
-/// - Keyword-based filtering
+/// - Paginated loading of large lists, with go to index for very long lists
+/// - Keyword-based filtering and LPOS search
 /// - In-place value editing
-/// - Add values to either end of the list
+/// - Add values to either end of the list, or insert before/after an index
+/// - Move elements to another list and trim the list to a range
+/// - Drag reorder of small lists
 /// - Delete individual items
 pub struct ZedisListEditor {
     /// Table component managing the list data display and interactions
     table_state: Entity<ZedisKvTable<ZedisListValues>>,
+    /// Reference to server state for executing Redis operations
+    server_state: Entity<ZedisServerState>,
+    /// Input for the index to go to
+    index_state: Entity<InputState>,
+    /// Input for the element searched with LPOS
+    find_state: Entity<InputState>,
+    /// Last searched element and its occurrence, the next search finds the following one
+    find_rank: Option<(SharedString, usize)>,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl ZedisListEditor {
@@ -257,18 +333,280 @@ impl ZedisListEditor {
     ///
     /// Initializes a single-column table to display list values.
     pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut subscriptions = Vec::new();
         let table_state = cx.new(|cx| {
-            ZedisKvTable::<ZedisListValues>::new(vec![KvTableColumn::new("Value", None)], server_state, window, cx)
+            ZedisKvTable::<ZedisListValues>::new(
+                vec![KvTableColumn::new("Value", None)],
+                server_state.clone(),
+                window,
+                cx,
+            )
         });
+        let index_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .clean_on_escape()
+                .placeholder(i18n_list_editor(cx, "go_to_index_placeholder"))
+        });
+        let find_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .clean_on_escape()
+                .placeholder(i18n_list_editor(cx, "find_value_placeholder"))
+        });
+
+        subscriptions.push(cx.subscribe(&index_state, |this, _, event, cx| {
+            if matches!(event, InputEvent::PressEnter { .. }) {
+                this.handle_go_to_index(cx);
+            }
+        }));
+        subscriptions.push(cx.subscribe(&find_state, |this, _, event, cx| {
+            if matches!(event, InputEvent::PressEnter { .. }) {
+                this.handle_find_value(cx);
+            }
+        }));
+        // Scroll to the located element, the table has been updated by the same events
+        subscriptions.push(cx.subscribe(&server_state, |this, _, event, cx| match event {
+            ServerEvent::ValueIndexFound(_, index) => {
+                let index = *index;
+                this.table_state.update(cx, |table, cx| {
+                    if let Some(row_ix) = table.fetcher(cx).visible_row(index) {
+                        table.scroll_to_row(row_ix, cx);
+                    }
+                });
+            }
+            ServerEvent::KeySelected(_) => {
+                this.find_rank = None;
+            }
+            _ => {}
+        }));
 
         info!("Creating new list editor view");
 
-        Self { table_state }
+        Self {
+            table_state,
+            server_state,
+            index_state,
+            find_state,
+            find_rank: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Jumps to the index of the input, loading it when needed.
+    fn handle_go_to_index(&mut self, cx: &mut Context<Self>) {
+        let Ok(index) = self.index_state.read(cx).value().trim().parse::<usize>() else {
+            return;
+        };
+        self.server_state.update(cx, |state, cx| {
+            state.go_to_list_index(index, cx);
+        });
+    }
+
+    /// Finds the next occurrence of the input element with LPOS.
+    fn handle_find_value(&mut self, cx: &mut Context<Self>) {
+        let element = self.find_state.read(cx).value();
+        if element.is_empty() {
+            return;
+        }
+        let rank = match &self.find_rank {
+            Some((last, rank)) if *last == element => rank + 1,
+            _ => 1,
+        };
+        self.find_rank = Some((element.clone(), rank));
+        self.server_state.update(cx, |state, cx| {
+            state.find_list_value(element, rank, cx);
+        });
+    }
+
+    /// Opens a dialog to insert a value before or after an index (LINSERT).
+    fn handle_insert_value(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            if values.len() != 3 {
+                return false;
+            }
+            let Ok(index) = values[1].trim().parse::<usize>() else {
+                return false;
+            };
+            let before = values[0] == "0";
+            server_state.update(cx, |state, cx| {
+                state.insert_list_value(index, before, values[2].clone(), cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+
+        let fields = vec![
+            FormField::new(i18n_list_editor(cx, "positon")).with_options(vec!["BEFORE".into(), "AFTER".into()]),
+            FormField::new(i18n_list_editor(cx, "index"))
+                .with_placeholder(i18n_list_editor(cx, "index_placeholder"))
+                .with_validate(|value| value.trim().parse::<usize>().is_ok())
+                .with_focus(),
+            FormField::new(i18n_common(cx, "value")).with_placeholder(i18n_common(cx, "value_placeholder")),
+        ];
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_list_editor(cx, "insert_value_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    /// Opens a dialog to move the head or tail element to another list (LMOVE).
+    fn handle_move_value(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            if values.len() != 3 || values[0].is_empty() {
+                return false;
+            }
+            let from_left = values[1] == "0";
+            let to_left = values[2] == "0";
+            server_state.update(cx, |state, cx| {
+                state.move_list_value(values[0].clone(), from_left, to_left, cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+
+        let fields = vec![
+            FormField::new(i18n_list_editor(cx, "destination"))
+                .with_placeholder(i18n_list_editor(cx, "destination_placeholder"))
+                .with_focus(),
+            FormField::new(i18n_list_editor(cx, "move_from")).with_options(vec!["LEFT".into(), "RIGHT".into()]),
+            FormField::new(i18n_list_editor(cx, "move_to")).with_options(vec!["LEFT".into(), "RIGHT".into()]),
+        ];
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_list_editor(cx, "move_value_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    /// Opens a dialog to trim the list to a range (LTRIM).
+    fn handle_trim(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            if values.len() != 2 {
+                return false;
+            }
+            let (Ok(start), Ok(stop)) = (values[0].trim().parse::<i64>(), values[1].trim().parse::<i64>()) else {
+                return false;
+            };
+            server_state.update(cx, |state, cx| {
+                state.trim_list_value(start, stop, cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+
+        let is_index = |value: &str| value.trim().parse::<i64>().is_ok();
+        let fields = vec![
+            FormField::new(i18n_list_editor(cx, "trim_start"))
+                .with_placeholder("0".into())
+                .with_validate(is_index)
+                .with_focus(),
+            FormField::new(i18n_list_editor(cx, "trim_stop"))
+                .with_placeholder("-1".into())
+                .with_validate(is_index),
+        ];
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_list_editor(cx, "trim_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    /// Renders the toolbar: go to index, LPOS search, insert, move and trim.
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let readonly = self.server_state.read(cx).is_readonly();
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(Input::new(&self.index_state).small().w(px(INDEX_INPUT_WIDTH)))
+            .child(
+                Button::new("zedis-list-go-to-index")
+                    .outline()
+                    .small()
+                    .label(i18n_list_editor(cx, "go_to_index"))
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.handle_go_to_index(cx);
+                    })),
+            )
+            .child(Input::new(&self.find_state).small().w(px(FIND_INPUT_WIDTH)))
+            .child(
+                Button::new("zedis-list-find-value")
+                    .outline()
+                    .small()
+                    .label(i18n_list_editor(cx, "find_value"))
+                    .tooltip(i18n_list_editor(cx, "find_value_tooltip"))
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.handle_find_value(cx);
+                    })),
+            )
+            .child(h_flex().flex_1())
+            .child(
+                Button::new("zedis-list-insert-value")
+                    .outline()
+                    .small()
+                    .label(i18n_list_editor(cx, "insert_value"))
+                    .tooltip(i18n_list_editor(cx, "insert_value_tooltip"))
+                    .disabled(readonly)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.handle_insert_value(window, cx);
+                    })),
+            )
+            .child(
+                Button::new("zedis-list-move-value")
+                    .outline()
+                    .small()
+                    .label(i18n_list_editor(cx, "move_value"))
+                    .tooltip(i18n_list_editor(cx, "move_value_tooltip"))
+                    .disabled(readonly)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.handle_move_value(window, cx);
+                    })),
+            )
+            .child(
+                Button::new("zedis-list-trim")
+                    .outline()
+                    .small()
+                    .danger()
+                    .label(i18n_list_editor(cx, "trim"))
+                    .tooltip(i18n_list_editor(cx, "trim_tooltip"))
+                    .disabled(readonly)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.handle_trim(window, cx);
+                    })),
+            )
     }
 }
 
 impl Render for ZedisListEditor {
-    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
-        div().size_full().child(self.table_state.clone()).into_any_element()
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .child(self.render_toolbar(cx))
+            .child(
+                div()
+                    .flex_1()
+                    .w_full()
+                    .overflow_hidden()
+                    .child(self.table_state.clone()),
+            )
+            .into_any_element()
     }
 }
@@ -0,0 +1,697 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    assets::CustomIconName,
+    helpers::get_font_family,
+    states::{
+        FunctionLibrary, SavedScript, ScriptMode, ScriptOutput, ServerEvent, ZedisServerState, i18n_common,
+        i18n_script_editor,
+    },
+};
+use chrono::{Local, TimeZone};
+use gpui::{App, Entity, ScrollHandle, SharedString, Subscription, Window, div, prelude::*, px};
+use gpui_component::{
+    ActiveTheme, Disableable, IconName, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    highlighter::{LanguageConfig, LanguageRegistry},
+    input::{Input, InputEvent, InputState, TabSize},
+    label::Label,
+    menu::{DropdownMenu, PopupMenuItem},
+    v_flex,
+};
+use std::sync::{Arc, Once};
+use tracing::info;
+
+// Constants for UI layout
+const SCRIPT_LANGUAGE: &str = "lua";
+const DEFAULT_TAB_SIZE: usize = 2;
+const NAME_INPUT_WIDTH: f32 = 200.0;
+const FUNCTIONS_PANEL_WIDTH: f32 = 320.0;
+const CONSOLE_HEIGHT: f32 = 220.0;
+const SAVED_SCRIPTS_MENU_MIN_WIDTH: f32 = 240.0;
+const SAVED_SCRIPTS_MENU_MAX_HEIGHT: f32 = 400.0;
+
+/// Register the Lua grammar, gpui-component does not ship one.
+fn register_lua_language() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| {
+        let config = LanguageConfig::new(
+            SCRIPT_LANGUAGE,
+            tree_sitter_lua::LANGUAGE.into(),
+            vec![],
+            tree_sitter_lua::HIGHLIGHTS_QUERY,
+            tree_sitter_lua::INJECTIONS_QUERY,
+            tree_sitter_lua::LOCALS_QUERY,
+        );
+        LanguageRegistry::singleton().register(SCRIPT_LANGUAGE, &config);
+    });
+}
+
+fn format_time(ts: i64) -> String {
+    Local
+        .timestamp_opt(ts, 0)
+        .single()
+        .map(|t| t.format("%H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+/// Non-empty lines of the input, one key or argument per line.
+fn input_lines(state: &Entity<InputState>, cx: &App) -> Vec<SharedString> {
+    state
+        .read(cx)
+        .value()
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| SharedString::from(line.to_string()))
+        .collect()
+}
+
+/// Lua script and Redis Functions workbench of the current server.
+///
+/// Features:
+/// - Script editor (Lua, without highlighting) with KEYS and ARGV, one per line
+/// - EVAL, EVALSHA and EVAL_RO, plus SCRIPT LOAD / EXISTS / FLUSH
+/// - Function libraries with FUNCTION LOAD REPLACE, FCALL / FCALL_RO and FUNCTION DELETE
+/// - Console with the replies formatted like redis-cli
+/// - Scripts saved locally per server
+pub struct ZedisScriptEditor {
+    server_state: Entity<ZedisServerState>,
+    editor: Entity<InputState>,
+    name_state: Entity<InputState>,
+    keys_state: Entity<InputState>,
+    args_state: Entity<InputState>,
+    outputs: Arc<Vec<ScriptOutput>>,
+    libraries: Arc<Vec<FunctionLibrary>>,
+    saved_scripts: Arc<Vec<SavedScript>>,
+    console_scroll: ScrollHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisScriptEditor {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        register_lua_language();
+        let mut subscriptions = vec![
+            cx.subscribe(&server_state, |this, server_state, event, cx| match event {
+                ServerEvent::ScriptOutputUpdated => {
+                    this.outputs = server_state.read(cx).script_outputs();
+                    this.console_scroll.scroll_to_bottom();
+                    cx.notify();
+                }
+                ServerEvent::FunctionLibrariesUpdated => {
+                    this.libraries = server_state.read(cx).function_libraries();
+                    cx.notify();
+                }
+                ServerEvent::SavedScriptsUpdated => {
+                    this.saved_scripts = server_state.read(cx).saved_scripts();
+                    cx.notify();
+                }
+                ServerEvent::ServerSelected(..) => {
+                    this.reload(cx);
+                }
+                _ => {}
+            }),
+        ];
+
+        let editor = cx.new(|cx| {
+            InputState::new(window, cx)
+                .code_editor(SCRIPT_LANGUAGE)
+                .line_number(true)
+                .indent_guides(true)
+                .tab_size(TabSize {
+                    tab_size: DEFAULT_TAB_SIZE,
+                    hard_tabs: false,
+                })
+                .searchable(true)
+                .placeholder("return redis.call('GET', KEYS[1])")
+        });
+        let name_state =
+            cx.new(|cx| InputState::new(window, cx).placeholder(i18n_script_editor(cx, "name_placeholder")));
+        let keys_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .auto_grow(2, 4)
+                .placeholder(i18n_script_editor(cx, "keys_placeholder"))
+        });
+        let args_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .auto_grow(2, 4)
+                .placeholder(i18n_script_editor(cx, "args_placeholder"))
+        });
+        // Save and delete buttons depend on the name
+        subscriptions.push(cx.subscribe(&name_state, |_this, _, event, cx| {
+            if let InputEvent::Change = event {
+                cx.notify();
+            }
+        }));
+        info!("Creating new script editor view");
+
+        let state = server_state.read(cx);
+        let mut this = Self {
+            outputs: state.script_outputs(),
+            libraries: state.function_libraries(),
+            saved_scripts: state.saved_scripts(),
+            server_state,
+            editor,
+            name_state,
+            keys_state,
+            args_state,
+            console_scroll: ScrollHandle::new(),
+            _subscriptions: subscriptions,
+        };
+        this.reload(cx);
+        this
+    }
+
+    /// Load the saved scripts and function libraries of the server
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        self.server_state.update(cx, |state, cx| {
+            if state.server_id().is_empty() {
+                return;
+            }
+            state.load_saved_scripts(cx);
+            state.load_function_libraries(cx);
+        });
+    }
+
+    fn script(&self, cx: &App) -> SharedString {
+        self.editor.read(cx).value()
+    }
+
+    fn run_script(&mut self, mode: ScriptMode, cx: &mut Context<Self>) {
+        let script = self.script(cx);
+        let keys = input_lines(&self.keys_state, cx);
+        let args = input_lines(&self.args_state, cx);
+        self.server_state.update(cx, move |state, cx| {
+            state.run_script(mode, script, keys, args, cx);
+        });
+    }
+
+    fn call_function(&mut self, function: SharedString, read_only: bool, cx: &mut Context<Self>) {
+        let keys = input_lines(&self.keys_state, cx);
+        let args = input_lines(&self.args_state, cx);
+        self.server_state.update(cx, move |state, cx| {
+            state.call_function(function, keys, args, read_only, cx);
+        });
+    }
+
+    fn save_script(&mut self, cx: &mut Context<Self>) {
+        let script = SavedScript {
+            name: self.name_state.read(cx).value().trim().to_string(),
+            script: self.script(cx).to_string(),
+            keys: input_lines(&self.keys_state, cx)
+                .iter()
+                .map(|item| item.to_string())
+                .collect(),
+            args: input_lines(&self.args_state, cx)
+                .iter()
+                .map(|item| item.to_string())
+                .collect(),
+            ..Default::default()
+        };
+        self.server_state.update(cx, move |state, cx| {
+            state.save_script(script, cx);
+        });
+    }
+
+    /// Fill the editor, name, KEYS and ARGV with the saved script
+    fn open_saved_script(&mut self, script: SavedScript, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor.update(cx, |state, cx| {
+            state.set_value(script.script, window, cx);
+        });
+        self.name_state.update(cx, |state, cx| {
+            state.set_value(script.name, window, cx);
+        });
+        self.keys_state.update(cx, |state, cx| {
+            state.set_value(script.keys.join("\n"), window, cx);
+        });
+        self.args_state.update(cx, |state, cx| {
+            state.set_value(script.args.join("\n"), window, cx);
+        });
+    }
+
+    /// Asks for confirmation, then deletes the saved script
+    fn handle_delete_script(&mut self, name: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let message = format!("{} {name}", i18n_script_editor(cx, "delete_script_prompt"));
+        window.open_dialog(cx, move |dialog, _, _cx| {
+            let server_state = server_state.clone();
+            let name = name.clone();
+            dialog.confirm().child(message.clone()).on_ok(move |_, window, cx| {
+                let name = name.clone();
+                server_state.update(cx, |state, cx| {
+                    state.delete_saved_script(name, cx);
+                });
+                window.close_dialog(cx);
+                true
+            })
+        });
+    }
+
+    /// Asks for confirmation, then deletes the function library
+    fn handle_delete_library(&mut self, library: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let message = format!("{} {library}", i18n_script_editor(cx, "delete_library_prompt"));
+        window.open_dialog(cx, move |dialog, _, _cx| {
+            let server_state = server_state.clone();
+            let library = library.clone();
+            dialog.confirm().child(message.clone()).on_ok(move |_, window, cx| {
+                let library = library.clone();
+                server_state.update(cx, |state, cx| {
+                    state.delete_function_library(library, cx);
+                });
+                window.close_dialog(cx);
+                true
+            })
+        });
+    }
+
+    /// Asks for confirmation, then removes all scripts from the script cache
+    fn handle_flush_scripts(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let message = i18n_script_editor(cx, "flush_scripts_prompt");
+        window.open_dialog(cx, move |dialog, _, _cx| {
+            let server_state = server_state.clone();
+            dialog.confirm().child(message.clone()).on_ok(move |_, window, cx| {
+                server_state.update(cx, |state, cx| {
+                    state.flush_scripts(cx);
+                });
+                window.close_dialog(cx);
+                true
+            })
+        });
+    }
+
+    /// Renders the saved scripts menu, name and the script commands.
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity();
+        let saved_scripts = self.saved_scripts.clone();
+        let name = self.name_state.read(cx).value().trim().to_string();
+        let is_saved = saved_scripts.iter().any(|item| item.name == name);
+        let empty_label = i18n_script_editor(cx, "saved_scripts_empty");
+
+        let saved_scripts_button = Button::new("script-editor-saved-scripts")
+            .outline()
+            .small()
+            .icon(IconName::FolderOpen)
+            .label(i18n_script_editor(cx, "saved_scripts"))
+            .dropdown_menu(move |menu, _window, _cx| {
+                let mut menu = menu
+                    .min_w(px(SAVED_SCRIPTS_MENU_MIN_WIDTH))
+                    .max_h(px(SAVED_SCRIPTS_MENU_MAX_HEIGHT))
+                    .scrollable(true);
+                if saved_scripts.is_empty() {
+                    return menu.label(empty_label.clone());
+                }
+                for script in saved_scripts.iter() {
+                    let view = view.clone();
+                    let script = script.clone();
+                    menu = menu.item(PopupMenuItem::new(script.name.clone()).icon(IconName::File).on_click(
+                        move |_, window, cx| {
+                            view.update(cx, |this, cx| {
+                                this.open_saved_script(script.clone(), window, cx);
+                            });
+                        },
+                    ));
+                }
+                menu
+            });
+
+        h_flex()
+            .w_full()
+            .gap_2()
+            .flex_wrap()
+            .child(saved_scripts_button)
+            .child(Input::new(&self.name_state).small().w(px(NAME_INPUT_WIDTH)))
+            .child(
+                Button::new("script-editor-save")
+                    .outline()
+                    .small()
+                    .label(i18n_common(cx, "save"))
+                    .tooltip(i18n_script_editor(cx, "save_tooltip"))
+                    .disabled(name.is_empty())
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.save_script(cx);
+                    })),
+            )
+            .child(
+                Button::new("script-editor-delete")
+                    .outline()
+                    .small()
+                    .icon(IconName::Delete)
+                    .tooltip(i18n_script_editor(cx, "delete_script_tooltip"))
+                    .disabled(!is_saved)
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.handle_delete_script(name.clone().into(), window, cx);
+                    })),
+            )
+            .child(div().flex_1())
+            .child(
+                Button::new("script-editor-eval")
+                    .primary()
+                    .small()
+                    .icon(CustomIconName::Play)
+                    .label("EVAL")
+                    .tooltip(i18n_script_editor(cx, "eval_tooltip"))
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.run_script(ScriptMode::Eval, cx);
+                    })),
+            )
+            .child(
+                Button::new("script-editor-evalsha")
+                    .outline()
+                    .small()
+                    .label("EVALSHA")
+                    .tooltip(i18n_script_editor(cx, "evalsha_tooltip"))
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.run_script(ScriptMode::EvalSha, cx);
+                    })),
+            )
+            .child(
+                Button::new("script-editor-eval-ro")
+                    .outline()
+                    .small()
+                    .label("EVAL_RO")
+                    .tooltip(i18n_script_editor(cx, "eval_ro_tooltip"))
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.run_script(ScriptMode::EvalRo, cx);
+                    })),
+            )
+            .child(
+                Button::new("script-editor-script-load")
+                    .outline()
+                    .small()
+                    .label("SCRIPT LOAD")
+                    .tooltip(i18n_script_editor(cx, "script_load_tooltip"))
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        let script = this.script(cx);
+                        this.server_state.update(cx, move |state, cx| {
+                            state.load_script(script, cx);
+                        });
+                    })),
+            )
+            .child(
+                Button::new("script-editor-script-exists")
+                    .outline()
+                    .small()
+                    .label("SCRIPT EXISTS")
+                    .tooltip(i18n_script_editor(cx, "script_exists_tooltip"))
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        let script = this.script(cx);
+                        this.server_state.update(cx, move |state, cx| {
+                            state.check_script_exists(script, cx);
+                        });
+                    })),
+            )
+            .child(
+                Button::new("script-editor-script-flush")
+                    .outline()
+                    .small()
+                    .label("SCRIPT FLUSH")
+                    .tooltip(i18n_script_editor(cx, "script_flush_tooltip"))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.handle_flush_scripts(window, cx);
+                    })),
+            )
+    }
+
+    /// Renders the function libraries with their functions.
+    fn render_functions(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let border = cx.theme().border;
+        let muted = cx.theme().muted_foreground;
+        let fcall_tooltip = i18n_script_editor(cx, "fcall_tooltip");
+        let fcall_ro_tooltip = i18n_script_editor(cx, "fcall_ro_tooltip");
+        let edit_tooltip = i18n_script_editor(cx, "edit_library_tooltip");
+        let delete_tooltip = i18n_script_editor(cx, "delete_library_tooltip");
+
+        let header = h_flex()
+            .w_full()
+            .gap_1()
+            .child(Label::new(i18n_script_editor(cx, "functions")).flex_1().text_sm())
+            .child(
+                Button::new("script-editor-functions-refresh")
+                    .ghost()
+                    .xsmall()
+                    .icon(CustomIconName::RotateCw)
+                    .tooltip(i18n_script_editor(cx, "refresh_functions_tooltip"))
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.load_function_libraries(cx);
+                        });
+                    })),
+            )
+            .child(
+                Button::new("script-editor-function-load")
+                    .outline()
+                    .xsmall()
+                    .label("FUNCTION LOAD")
+                    .tooltip(i18n_script_editor(cx, "function_load_tooltip"))
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        let code = this.script(cx);
+                        this.server_state.update(cx, move |state, cx| {
+                            state.load_function_library(code, cx);
+                        });
+                    })),
+            );
+
+        let mut list = v_flex()
+            .id("script-editor-functions")
+            .flex_1()
+            .w_full()
+            .gap_1()
+            .overflow_y_scroll();
+        if self.libraries.is_empty() {
+            list = list.child(
+                Label::new(i18n_script_editor(cx, "functions_empty"))
+                    .text_sm()
+                    .text_color(muted),
+            );
+        }
+        for (library_index, library) in self.libraries.iter().enumerate() {
+            let library_name: SharedString = library.name.clone().into();
+            let code: SharedString = library.code.clone().into();
+            let library_row = h_flex()
+                .w_full()
+                .gap_1()
+                .pt_1()
+                .border_t_1()
+                .border_color(border)
+                .child(Label::new(library_name.clone()).flex_1().text_sm().text_ellipsis())
+                .child(Label::new(library.engine.clone()).text_xs().text_color(muted))
+                .child(
+                    Button::new(("script-editor-library-edit", library_index))
+                        .ghost()
+                        .xsmall()
+                        .icon(CustomIconName::FilePenLine)
+                        .tooltip(edit_tooltip.clone())
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            let code = code.clone();
+                            this.editor.update(cx, |state, cx| {
+                                state.set_value(code, window, cx);
+                            });
+                        })),
+                )
+                .child(
+                    Button::new(("script-editor-library-delete", library_index))
+                        .ghost()
+                        .xsmall()
+                        .icon(IconName::Delete)
+                        .tooltip(delete_tooltip.clone())
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.handle_delete_library(library_name.clone(), window, cx);
+                        })),
+                );
+            list = list.child(library_row);
+            for (function_index, function) in library.functions.iter().enumerate() {
+                let id = library_index * 1000 + function_index;
+                let function_name: SharedString = function.name.clone().into();
+                let read_only = function.is_read_only();
+                let ro_function_name = function_name.clone();
+                let row = h_flex()
+                    .w_full()
+                    .gap_1()
+                    .pl_3()
+                    .child(
+                        Label::new(function_name.clone())
+                            .flex_1()
+                            .text_sm()
+                            .font_family(get_font_family())
+                            .text_ellipsis(),
+                    )
+                    .when(!function.flags.is_empty(), |this| {
+                        this.child(Label::new(function.flags.join(",")).text_xs().text_color(muted))
+                    })
+                    .child(
+                        Button::new(("script-editor-fcall", id))
+                            .ghost()
+                            .xsmall()
+                            .label("FCALL")
+                            .tooltip(fcall_tooltip.clone())
+                            .on_click(cx.listener(move |this, _, _window, cx| {
+                                this.call_function(function_name.clone(), false, cx);
+                            })),
+                    )
+                    .when(read_only, |this| {
+                        this.child(
+                            Button::new(("script-editor-fcall-ro", id))
+                                .ghost()
+                                .xsmall()
+                                .label("FCALL_RO")
+                                .tooltip(fcall_ro_tooltip.clone())
+                                .on_click(cx.listener(move |this, _, _window, cx| {
+                                    this.call_function(ro_function_name.clone(), true, cx);
+                                })),
+                        )
+                    });
+                list = list.child(row);
+            }
+        }
+
+        v_flex()
+            .w(px(FUNCTIONS_PANEL_WIDTH))
+            .h_full()
+            .gap_2()
+            .p_2()
+            .border_1()
+            .border_color(border)
+            .rounded_md()
+            .child(header)
+            .child(list)
+    }
+
+    /// Renders the console, oldest output first.
+    fn render_console(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let border = cx.theme().border;
+        let muted = cx.theme().muted_foreground;
+        let danger = cx.theme().danger;
+
+        let header = h_flex()
+            .w_full()
+            .child(Label::new(i18n_script_editor(cx, "console")).flex_1().text_sm())
+            .child(
+                Button::new("script-editor-console-clear")
+                    .ghost()
+                    .xsmall()
+                    .icon(IconName::Delete)
+                    .tooltip(i18n_script_editor(cx, "clear_console_tooltip"))
+                    .disabled(self.outputs.is_empty())
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.clear_script_outputs(cx);
+                        });
+                    })),
+            );
+
+        let outputs = self.outputs.iter().map(|output| {
+            v_flex()
+                .w_full()
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .text_color(muted)
+                        .child(format_time(output.created_at))
+                        .child(format!("> {}", output.command)),
+                )
+                .child(
+                    div()
+                        .whitespace_normal()
+                        .when(output.error, |this| this.text_color(danger))
+                        .children(output.output.lines().map(|line| div().child(line.to_string()))),
+                )
+        });
+
+        v_flex()
+            .w_full()
+            .h(px(CONSOLE_HEIGHT))
+            .gap_1()
+            .p_2()
+            .border_1()
+            .border_color(border)
+            .rounded_md()
+            .child(header)
+            .child(
+                v_flex()
+                    .id("script-editor-console")
+                    .flex_1()
+                    .w_full()
+                    .gap_1()
+                    .text_sm()
+                    .font_family(get_font_family())
+                    .overflow_y_scroll()
+                    .track_scroll(&self.console_scroll)
+                    .children(outputs),
+            )
+    }
+}
+
+impl Render for ZedisScriptEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let border = cx.theme().border;
+        let muted = cx.theme().muted_foreground;
+
+        let editor = div()
+            .flex_1()
+            .min_h_0()
+            .w_full()
+            .border_1()
+            .border_color(border)
+            .rounded_md()
+            .child(
+                Input::new(&self.editor)
+                    .bordered(false)
+                    .appearance(false)
+                    .p_0()
+                    .size_full()
+                    .font_family(get_font_family())
+                    .focus_bordered(false),
+            );
+        let params = h_flex()
+            .w_full()
+            .gap_2()
+            .items_start()
+            .child(
+                v_flex()
+                    .flex_1()
+                    .gap_1()
+                    .child(Label::new("KEYS").text_xs().text_color(muted))
+                    .child(Input::new(&self.keys_state).small()),
+            )
+            .child(
+                v_flex()
+                    .flex_1()
+                    .gap_1()
+                    .child(Label::new("ARGV").text_xs().text_color(muted))
+                    .child(Input::new(&self.args_state).small()),
+            );
+
+        v_flex()
+            .size_full()
+            .gap_2()
+            .p_2()
+            .child(self.render_toolbar(cx))
+            .child(
+                h_flex()
+                    .flex_1()
+                    .min_h_0()
+                    .w_full()
+                    .gap_2()
+                    .items_start()
+                    .child(v_flex().flex_1().h_full().gap_2().child(editor).child(params))
+                    .child(self.render_functions(cx)),
+            )
+            .child(self.render_console(cx))
+    }
+}
@@ -14,7 +14,7 @@
 
 use crate::assets::CustomIconName;
 use crate::components::Card;
-use crate::connection::RedisServer;
+use crate::connection::{ReadPreference, RedisServer};
 use crate::helpers::{is_windows, validate_common_string, validate_host, validate_long_string};
 use crate::states::{Route, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_servers};
 use gpui::{App, Entity, SharedString, Subscription, Window, div, prelude::*, px};
@@ -97,6 +97,8 @@ pub struct ZedisServers {
     client_key_state: Entity<InputState>,
     root_cert_state: Entity<InputState>,
     master_name_state: Entity<InputState>,
+    sentinel_username_state: Entity<InputState>,
+    sentinel_password_state: Entity<InputState>,
     description_state: Entity<InputState>,
 
     /// Flag indicating if we're adding a new server (vs editing existing)
@@ -104,6 +106,10 @@ pub struct ZedisServers {
 
     server_enable_tls: Rc<Cell<bool>>,
     server_insecure_tls: Rc<Cell<bool>>,
+    sentinel_enable_tls: Rc<Cell<bool>>,
+    sentinel_insecure_tls: Rc<Cell<bool>>,
+    /// Whether the keys and values are read from a replica
+    prefer_replica: Rc<Cell<bool>>,
 
     _subscriptions: Vec<Subscription>,
 }
@@ -165,6 +171,17 @@ impl ZedisServers {
                 .placeholder(i18n_servers(cx, "master_name_placeholder"))
                 .validate(|s, _cx| validate_common_string(s))
         });
+        let sentinel_username_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_servers(cx, "sentinel_username_placeholder"))
+                .validate(|s, _cx| validate_common_string(s))
+        });
+        let sentinel_password_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_servers(cx, "sentinel_password_placeholder"))
+                .validate(|s, _cx| validate_common_string(s))
+                .masked(true)
+        });
 
         let port_state_clone = port_state.clone();
         let username_state_clone = username_state.clone();
@@ -230,10 +247,15 @@ impl ZedisServers {
             client_key_state,
             root_cert_state,
             master_name_state,
+            sentinel_username_state,
+            sentinel_password_state,
             description_state,
             server_id: String::new(),
             server_enable_tls: Rc::new(Cell::new(false)),
             server_insecure_tls: Rc::new(Cell::new(false)),
+            sentinel_enable_tls: Rc::new(Cell::new(false)),
+            sentinel_insecure_tls: Rc::new(Cell::new(false)),
+            prefer_replica: Rc::new(Cell::new(false)),
             _subscriptions: subscriptions,
         }
     }
@@ -274,8 +296,21 @@ impl ZedisServers {
         self.root_cert_state.update(cx, |state, cx| {
             state.set_value(server.root_cert.clone().unwrap_or_default(), window, cx);
         });
+        self.master_name_state.update(cx, |state, cx| {
+            state.set_value(server.master_name.clone().unwrap_or_default(), window, cx);
+        });
+        self.sentinel_username_state.update(cx, |state, cx| {
+            state.set_value(server.sentinel_username.clone().unwrap_or_default(), window, cx);
+        });
+        self.sentinel_password_state.update(cx, |state, cx| {
+            state.set_value(server.sentinel_password.clone().unwrap_or_default(), window, cx);
+        });
         self.server_enable_tls.set(server.tls.unwrap_or(false));
         self.server_insecure_tls.set(server.insecure.unwrap_or(false));
+        self.sentinel_enable_tls.set(server.sentinel_tls.unwrap_or(false));
+        self.sentinel_insecure_tls
+            .set(server.sentinel_insecure.unwrap_or(false));
+        self.prefer_replica.set(server.prefers_replica());
     }
 
     /// Show confirmation dialog and remove server from configuration
@@ -322,6 +357,8 @@ impl ZedisServers {
         let username_state = self.username_state.clone();
         let password_state = self.password_state.clone();
         let master_name_state = self.master_name_state.clone();
+        let sentinel_username_state = self.sentinel_username_state.clone();
+        let sentinel_password_state = self.sentinel_password_state.clone();
         let description_state = self.description_state.clone();
         let client_cert_state = self.client_cert_state.clone();
         let client_key_state = self.client_key_state.clone();
@@ -332,6 +369,9 @@ impl ZedisServers {
         // Create shared state for TLS checkbox
         let server_enable_tls = self.server_enable_tls.clone();
         let server_insecure_tls = self.server_insecure_tls.clone();
+        let sentinel_enable_tls = self.sentinel_enable_tls.clone();
+        let sentinel_insecure_tls = self.sentinel_insecure_tls.clone();
+        let prefer_replica = self.prefer_replica.clone();
         let server_state_clone = server_state.clone();
         let name_state_clone = name_state.clone();
         let host_state_clone = host_state.clone();
@@ -339,6 +379,8 @@ impl ZedisServers {
         let username_state_clone = username_state.clone();
         let password_state_clone = password_state.clone();
         let master_name_state_clone = master_name_state.clone();
+        let sentinel_username_state_clone = sentinel_username_state.clone();
+        let sentinel_password_state_clone = sentinel_password_state.clone();
         let description_state_clone = description_state.clone();
         let client_cert_state_clone = client_cert_state.clone();
         let client_key_state_clone = client_key_state.clone();
@@ -346,6 +388,9 @@ impl ZedisServers {
         let server_id_clone = server_id.clone();
         let server_enable_tls_for_submit = self.server_enable_tls.clone();
         let server_insecure_tls_for_submit = self.server_insecure_tls.clone();
+        let sentinel_enable_tls_for_submit = self.sentinel_enable_tls.clone();
+        let sentinel_insecure_tls_for_submit = self.sentinel_insecure_tls.clone();
+        let prefer_replica_for_submit = self.prefer_replica.clone();
 
         let handle_submit = Rc::new(move |window: &mut Window, cx: &mut App| {
             let name = name_state_clone.read(cx).value();
@@ -408,6 +453,33 @@ impl ZedisServers {
             } else {
                 Some(master_name_val)
             };
+            let sentinel_username_val = sentinel_username_state_clone.read(cx).value();
+            let sentinel_username = if sentinel_username_val.is_empty() {
+                None
+            } else {
+                Some(sentinel_username_val)
+            };
+            let sentinel_password_val = sentinel_password_state_clone.read(cx).value();
+            let sentinel_password = if sentinel_password_val.is_empty() {
+                None
+            } else {
+                Some(sentinel_password_val)
+            };
+            let sentinel_tls = if sentinel_enable_tls_for_submit.get() {
+                Some(true)
+            } else {
+                None
+            };
+            let sentinel_insecure = if sentinel_tls.is_some() && sentinel_insecure_tls_for_submit.get() {
+                Some(true)
+            } else {
+                None
+            };
+            let read_preference = if prefer_replica_for_submit.get() {
+                Some(ReadPreference::PreferReplica)
+            } else {
+                None
+            };
             let desc_val = description_state_clone.read(cx).value();
             let description = if desc_val.is_empty() { None } else { Some(desc_val) };
 
@@ -429,6 +501,11 @@ impl ZedisServers {
                         client_cert: client_cert.map(|c| c.to_string()),
                         client_key: client_key.map(|k| k.to_string()),
                         root_cert: root_cert.map(|r| r.to_string()),
+                        sentinel_username: sentinel_username.map(|u| u.to_string()),
+                        sentinel_password: sentinel_password.map(|p| p.to_string()),
+                        sentinel_tls,
+                        sentinel_insecure,
+                        read_preference,
                         ..current_server
                     },
                     cx,
@@ -463,6 +540,12 @@ impl ZedisServers {
             let root_cert_label = i18n_common(cx, "root_cert");
             let description_label = i18n_common(cx, "description");
             let master_name_label = i18n_servers(cx, "master_name");
+            let sentinel_username_label = i18n_servers(cx, "sentinel_username");
+            let sentinel_password_label = i18n_servers(cx, "sentinel_password");
+            let sentinel_tls_label = i18n_servers(cx, "sentinel_tls");
+            let sentinel_insecure_tls_label = i18n_servers(cx, "sentinel_insecure_tls");
+            let read_preference_label = i18n_servers(cx, "read_preference");
+            let prefer_replica_label = i18n_servers(cx, "prefer_replica");
 
             dialog
                 .title(title)
@@ -493,7 +576,7 @@ impl ZedisServers {
                         .child(field().label(tls_label).child({
                             let server_enable_tls = server_enable_tls.clone();
                             Checkbox::new("redis-server-tls")
-                                .label(tls_check_label)
+                                .label(tls_check_label.clone())
                                 .checked(server_enable_tls.get())
                                 .on_click(move |checked, _, cx| {
                                     server_enable_tls.set(*checked);
@@ -506,7 +589,7 @@ impl ZedisServers {
                             .child(field().label(insecure_tls_label).child({
                                 let server_insecure_tls = server_insecure_tls.clone();
                                 Checkbox::new("redis-server-insecure-tls")
-                                    .label(insecure_tls_check_label)
+                                    .label(insecure_tls_check_label.clone())
                                     .checked(server_insecure_tls.get())
                                     .on_click(move |checked, _, cx| {
                                         server_insecure_tls.set(*checked);
@@ -518,8 +601,52 @@ impl ZedisServers {
                             .child(field().label(root_cert_label).child(Input::new(&root_cert_state)));
                     }
 
+                    // the sentinels have their own credentials and TLS settings
                     form = form
                         .child(field().label(master_name_label).child(Input::new(&master_name_state)))
+                        .child(
+                            field()
+                                .label(sentinel_username_label)
+                                .child(Input::new(&sentinel_username_state)),
+                        )
+                        .child(
+                            field()
+                                .label(sentinel_password_label)
+                                .child(Input::new(&sentinel_password_state).mask_toggle()),
+                        )
+                        .child(field().label(sentinel_tls_label).child({
+                            let sentinel_enable_tls = sentinel_enable_tls.clone();
+                            Checkbox::new("redis-server-sentinel-tls")
+                                .label(tls_check_label)
+                                .checked(sentinel_enable_tls.get())
+                                .on_click(move |checked, _, cx| {
+                                    sentinel_enable_tls.set(*checked);
+                                    cx.stop_propagation();
+                                })
+                        }));
+                    if sentinel_enable_tls.get() {
+                        form = form.child(field().label(sentinel_insecure_tls_label).child({
+                            let sentinel_insecure_tls = sentinel_insecure_tls.clone();
+                            Checkbox::new("redis-server-sentinel-insecure-tls")
+                                .label(insecure_tls_check_label)
+                                .checked(sentinel_insecure_tls.get())
+                                .on_click(move |checked, _, cx| {
+                                    sentinel_insecure_tls.set(*checked);
+                                    cx.stop_propagation();
+                                })
+                        }));
+                    }
+                    form = form
+                        .child(field().label(read_preference_label).child({
+                            let prefer_replica = prefer_replica.clone();
+                            Checkbox::new("redis-server-prefer-replica")
+                                .label(prefer_replica_label)
+                                .checked(prefer_replica.get())
+                                .on_click(move |checked, _, cx| {
+                                    prefer_replica.set(*checked);
+                                    cx.stop_propagation();
+                                })
+                        }))
                         .child(field().label(description_label).child(Input::new(&description_state)));
 
                     div()
@@ -14,11 +14,19 @@
 
 use crate::{
     components::{FormDialog, FormField, ZedisKvFetcher, open_add_form_dialog},
-    states::{RedisValue, ZedisServerState, i18n_common, i18n_set_editor},
+    states::{
+        RedisElement, RedisValue, ServerEvent, SetAlgebra, SetOperation, ZedisServerState, i18n_common, i18n_set_editor,
+    },
     views::{KvTableColumn, ZedisKvTable},
 };
-use gpui::{App, Entity, SharedString, Window, div, prelude::*};
-use gpui_component::WindowExt;
+use gpui::{App, Entity, SharedString, Subscription, Window, div, prelude::*};
+use gpui_component::{
+    ActiveTheme, Disableable, Selectable, Sizable, WindowExt,
+    button::Button,
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    v_flex,
+};
 use std::rc::Rc;
 use tracing::info;
 
@@ -91,6 +99,11 @@ impl ZedisKvFetcher for ZedisSetValues {
     ///
     /// For SETs, there's only one column (the member value itself).
     fn get(&self, row_ix: usize, _col_ix: usize) -> Option<SharedString> {
+        self.value.set_value()?.values.get(row_ix).map(|item| item.text.clone())
+    }
+
+    /// Retrieves the member at the given row, rendered by its detected format.
+    fn get_element(&self, row_ix: usize, _col_ix: usize) -> Option<RedisElement> {
         self.value.set_value()?.values.get(row_ix).cloned()
     }
 
@@ -152,6 +165,15 @@ impl ZedisKvFetcher for ZedisSetValues {
 pub struct ZedisSetEditor {
     /// The table component that renders the SET members
     table_state: Entity<ZedisKvTable<ZedisSetValues>>,
+    /// Reference to server state for executing Redis operations
+    server_state: Entity<ZedisServerState>,
+    /// Operation applied with the other keys
+    operation: SetOperation,
+    /// Input for the other set keys, separated by spaces
+    keys_state: Entity<InputState>,
+    /// Whether the keys input should be reset (the selected key changed)
+    key_changed: bool,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl ZedisSetEditor {
@@ -165,19 +187,261 @@ impl ZedisSetEditor {
     /// # Returns
     /// A new `ZedisSetEditor` instance with a single-column table
     pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut subscriptions = Vec::new();
         // Initialize the KV table with a single "Value" column
         let table_state = cx.new(|cx| {
-            ZedisKvTable::<ZedisSetValues>::new(vec![KvTableColumn::new("Value", None)], server_state, window, cx)
+            ZedisKvTable::<ZedisSetValues>::new(
+                vec![KvTableColumn::new("Value", None)],
+                server_state.clone(),
+                window,
+                cx,
+            )
         });
+        let keys_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .clean_on_escape()
+                .placeholder(i18n_set_editor(cx, "other_keys_placeholder"))
+        });
+        subscriptions.push(cx.subscribe(&keys_state, |this, _, event, cx| {
+            if matches!(event, InputEvent::PressEnter { .. }) {
+                this.apply_algebra(cx);
+            }
+        }));
+        // Reset the keys input when key selection changes
+        subscriptions.push(cx.subscribe(&server_state, |this, _, event, _cx| {
+            if matches!(event, ServerEvent::KeySelected(_)) {
+                this.key_changed = true;
+            }
+        }));
 
         info!("Creating new SET editor view");
-        Self { table_state }
+        Self {
+            table_state,
+            server_state,
+            operation: SetOperation::default(),
+            keys_state,
+            key_changed: false,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Returns the other set keys of the input.
+    fn other_keys(&self, cx: &App) -> Vec<SharedString> {
+        self.keys_state
+            .read(cx)
+            .value()
+            .split_whitespace()
+            .map(|key| SharedString::from(key.to_string()))
+            .collect()
+    }
+
+    /// Shows the result of the operation with the other keys in the table.
+    fn apply_algebra(&mut self, cx: &mut Context<Self>) {
+        let keys = self.other_keys(cx);
+        if keys.is_empty() {
+            return;
+        }
+        let algebra = SetAlgebra {
+            operation: self.operation,
+            keys,
+        };
+        self.server_state.update(cx, |state, cx| {
+            state.set_set_algebra(Some(algebra), cx);
+        });
+    }
+
+    /// Browses the members of the set again.
+    fn clear_algebra(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.keys_state.update(cx, |input, cx| {
+            input.set_value(SharedString::default(), window, cx);
+        });
+        self.server_state.update(cx, |state, cx| {
+            state.set_set_algebra(None, cx);
+        });
+    }
+
+    /// Opens a dialog to store the result of the operation as a new key.
+    fn handle_store_result(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let Some(destination) = values.first().filter(|value| !value.is_empty()) else {
+                return false;
+            };
+            server_state.update(cx, |state, cx| {
+                state.store_set_algebra(destination.clone(), cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+        let fields = vec![
+            FormField::new(i18n_set_editor(cx, "destination"))
+                .with_placeholder(i18n_set_editor(cx, "destination_placeholder"))
+                .with_focus(),
+        ];
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_set_editor(cx, "store_result_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    /// Opens a dialog to check which of the pasted members exist in the set.
+    fn handle_check_members(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let Some(value) = values.first() else {
+                return false;
+            };
+            // one member per line
+            let members: Vec<SharedString> = value
+                .lines()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty())
+                .map(|line| SharedString::from(line.to_string()))
+                .collect();
+            if members.is_empty() {
+                return false;
+            }
+            server_state.update(cx, |state, cx| {
+                state.check_set_members(members, cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+        let fields = vec![
+            FormField::new(i18n_set_editor(cx, "members"))
+                .with_placeholder(i18n_set_editor(cx, "members_placeholder"))
+                .with_multi_line()
+                .with_focus(),
+        ];
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_set_editor(cx, "check_members_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    /// Renders the set algebra toolbar: operation, other keys and result actions.
+    fn render_algebra_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let readonly = self.server_state.read(cx).is_readonly();
+        let has_result = self
+            .server_state
+            .read(cx)
+            .value()
+            .and_then(|value| value.set_value())
+            .is_some_and(|set| set.algebra.is_some());
+
+        let operation_button = |id: &'static str, operation: SetOperation, cx: &mut Context<Self>| {
+            Button::new(id)
+                .outline()
+                .small()
+                .label(operation.command())
+                .selected(self.operation == operation)
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.operation = operation;
+                    cx.notify();
+                }))
+        };
+
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(operation_button("zedis-set-algebra-inter", SetOperation::Inter, cx))
+            .child(operation_button("zedis-set-algebra-union", SetOperation::Union, cx))
+            .child(operation_button("zedis-set-algebra-diff", SetOperation::Diff, cx))
+            .child(Input::new(&self.keys_state).small().flex_1())
+            .child(
+                Button::new("zedis-set-algebra-apply")
+                    .outline()
+                    .small()
+                    .label(i18n_set_editor(cx, "apply_algebra"))
+                    .tooltip(i18n_set_editor(cx, "apply_algebra_tooltip"))
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.apply_algebra(cx);
+                    })),
+            )
+            .child(
+                Button::new("zedis-set-algebra-clear")
+                    .outline()
+                    .small()
+                    .label(i18n_set_editor(cx, "clear_algebra"))
+                    .disabled(!has_result)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.clear_algebra(window, cx);
+                    })),
+            )
+            .child(
+                Button::new("zedis-set-algebra-count")
+                    .outline()
+                    .small()
+                    .label("SINTERCARD")
+                    .tooltip(i18n_set_editor(cx, "intersection_count_tooltip"))
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        let keys = this.other_keys(cx);
+                        if keys.is_empty() {
+                            return;
+                        }
+                        this.server_state.update(cx, |state, cx| {
+                            state.count_set_intersection(keys, cx);
+                        });
+                    })),
+            )
+            .child(
+                Button::new("zedis-set-algebra-store")
+                    .outline()
+                    .small()
+                    .label(i18n_set_editor(cx, "store_result"))
+                    .tooltip(i18n_set_editor(cx, "store_result_tooltip"))
+                    .disabled(readonly || !has_result)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.handle_store_result(window, cx);
+                    })),
+            )
+            .child(
+                Button::new("zedis-set-check-members")
+                    .outline()
+                    .small()
+                    .label(i18n_set_editor(cx, "check_members"))
+                    .tooltip(i18n_set_editor(cx, "check_members_tooltip"))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.handle_check_members(window, cx);
+                    })),
+            )
     }
 }
 
 impl Render for ZedisSetEditor {
-    /// Renders the SET editor as a full-size container with the table.
-    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
-        div().size_full().child(self.table_state.clone()).into_any_element()
+    /// Renders the SET editor as a full-size container with the algebra toolbar and the table.
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Reset the keys input when key changes
+        if self.key_changed {
+            self.key_changed = false;
+            self.keys_state.update(cx, |input, cx| {
+                input.set_value(SharedString::default(), window, cx);
+            });
+        }
+        v_flex()
+            .size_full()
+            .child(self.render_algebra_bar(cx))
+            .child(
+                div()
+                    .flex_1()
+                    .w_full()
+                    .overflow_hidden()
+                    .child(self.table_state.clone()),
+            )
+            .into_any_element()
     }
 }
@@ -14,7 +14,7 @@
 
 use crate::{
     helpers::get_or_create_config_dir,
-    states::{ZedisGlobalStore, i18n_settings, update_app_state_and_save},
+    states::{FormatterRule, ProtobufRule, ValueFormatter, ZedisGlobalStore, i18n_settings, update_app_state_and_save},
 };
 use gpui::{Entity, Subscription, Window, prelude::*};
 use gpui_component::{
@@ -28,6 +28,11 @@ pub struct ZedisSettingEditor {
     max_key_tree_depth_state: Entity<InputState>,
     key_separator_state: Entity<InputState>,
     max_truncate_length_state: Entity<InputState>,
+    partial_value_size_state: Entity<InputState>,
+    protobuf_descriptors_state: Entity<InputState>,
+    protobuf_rules_state: Entity<InputState>,
+    formatters_state: Entity<InputState>,
+    formatter_rules_state: Entity<InputState>,
     config_dir_state: Entity<InputState>,
     _subscriptions: Vec<Subscription>,
 }
@@ -38,6 +43,29 @@ impl ZedisSettingEditor {
         let max_key_tree_depth = store.max_key_tree_depth();
         let key_separator = store.key_separator().to_string();
         let max_truncate_length = store.max_truncate_length();
+        let partial_value_size = store.partial_value_size();
+        let protobuf_descriptors = store.protobuf_descriptors().join("\n");
+        let protobuf_rules = store
+            .protobuf_rules()
+            .iter()
+            .map(|rule| format!("{} = {}", rule.pattern, rule.message))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let formatters = store
+            .formatters()
+            .iter()
+            .map(|formatter| match &formatter.encode {
+                Some(encode) => format!("{} = {} => {}", formatter.name, formatter.decode, encode),
+                None => format!("{} = {}", formatter.name, formatter.decode),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let formatter_rules = store
+            .formatter_rules()
+            .iter()
+            .map(|rule| format!("{} = {}", rule.pattern, rule.formatter))
+            .collect::<Vec<_>>()
+            .join("\n");
         let max_key_tree_depth_state = cx.new(|cx| {
             InputState::new(window, cx)
                 .placeholder(i18n_settings(cx, "max_key_tree_depth_placeholder"))
@@ -53,6 +81,39 @@ impl ZedisSettingEditor {
                 .placeholder(i18n_settings(cx, "max_truncate_length_placeholder"))
                 .default_value(max_truncate_length.to_string())
         });
+        let partial_value_size_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_settings(cx, "partial_value_size_placeholder"))
+                .default_value(partial_value_size.to_string())
+        });
+        let protobuf_descriptors_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .rows(3)
+                .placeholder(i18n_settings(cx, "protobuf_descriptors_placeholder"))
+                .default_value(protobuf_descriptors)
+        });
+        let protobuf_rules_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .rows(3)
+                .placeholder(i18n_settings(cx, "protobuf_rules_placeholder"))
+                .default_value(protobuf_rules)
+        });
+        let formatters_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .rows(3)
+                .placeholder(i18n_settings(cx, "formatters_placeholder"))
+                .default_value(formatters)
+        });
+        let formatter_rules_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .rows(3)
+                .placeholder(i18n_settings(cx, "formatter_rules_placeholder"))
+                .default_value(formatter_rules)
+        });
 
         let config_dir = get_or_create_config_dir().unwrap_or_default();
 
@@ -117,6 +178,122 @@ impl ZedisSettingEditor {
                 }
             },
         ));
+        subscriptions.push(
+            cx.subscribe_in(&partial_value_size_state, window, |_view, state, event, _window, cx| {
+                if let InputEvent::Blur = &event {
+                    let Ok(value) = state.read(cx).value().parse::<usize>() else {
+                        return;
+                    };
+                    update_app_state_and_save(cx, "save_partial_value_size", move |state, _cx| {
+                        state.set_partial_value_size(value);
+                    });
+                }
+            }),
+        );
+        subscriptions.push(cx.subscribe_in(
+            &protobuf_descriptors_state,
+            window,
+            |_view, state, event, _window, cx| {
+                if let InputEvent::Blur = &event {
+                    // one FileDescriptorSet file per line
+                    let descriptors = state
+                        .read(cx)
+                        .value()
+                        .lines()
+                        .map(|line| line.trim().to_string())
+                        .filter(|line| !line.is_empty())
+                        .collect::<Vec<_>>();
+                    update_app_state_and_save(cx, "save_protobuf_descriptors", move |state, _cx| {
+                        state.set_protobuf_descriptors(descriptors.clone());
+                    });
+                }
+            },
+        ));
+        subscriptions.push(
+            cx.subscribe_in(&protobuf_rules_state, window, |_view, state, event, _window, cx| {
+                if let InputEvent::Blur = &event {
+                    // one `pattern = package.Message` rule per line
+                    let rules = state
+                        .read(cx)
+                        .value()
+                        .lines()
+                        .filter_map(|line| {
+                            let (pattern, message) = line.split_once('=')?;
+                            let (pattern, message) = (pattern.trim(), message.trim());
+                            if pattern.is_empty() || message.is_empty() {
+                                return None;
+                            }
+                            Some(ProtobufRule {
+                                pattern: pattern.to_string(),
+                                message: message.to_string(),
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    update_app_state_and_save(cx, "save_protobuf_rules", move |state, _cx| {
+                        state.set_protobuf_rules(rules.clone());
+                    });
+                }
+            }),
+        );
+        subscriptions.push(
+            cx.subscribe_in(&formatters_state, window, |_view, state, event, _window, cx| {
+                if let InputEvent::Blur = &event {
+                    // one `name = decode command => encode command` formatter per line
+                    let formatters = state
+                        .read(cx)
+                        .value()
+                        .lines()
+                        .filter_map(|line| {
+                            let (name, commands) = line.split_once('=')?;
+                            let (decode, encode) = match commands.split_once("=>") {
+                                Some((decode, encode)) => (decode.trim(), Some(encode.trim())),
+                                None => (commands.trim(), None),
+                            };
+                            let name = name.trim();
+                            if name.is_empty() || decode.is_empty() {
+                                return None;
+                            }
+                            Some(ValueFormatter {
+                                name: name.to_string(),
+                                decode: decode.to_string(),
+                                encode: encode
+                                    .filter(|encode| !encode.is_empty())
+                                    .map(|encode| encode.to_string()),
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    update_app_state_and_save(cx, "save_formatters", move |state, _cx| {
+                        state.set_formatters(formatters.clone());
+                    });
+                }
+            }),
+        );
+        subscriptions.push(
+            cx.subscribe_in(&formatter_rules_state, window, |_view, state, event, _window, cx| {
+                if let InputEvent::Blur = &event {
+                    // one `pattern = formatter name` rule per line
+                    let rules = state
+                        .read(cx)
+                        .value()
+                        .lines()
+                        .filter_map(|line| {
+                            let (pattern, formatter) = line.split_once('=')?;
+                            let (pattern, formatter) = (pattern.trim(), formatter.trim());
+                            if pattern.is_empty() || formatter.is_empty() {
+                                return None;
+                            }
+                            Some(FormatterRule {
+                                pattern: pattern.to_string(),
+                                formatter: formatter.to_string(),
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    update_app_state_and_save(cx, "save_formatter_rules", move |state, _cx| {
+                        state.set_formatter_rules(rules.clone());
+                    });
+                }
+            }),
+        );
         let config_dir_state =
             cx.new(|cx| InputState::new(window, cx).default_value(config_dir.to_string_lossy().to_string()));
 
@@ -124,6 +301,11 @@ impl ZedisSettingEditor {
             _subscriptions: subscriptions,
             config_dir_state,
             max_truncate_length_state,
+            partial_value_size_state,
+            protobuf_descriptors_state,
+            protobuf_rules_state,
+            formatters_state,
+            formatter_rules_state,
             key_separator_state,
             max_key_tree_depth_state,
         }
@@ -154,6 +336,31 @@ impl Render for ZedisSettingEditor {
                             .label(i18n_settings(cx, "max_truncate_length"))
                             .child(Input::new(&self.max_truncate_length_state)),
                     )
+                    .child(
+                        field()
+                            .label(i18n_settings(cx, "partial_value_size"))
+                            .child(Input::new(&self.partial_value_size_state)),
+                    )
+                    .child(
+                        field()
+                            .label(i18n_settings(cx, "protobuf_descriptors"))
+                            .child(Input::new(&self.protobuf_descriptors_state)),
+                    )
+                    .child(
+                        field()
+                            .label(i18n_settings(cx, "protobuf_rules"))
+                            .child(Input::new(&self.protobuf_rules_state)),
+                    )
+                    .child(
+                        field()
+                            .label(i18n_settings(cx, "formatters"))
+                            .child(Input::new(&self.formatters_state)),
+                    )
+                    .child(
+                        field()
+                            .label(i18n_settings(cx, "formatter_rules"))
+                            .child(Input::new(&self.formatter_rules_state)),
+                    )
                     .child(
                         field()
                             .label(i18n_settings(cx, "config_dir"))
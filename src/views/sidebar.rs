@@ -16,17 +16,19 @@ use crate::{
     assets::CustomIconName,
     helpers::is_development,
     states::{
-        FontSize, FontSizeAction, LocaleAction, Route, ServerEvent, SettingsAction, ThemeAction, ZedisGlobalStore,
-        ZedisServerState, i18n_sidebar,
+        BookmarkKey, FontSize, FontSizeAction, LocaleAction, Route, ServerEvent, SettingsAction, ThemeAction,
+        ZedisGlobalStore, ZedisTabsState, i18n_sidebar,
     },
 };
-use gpui::{Context, Corner, Entity, Pixels, SharedString, Subscription, Window, div, prelude::*, px, uniform_list};
+use gpui::{
+    App, Context, Corner, Entity, Pixels, SharedString, Subscription, Window, div, prelude::*, px, uniform_list,
+};
 use gpui_component::{
-    ActiveTheme, Icon, IconName, ThemeMode,
+    ActiveTheme, Disableable, Icon, IconName, Selectable, ThemeMode,
     button::{Button, ButtonVariants},
     label::Label,
     list::ListItem,
-    menu::DropdownMenu,
+    menu::{DropdownMenu, PopupMenuItem},
     v_flex,
 };
 use tracing::info;
@@ -39,6 +41,17 @@ const STAR_BUTTON_HEIGHT: f32 = 48.0;
 const SETTINGS_BUTTON_HEIGHT: f32 = 44.0;
 const SERVER_LIST_ITEM_BORDER_WIDTH: f32 = 3.0;
 const SETTINGS_ICON_SIZE: f32 = 18.0;
+const BOOKMARKS_MENU_MIN_WIDTH: f32 = 280.0;
+const BOOKMARKS_MENU_MAX_HEIGHT: f32 = 480.0;
+
+/// Switch to the editor route, used when a bookmark is opened
+fn go_to_editor(cx: &mut App) {
+    cx.update_global::<ZedisGlobalStore, ()>(|store, cx| {
+        store.update(cx, |state, cx| {
+            state.go_to(Route::Editor, cx);
+        });
+    });
+}
 
 /// Internal state for sidebar component
 ///
@@ -58,6 +71,8 @@ struct SidebarState {
 /// Features:
 /// - Star button (link to GitHub)
 /// - Server list for quick navigation between servers and home
+/// - Script workbench of the active tab
+/// - Bookmarks menu (pinned keys, saved searches, recent keys)
 /// - Settings menu with theme and language options
 ///
 /// The sidebar provides quick access to:
@@ -68,8 +83,8 @@ pub struct ZedisSidebar {
     /// Internal state with cached server list
     state: SidebarState,
 
-    /// Reference to server state for Redis operations
-    server_state: Entity<ZedisServerState>,
+    /// Editor tabs, servers and bookmarks are opened in the active tab
+    tabs: Entity<ZedisTabsState>,
 
     /// Event subscriptions for reactive updates
     _subscriptions: Vec<Subscription>,
@@ -81,11 +96,11 @@ impl ZedisSidebar {
     /// Sets up listeners for:
     /// - Server selection changes (updates current selection)
     /// - Server list updates (refreshes displayed servers)
-    pub fn new(server_state: Entity<ZedisServerState>, _window: &mut Window, cx: &mut Context<Self>) -> Self {
+    pub fn new(tabs: Entity<ZedisTabsState>, _window: &mut Window, cx: &mut Context<Self>) -> Self {
         let mut subscriptions = vec![];
 
-        // Subscribe to server events for reactive updates
-        subscriptions.push(cx.subscribe(&server_state, |this, _server_state, event, cx| {
+        // Subscribe to server events (of the active tab) for reactive updates
+        subscriptions.push(cx.subscribe(&tabs, |this, _tabs, event, cx| {
             match event {
                 ServerEvent::ServerSelected(server_id, _) => {
                     // Update current selection highlight
@@ -103,11 +118,10 @@ impl ZedisSidebar {
         }));
 
         // Get current server ID for initial selection
-        let state = server_state.read(cx).clone();
-        let server_id = state.server_id().to_string().into();
+        let server_id = tabs.read(cx).active().read(cx).server_id().to_string().into();
 
         let mut this = Self {
-            server_state,
+            tabs,
             state: SidebarState {
                 server_id,
                 ..Default::default()
@@ -131,7 +145,7 @@ impl ZedisSidebar {
         // Start with home page entry
         let mut server_names = vec![(SharedString::default(), SharedString::default())];
 
-        let server_state = self.server_state.read(cx);
+        let server_state = self.tabs.read(cx).active().read(cx);
         if let Some(servers) = server_state.servers() {
             server_names.extend(
                 servers
@@ -209,7 +223,7 @@ impl ZedisSidebar {
                                     });
                                 });
 
-                                this.server_state.update(cx, |state, cx| {
+                                this.tabs.read(cx).active().update(cx, |state, cx| {
                                     state.select(server_id.clone(), 0, cx);
                                 });
                             });
@@ -220,6 +234,146 @@ impl ZedisSidebar {
         .size_full()
     }
 
+    /// Render scripts button, toggling the script workbench of the active tab
+    ///
+    /// Disabled until a server is selected in the active tab.
+    fn render_scripts_button(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_scripts = cx.global::<ZedisGlobalStore>().read(cx).route() == Route::Scripts;
+        let has_server = !self.state.server_id.is_empty();
+        let btn = Button::new("zedis-sidebar-scripts-btn")
+            .ghost()
+            .w_full()
+            .h(px(SETTINGS_BUTTON_HEIGHT))
+            .tooltip(i18n_sidebar(cx, "scripts"))
+            .selected(is_scripts)
+            .disabled(!has_server)
+            .child(Icon::new(IconName::SquareTerminal).size(px(SETTINGS_ICON_SIZE)))
+            .on_click(move |_, _window, cx| {
+                let route = if is_scripts { Route::Editor } else { Route::Scripts };
+                cx.update_global::<ZedisGlobalStore, ()>(|store, cx| {
+                    store.update(cx, |state, cx| {
+                        state.go_to(route, cx);
+                    });
+                });
+            });
+        div().border_t_1().border_color(cx.theme().border).child(btn)
+    }
+
+    /// Render bookmarks button with dropdown menu
+    ///
+    /// The dropdown lists pinned keys, saved searches and recent keys of all servers.
+    /// Clicking an entry switches server/db as needed and opens the key or runs the search.
+    fn render_bookmarks_button(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let tabs = self.tabs.clone();
+        let btn = Button::new("zedis-sidebar-bookmarks-btn")
+            .ghost()
+            .w_full()
+            .h(px(SETTINGS_BUTTON_HEIGHT))
+            .tooltip(i18n_sidebar(cx, "bookmarks"))
+            .child(Icon::new(CustomIconName::Bookmark).size(px(SETTINGS_ICON_SIZE)))
+            .dropdown_menu_with_anchor(Corner::BottomLeft, move |menu, _window, cx| {
+                let server_state = tabs.read(cx).active();
+                let state = server_state.read(cx);
+                let bookmarks = state.bookmarks().clone();
+                // Label of a key with its server name and db, e.g. `user:1 (local/0)`
+                let key_label = |item: &BookmarkKey| -> SharedString {
+                    let server_name = state
+                        .server(&item.server_id)
+                        .map(|server| server.name.as_str())
+                        .unwrap_or(item.server_id.as_str());
+                    format!("{} ({server_name}/{})", item.key, item.db).into()
+                };
+                let pinned_keys: Vec<_> = bookmarks
+                    .pinned_keys
+                    .iter()
+                    .map(|item| (key_label(item), item.clone()))
+                    .collect();
+                let recent_keys: Vec<_> = bookmarks
+                    .recent_keys
+                    .iter()
+                    .map(|item| (key_label(item), item.clone()))
+                    .collect();
+                let filtered_label = i18n_sidebar(cx, "bookmarks_filtered");
+                let saved_searches: Vec<_> = bookmarks
+                    .saved_searches
+                    .iter()
+                    .map(|search| {
+                        let server_name = state
+                            .server(&search.server_id)
+                            .map(|server| server.name.as_str())
+                            .unwrap_or(search.server_id.as_str());
+                        let mut label = format!("{} ({server_name}/{})", search.label(), search.db);
+                        if search.has_key_filter() {
+                            label = format!("{label} {filtered_label}");
+                        }
+                        (SharedString::from(label), search.clone())
+                    })
+                    .collect();
+
+                let mut menu = menu
+                    .min_w(px(BOOKMARKS_MENU_MIN_WIDTH))
+                    .max_h(px(BOOKMARKS_MENU_MAX_HEIGHT))
+                    .scrollable(true);
+                if pinned_keys.is_empty() && saved_searches.is_empty() && recent_keys.is_empty() {
+                    return menu.label(i18n_sidebar(cx, "bookmarks_empty"));
+                }
+                if !pinned_keys.is_empty() {
+                    menu = menu.label(i18n_sidebar(cx, "pinned_keys"));
+                    for (label, item) in pinned_keys {
+                        let server_state = server_state.clone();
+                        menu = menu.item(PopupMenuItem::new(label).icon(IconName::Star).on_click(
+                            move |_, _window, cx| {
+                                go_to_editor(cx);
+                                server_state.update(cx, |state, cx| {
+                                    state.open_bookmark_key(item.clone(), cx);
+                                });
+                            },
+                        ));
+                    }
+                }
+                if !saved_searches.is_empty() {
+                    menu = menu.separator().label(i18n_sidebar(cx, "saved_searches"));
+                    for (label, search) in saved_searches {
+                        let server_state = server_state.clone();
+                        menu = menu.item(PopupMenuItem::new(label).icon(IconName::Search).on_click(
+                            move |_, _window, cx| {
+                                go_to_editor(cx);
+                                server_state.update(cx, |state, cx| {
+                                    state.open_saved_search(search.clone(), cx);
+                                });
+                            },
+                        ));
+                    }
+                }
+                if !recent_keys.is_empty() {
+                    menu = menu.separator().label(i18n_sidebar(cx, "recent_keys"));
+                    for (label, item) in recent_keys {
+                        let server_state = server_state.clone();
+                        menu = menu.item(PopupMenuItem::new(label).icon(CustomIconName::Key).on_click(
+                            move |_, _window, cx| {
+                                go_to_editor(cx);
+                                server_state.update(cx, |state, cx| {
+                                    state.open_bookmark_key(item.clone(), cx);
+                                });
+                            },
+                        ));
+                    }
+                    let server_state = server_state.clone();
+                    menu = menu.separator().item(
+                        PopupMenuItem::new(i18n_sidebar(cx, "clear_recent_keys"))
+                            .icon(IconName::Delete)
+                            .on_click(move |_, _window, cx| {
+                                server_state.update(cx, |state, cx| {
+                                    state.clear_recent_keys(cx);
+                                });
+                            }),
+                    );
+                }
+                menu
+            });
+        div().border_t_1().border_color(cx.theme().border).child(btn)
+    }
+
     /// Render settings button with dropdown menu
     ///
     /// The dropdown contains two submenus:
@@ -371,7 +525,9 @@ impl Render for ZedisSidebar {
     /// Layout structure (top to bottom):
     /// 1. GitHub star button
     /// 2. Server list (scrollable, takes remaining space)
-    /// 3. Settings button (theme & language)
+    /// 3. Scripts button (script workbench)
+    /// 4. Bookmarks button (pinned keys, saved searches, recent keys)
+    /// 5. Settings button (theme & language)
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         tracing::debug!("Rendering sidebar view");
         let show_settings_button = is_development();
@@ -387,6 +543,8 @@ impl Render for ZedisSidebar {
                 // Server list takes up remaining vertical space
                 div().flex_1().size_full().child(self.render_server_list(window, cx)),
             )
+            .child(self.render_scripts_button(window, cx))
+            .child(self.render_bookmarks_button(window, cx))
             .when(show_settings_button, |this| {
                 this.child(self.render_settings_button(window, cx))
             })
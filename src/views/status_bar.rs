@@ -12,17 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::views::ZedisHistoryViewer;
 use crate::{
     assets::CustomIconName,
-    connection::RedisClientDescription,
+    connection::{RedisClientDescription, ReplicaInfo},
     states::{
-        ErrorMessage, ServerEvent, ServerTask, ViewMode, ZedisServerState, i18n_common, i18n_sidebar, i18n_status_bar,
+        ConnectionState, ErrorMessage, ServerEvent, ServerTask, ViewMode, ZedisGlobalStore, ZedisServerState,
+        i18n_common, i18n_sidebar, i18n_status_bar,
     },
 };
-use gpui::{Entity, Hsla, SharedString, Subscription, Task, TextAlign, Window, div, prelude::*};
+use gpui::{Entity, Hsla, SharedString, Subscription, Task, TextAlign, Window, div, prelude::*, px};
 use gpui_component::select::{SearchableVec, Select, SelectEvent, SelectItem, SelectState};
 use gpui_component::{
-    ActiveTheme, Disableable, Icon, IconName, IndexPath, Sizable,
+    ActiveTheme, Disableable, Icon, IconName, IndexPath, Sizable, WindowExt,
     button::{Button, ButtonVariants},
     h_flex,
     label::Label,
@@ -31,6 +33,8 @@ use gpui_component::{
 use std::{sync::Arc, time::Duration};
 use tracing::info;
 
+const HISTORY_DIALOG_WIDTH: f32 = 900.0;
+
 /// Formats the database size and scan count string "count/total".
 #[inline]
 fn format_size(dbsize: Option<u64>, scan_count: usize) -> SharedString {
@@ -72,20 +76,87 @@ fn format_nodes(nodes: (usize, usize), version: &str) -> SharedString {
     format!("{} / {} (v{})", nodes.0, nodes.1, version).into()
 }
 
+/// Formats the address and the replication lag of a replica, e.g. "10.0.0.2:6379 lag 0s / 120B".
+#[inline]
+fn format_replica(replica: &ReplicaInfo, cx: &Context<ZedisStatusBar>) -> String {
+    if !replica.online {
+        return format!("{} {}", replica.host_port(), i18n_status_bar(cx, "replica_offline"));
+    }
+    let lag = replica
+        .lag
+        .map(|lag| format!("{lag}s"))
+        .unwrap_or_else(|| "--".to_string());
+    let offset_lag = replica
+        .offset_lag
+        .map(|offset_lag| format!(" / {offset_lag}B"))
+        .unwrap_or_default();
+    format!(
+        "{} {} {lag}{offset_lag}",
+        replica.host_port(),
+        i18n_status_bar(cx, "replica_lag")
+    )
+}
+
 #[inline]
-fn format_nodes_description(description: Arc<RedisClientDescription>, cx: &Context<ZedisStatusBar>) -> SharedString {
+fn format_nodes_description(
+    description: Arc<RedisClientDescription>,
+    replicas: &[ReplicaInfo],
+    cx: &Context<ZedisStatusBar>,
+) -> SharedString {
     let t = i18n_sidebar(cx, "server_type");
     let master_nodes = i18n_sidebar(cx, "master_nodes");
     let slave_nodes = i18n_sidebar(cx, "slave_nodes");
-    let mut messages = Vec::with_capacity(3);
+    let mut messages = Vec::with_capacity(3 + replicas.len());
     messages.push(format!("{t}: {}", description.server_type.as_str()));
     messages.push(format!("{master_nodes}: {}", description.master_nodes));
-    if !description.slave_nodes.is_empty() {
+    if !replicas.is_empty() {
+        messages.push(format!("{}:", i18n_status_bar(cx, "replicas")));
+        messages.extend(
+            replicas
+                .iter()
+                .map(|replica| format!("  {}", format_replica(replica, cx))),
+        );
+    } else if !description.slave_nodes.is_empty() {
         messages.push(format!("{slave_nodes}: {}", description.slave_nodes));
     }
     messages.join("\n").into()
 }
 
+/// Label and tooltip of the replicas the reads are sent to, the label shows the largest lag
+#[inline]
+fn format_read_replicas(
+    read_replicas: &[SharedString],
+    replicas: &[ReplicaInfo],
+    cx: &Context<ZedisStatusBar>,
+) -> (SharedString, SharedString) {
+    if read_replicas.is_empty() {
+        return (SharedString::default(), SharedString::default());
+    }
+    let read_infos: Vec<Option<&ReplicaInfo>> = read_replicas
+        .iter()
+        .map(|addr| replicas.iter().find(|replica| replica.host_port() == addr.as_str()))
+        .collect();
+    let lag = read_infos
+        .iter()
+        .filter_map(|replica| replica.and_then(|replica| replica.lag))
+        .max()
+        .map(|lag| format!("{lag}s"))
+        .unwrap_or_else(|| "--".to_string());
+    let label = format!("{} {lag}", i18n_status_bar(cx, "read_from_replica"));
+    let mut messages = Vec::with_capacity(1 + read_replicas.len());
+    messages.push(format!("{}:", i18n_status_bar(cx, "read_replicas")));
+    messages.extend(
+        read_replicas
+            .iter()
+            .zip(read_infos)
+            .map(|(addr, replica)| match replica {
+                Some(replica) => format!("  {}", format_replica(replica, cx)),
+                None => format!("  {addr}"),
+            }),
+    );
+    (label.into(), messages.join("\n").into())
+}
+
 // --- Local State ---
 
 #[derive(Default)]
@@ -100,6 +171,10 @@ struct StatusBarServerState {
     scan_finished: bool,
     soft_wrap: bool,
     nodes_description: SharedString,
+    is_sentinel: bool,
+    /// Label of the replicas the reads are sent to, empty if they go to the masters
+    read_replicas: SharedString,
+    read_replicas_description: SharedString,
 }
 
 #[derive(Debug, Clone)]
@@ -118,6 +193,41 @@ impl SelectItem for DbInfo {
     }
 }
 
+#[derive(Debug, Clone)]
+struct FormatterInfo {
+    label: SharedString,
+    /// Formatter name, empty for the automatically detected format
+    name: SharedString,
+}
+
+/// A node the keys can be browsed from, the master or a replica
+#[derive(Debug, Clone)]
+struct ReplicaItem {
+    label: SharedString,
+    /// Address of the replica, empty for the master
+    addr: SharedString,
+}
+
+impl SelectItem for ReplicaItem {
+    type Value = SharedString;
+    fn title(&self) -> SharedString {
+        self.label.clone()
+    }
+    fn value(&self) -> &Self::Value {
+        &self.addr
+    }
+}
+
+impl SelectItem for FormatterInfo {
+    type Value = SharedString;
+    fn title(&self) -> SharedString {
+        self.label.clone()
+    }
+    fn value(&self) -> &Self::Value {
+        &self.name
+    }
+}
+
 /// Local state for the status bar to cache formatted strings and colors.
 /// This prevents re-calculating strings on every render frame.
 #[derive(Default)]
@@ -125,6 +235,7 @@ struct StatusBarState {
     server_state: StatusBarServerState,
     data_format: Option<SharedString>,
     error: Option<ErrorMessage>,
+    connection_state: ConnectionState,
 }
 
 pub struct ZedisStatusBar {
@@ -132,7 +243,11 @@ pub struct ZedisStatusBar {
 
     viewer_mode_state: Entity<SelectState<SearchableVec<SharedString>>>,
     db_state: Entity<SelectState<Vec<DbInfo>>>,
+    formatter_state: Entity<SelectState<Vec<FormatterInfo>>>,
+    replica_state: Entity<SelectState<Vec<ReplicaItem>>>,
     should_reset_viewer_mode: bool,
+    should_reset_replicas: bool,
+    should_reset_formatter: bool,
     server_state: Entity<ZedisServerState>,
     heartbeat_task: Option<Task<()>>,
     _subscriptions: Vec<Subscription>,
@@ -171,24 +286,22 @@ impl ZedisStatusBar {
                 ServerEvent::ErrorOccurred(error) => {
                     this.state.error = Some(error.clone());
                 }
+                ServerEvent::ConnectionStateChanged(connection_state) => {
+                    this.state.connection_state = *connection_state;
+                }
                 ServerEvent::TaskStarted(task) => {
-                    // Clear error when a new task starts (except background ping)
-                    if *task != ServerTask::RefreshRedisInfo {
+                    // Clear error when a new task starts (except background ping and reconnect)
+                    if !matches!(task, ServerTask::RefreshRedisInfo | ServerTask::Reconnect) {
                         this.state.error = None;
                     }
                 }
                 ServerEvent::ValueLoaded(_) => {
-                    let state = server_state.read(cx);
                     this.should_reset_viewer_mode = true;
-                    if let Some(value) = state.value().and_then(|item| item.bytes_value()) {
-                        let mut format = value.format.as_str().to_string();
-                        if let Some(mime) = &value.mime {
-                            format = format!("{}({})", format, mime);
-                        }
-                        this.state.data_format = Some(format.into());
-                    } else {
-                        this.state.data_format = None;
-                    }
+                    this.should_reset_formatter = true;
+                    this.update_data_format(server_state, cx);
+                }
+                ServerEvent::ValueModeViewUpdated(_) => {
+                    this.update_data_format(server_state, cx);
                 }
                 _ => {
                     return;
@@ -245,10 +358,42 @@ impl ZedisStatusBar {
             },
         ));
 
+        let formatter_state = cx.new(|cx| SelectState::new(vec![], None, window, cx));
+        subscriptions.push(cx.subscribe_in(
+            &formatter_state,
+            window,
+            |view, _state, event: &SelectEvent<Vec<FormatterInfo>>, _window, cx| match event {
+                SelectEvent::Confirm(value) => {
+                    let name = value.clone().filter(|name| !name.is_empty());
+                    view.server_state.update(cx, |state, cx| {
+                        state.apply_value_formatter(name, cx);
+                    });
+                }
+            },
+        ));
+
+        let replica_state = cx.new(|cx| SelectState::new(vec![], None, window, cx));
+        subscriptions.push(cx.subscribe_in(
+            &replica_state,
+            window,
+            |view, _state, event: &SelectEvent<Vec<ReplicaItem>>, _window, cx| match event {
+                SelectEvent::Confirm(value) => {
+                    let addr = value.clone().filter(|addr| !addr.is_empty());
+                    view.server_state.update(cx, |state, cx| {
+                        state.browse_replica(addr, cx);
+                    });
+                }
+            },
+        ));
+
         let mut this = Self {
             heartbeat_task: None,
             viewer_mode_state,
             db_state,
+            formatter_state,
+            replica_state,
+            should_reset_replicas: false,
+            should_reset_formatter: false,
             server_state: server_state.clone(),
             _subscriptions: subscriptions,
             should_reset_viewer_mode: false,
@@ -268,6 +413,7 @@ impl ZedisStatusBar {
         }
         self.state.data_format = None;
         self.state.error = None;
+        self.state.connection_state = ConnectionState::Connected;
     }
     fn fill_state(&mut self, server_state: Entity<ZedisServerState>, cx: &Context<Self>) {
         let state = server_state.read(cx);
@@ -284,6 +430,8 @@ impl ZedisStatusBar {
         } else {
             redis_info.used_memory_human.clone()
         };
+        let (read_replicas, read_replicas_description) =
+            format_read_replicas(state.read_replicas(), &state.replicas(), cx);
         self.state.server_state = StatusBarServerState {
             supports_db_selection: state.supports_db_selection(),
             server_id: state.server_id().to_string().into(),
@@ -294,10 +442,29 @@ impl ZedisStatusBar {
             nodes: format_nodes(state.nodes(), state.version()),
             scan_finished: state.scan_completed(),
             soft_wrap: state.soft_wrap(),
-            nodes_description: format_nodes_description(state.nodes_description().clone(), cx),
+            nodes_description: format_nodes_description(state.nodes_description().clone(), &state.replicas(), cx),
+            is_sentinel: state.is_sentinel(),
+            read_replicas,
+            read_replicas_description,
         };
+        self.should_reset_replicas = true;
     }
     /// Start the heartbeat task
+    /// Update the data format label from the current bytes value
+    fn update_data_format(&mut self, server_state: Entity<ZedisServerState>, cx: &Context<Self>) {
+        let state = server_state.read(cx);
+        if let Some(value) = state.value().and_then(|item| item.bytes_value()) {
+            let mut format = value.format.as_str().to_string();
+            if let Some(formatter) = &value.formatter {
+                format = format!("{}({})", format, formatter.name);
+            } else if let Some(mime) = &value.mime {
+                format = format!("{}({})", format, mime);
+            }
+            self.state.data_format = Some(format.into());
+        } else {
+            self.state.data_format = None;
+        }
+    }
     fn start_heartbeat(&mut self, server_state: Entity<ZedisServerState>, cx: &mut Context<Self>) {
         // start task
         self.heartbeat_task = Some(cx.spawn(async move |_this, cx| {
@@ -309,16 +476,67 @@ impl ZedisStatusBar {
             }
         }));
     }
+    /// Open the edit history of the current server
+    fn open_history(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let viewer = cx.new(|cx| ZedisHistoryViewer::new(server_state, window, cx));
+        window.open_dialog(cx, move |dialog, _, cx| {
+            dialog
+                .title(i18n_status_bar(cx, "edit_history"))
+                .w(px(HISTORY_DIALOG_WIDTH))
+                .overlay_closable(true)
+                .child(viewer.clone())
+        });
+    }
+    /// Render the connection state, an offline server can be reconnected by clicking it
+    fn render_connection_state(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let button = Button::new("zedis-status-bar-connection").ghost().small().mr_1();
+        match self.state.connection_state {
+            ConnectionState::Connected => button
+                .disabled(true)
+                .tooltip(i18n_status_bar(cx, "connected"))
+                .icon(Icon::new(CustomIconName::CircleCheckBig).text_color(cx.theme().green)),
+            ConnectionState::Reconnecting { attempt } => button
+                .loading(true)
+                .text_color(cx.theme().yellow)
+                .tooltip(i18n_status_bar(cx, "reconnecting_tooltip"))
+                .label(format!("{} ({attempt})", i18n_status_bar(cx, "reconnecting"))),
+            ConnectionState::Offline => button
+                .text_color(cx.theme().red)
+                .tooltip(i18n_status_bar(cx, "reconnect_tooltip"))
+                .icon(Icon::new(CustomIconName::RotateCw))
+                .label(i18n_status_bar(cx, "offline"))
+                .on_click(cx.listener(|this, _, _window, cx| {
+                    this.server_state.update(cx, |state, cx| {
+                        state.reconnect(cx);
+                    });
+                })),
+        }
+    }
     /// Render the server status
     fn render_server_status(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let server_state = &self.state.server_state;
         let is_completed = server_state.scan_finished;
         let nodes_description = server_state.nodes_description.clone();
+        let read_replicas_description = server_state.read_replicas_description.clone();
         h_flex()
             .items_center()
+            .child(self.render_connection_state(cx))
             .when(server_state.supports_db_selection, |this| {
                 this.child(Select::new(&self.db_state).mr_2().mt_1().small())
             })
+            .when(server_state.is_sentinel, |this| {
+                this.child(
+                    div()
+                        .id("zedis-status-bar-replica")
+                        .mr_2()
+                        .mt_1()
+                        .child(Select::new(&self.replica_state).small())
+                        .tooltip(|window, cx| {
+                            Tooltip::new(i18n_status_bar(cx, "browse_replica_tooltip")).build(window, cx)
+                        }),
+                )
+            })
             .child(
                 Button::new("zedis-status-bar-key-collapse")
                     .outline()
@@ -332,6 +550,17 @@ impl ZedisStatusBar {
                         });
                     })),
             )
+            .child(
+                Button::new("zedis-status-bar-history")
+                    .outline()
+                    .small()
+                    .tooltip(i18n_status_bar(cx, "edit_history"))
+                    .icon(CustomIconName::History)
+                    .mr_1()
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.open_history(window, cx);
+                    })),
+            )
             .child(
                 Button::new("zedis-status-bar-scan-more")
                     .outline()
@@ -361,6 +590,18 @@ impl ZedisStatusBar {
                     .id("zedis-servers")
                     .tooltip(move |window, cx| Tooltip::new(nodes_description.clone()).build(window, cx)),
             )
+            .when(!server_state.read_replicas.is_empty(), |this| {
+                this.child(
+                    div()
+                        .child(
+                            Label::new(server_state.read_replicas.clone())
+                                .text_color(cx.theme().primary)
+                                .mr_4(),
+                        )
+                        .id("zedis-status-bar-read-replicas")
+                        .tooltip(move |window, cx| Tooltip::new(read_replicas_description.clone()).build(window, cx)),
+                )
+            })
             .child(
                 Button::new("zedis-status-bar-letency")
                     .ghost()
@@ -422,6 +663,15 @@ impl ZedisStatusBar {
             .label(data_format)
             .into_any_element()
     }
+    fn render_formatter(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.state.data_format.is_none() || cx.global::<ZedisGlobalStore>().read(cx).formatters().is_empty() {
+            return h_flex();
+        };
+        let label = i18n_status_bar(cx, "formatter");
+        h_flex()
+            .child(Label::new(label).mr_1())
+            .child(Select::new(&self.formatter_state).appearance(false))
+    }
     fn render_viewer_mode(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         if self.state.data_format.is_none() {
             return h_flex();
@@ -460,6 +710,60 @@ impl Render for ZedisStatusBar {
             });
             self.should_reset_viewer_mode = false;
         }
+        if self.should_reset_formatter {
+            let current = self
+                .server_state
+                .read(cx)
+                .value()
+                .and_then(|value| value.bytes_value())
+                .and_then(|value| value.formatter.clone());
+            let mut items = vec![FormatterInfo {
+                label: ViewMode::Auto.as_str().into(),
+                name: SharedString::default(),
+            }];
+            items.extend(
+                cx.global::<ZedisGlobalStore>()
+                    .read(cx)
+                    .formatters()
+                    .iter()
+                    .map(|formatter| FormatterInfo {
+                        label: formatter.name.clone().into(),
+                        name: formatter.name.clone().into(),
+                    }),
+            );
+            let selected = current
+                .and_then(|formatter| items.iter().position(|item| item.name == formatter.name.as_str()))
+                .unwrap_or(0);
+            self.formatter_state.update(cx, |state, cx| {
+                state.set_items(items, window, cx);
+                state.set_selected_index(Some(IndexPath::new(selected)), window, cx);
+            });
+            self.should_reset_formatter = false;
+        }
+        if self.should_reset_replicas {
+            let state = self.server_state.read(cx);
+            let browsed_replica = state.browsed_replica().cloned().unwrap_or_default();
+            let mut items = vec![ReplicaItem {
+                label: i18n_status_bar(cx, "read_from_master"),
+                addr: SharedString::default(),
+            }];
+            items.extend(state.replicas().iter().map(|replica| ReplicaItem {
+                label: format_replica(replica, cx).into(),
+                addr: replica.host_port().into(),
+            }));
+            if !browsed_replica.is_empty() && !items.iter().any(|item| item.addr == browsed_replica) {
+                items.push(ReplicaItem {
+                    label: browsed_replica.clone(),
+                    addr: browsed_replica.clone(),
+                });
+            }
+            let selected = items.iter().position(|item| item.addr == browsed_replica).unwrap_or(0);
+            self.replica_state.update(cx, |state, cx| {
+                state.set_items(items, window, cx);
+                state.set_selected_index(Some(IndexPath::new(selected)), window, cx);
+            });
+            self.should_reset_replicas = false;
+        }
         h_flex()
             .justify_between()
             .text_sm()
@@ -472,6 +776,7 @@ impl Render for ZedisStatusBar {
             .child(self.render_server_status(window, cx))
             .child(self.render_editor_settings(window, cx))
             .child(self.render_data_format(window, cx))
+            .child(self.render_formatter(window, cx))
             .child(self.render_viewer_mode(window, cx))
             .child(self.render_errors(window, cx))
     }
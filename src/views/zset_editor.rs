@@ -21,17 +21,33 @@
 //! - Updating scores of existing members (inline editing)
 //! - Removing members
 //! - Filtering members with pattern matching
+//! - Browsing ranges by rank, score or member with the count of members in the range
+//! - Incrementing scores and removing the browsed range
 //! - Incremental loading of large ZSETs with pagination
 
 use crate::{
     components::{FormDialog, FormField, ZedisKvFetcher, open_add_form_dialog},
-    states::{RedisValue, ZedisServerState, i18n_common, i18n_zset_editor},
+    states::{
+        RedisElement, RedisValue, ServerEvent, ZedisGlobalStore, ZedisServerState, ZsetRange, ZsetRangeBy, i18n_common,
+        i18n_zset_editor,
+    },
     views::{KvTableColumn, ZedisKvTable},
 };
-use gpui::{App, Entity, SharedString, Window, div, prelude::*};
-use gpui_component::WindowExt;
+use gpui::{App, Entity, SharedString, Subscription, Window, div, prelude::*, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Selectable, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    label::Label,
+    v_flex,
+};
+use rust_i18n::t;
 use std::rc::Rc;
 
+/// Width of the range bound input fields in pixels
+const RANGE_INPUT_WIDTH: f32 = 120.0;
+
 /// Data adapter for Redis ZSET values to work with the KV table component.
 ///
 /// This struct implements the `ZedisKvFetcher` trait to provide data access
@@ -57,8 +73,17 @@ impl ZedisKvFetcher for ZedisZsetValues {
         if col_ix == 2 {
             Some(score.to_string().into())
         } else {
-            Some(member.clone())
+            Some(member.text.clone())
+        }
+    }
+
+    /// Retrieves the member of the given row, the score column is rendered as text.
+    fn get_element(&self, row_ix: usize, col_ix: usize) -> Option<RedisElement> {
+        if col_ix == 2 {
+            return None;
         }
+        let (member, _score) = self.value.zset_value()?.values.get(row_ix)?;
+        Some(member.clone())
     }
 
     /// Returns the total cardinality of the ZSET (from Redis ZCARD).
@@ -185,9 +210,16 @@ impl ZedisKvFetcher for ZedisZsetValues {
     ///
     /// Called when the user edits the score column directly in the table.
     /// Updates the score for the existing member using Redis ZADD.
-    fn handle_update_value(&self, _row_ix: usize, values: Vec<SharedString>, _window: &mut Window, cx: &mut App) {
-        // Extract member name and new score from values
-        let Some(member) = values.first() else {
+    fn handle_update_value(
+        &self,
+        row_ix: usize,
+        values: Vec<SharedString>,
+        _original_values: Vec<SharedString>,
+        _window: &mut Window,
+        cx: &mut App,
+    ) {
+        // The member column is readonly, use the loaded member to keep its raw bytes
+        let Some((member, _score)) = self.value.zset_value().and_then(|zset| zset.values.get(row_ix)) else {
             return;
         };
         let Some(score_str) = values.get(1) else {
@@ -215,6 +247,17 @@ impl ZedisKvFetcher for ZedisZsetValues {
 pub struct ZedisZsetEditor {
     /// The table component that renders the ZSET members and scores
     table_state: Entity<ZedisKvTable<ZedisZsetValues>>,
+    /// Reference to server state for executing Redis operations
+    server_state: Entity<ZedisServerState>,
+    /// Kind of bounds used by the range inputs
+    range_by: ZsetRangeBy,
+    /// Input for the min bound of the range
+    min_state: Entity<InputState>,
+    /// Input for the max bound of the range
+    max_state: Entity<InputState>,
+    /// Whether the range inputs should be reset (the selected key changed)
+    key_changed: bool,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl ZedisZsetEditor {
@@ -228,6 +271,7 @@ impl ZedisZsetEditor {
     /// # Returns
     /// A new `ZedisZsetEditor` instance with a two-column table (Value and Score)
     pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut subscriptions = Vec::new();
         // Initialize the KV table with two columns: member and score
         let table_state = cx.new(|cx| {
             ZedisKvTable::<ZedisZsetValues>::new(
@@ -235,19 +279,258 @@ impl ZedisZsetEditor {
                     KvTableColumn::new("Value", None),       // Member name column (flexible width)
                     KvTableColumn::new("Score", Some(150.)), // Score column (fixed 150px width)
                 ],
-                server_state,
+                server_state.clone(),
                 window,
                 cx,
             )
         });
 
-        Self { table_state }
+        let range_by = ZsetRangeBy::default();
+        let (min, max) = range_by.default_bounds();
+        let min_state = cx.new(|cx| InputState::new(window, cx).placeholder(min));
+        let max_state = cx.new(|cx| InputState::new(window, cx).placeholder(max));
+        // Apply the range on Enter
+        for state in [&min_state, &max_state] {
+            subscriptions.push(cx.subscribe(state, |this, _, event, cx| {
+                if matches!(event, InputEvent::PressEnter { .. }) {
+                    this.apply_range(cx);
+                }
+            }));
+        }
+        // Reset the range inputs when key selection changes
+        subscriptions.push(cx.subscribe(&server_state, |this, _, event, _cx| {
+            if matches!(event, ServerEvent::KeySelected(_)) {
+                this.key_changed = true;
+            }
+        }));
+
+        Self {
+            table_state,
+            server_state,
+            range_by,
+            min_state,
+            max_state,
+            key_changed: false,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Switches the kind of range bounds, the inputs are cleared.
+    fn set_range_by(&mut self, range_by: ZsetRangeBy, window: &mut Window, cx: &mut Context<Self>) {
+        self.range_by = range_by;
+        let (min, max) = range_by.default_bounds();
+        for (state, placeholder) in [(&self.min_state, min), (&self.max_state, max)] {
+            state.update(cx, |input, cx| {
+                input.set_value(SharedString::default(), window, cx);
+                input.set_placeholder(placeholder, window, cx);
+            });
+        }
+        cx.notify();
+    }
+
+    /// Browses the range of the inputs, empty bounds use the whole sorted set.
+    fn apply_range(&mut self, cx: &mut Context<Self>) {
+        let (default_min, default_max) = self.range_by.default_bounds();
+        let bound = |state: &Entity<InputState>, default: &'static str, cx: &Context<Self>| {
+            let value = state.read(cx).value().trim().to_string();
+            if value.is_empty() {
+                SharedString::from(default)
+            } else {
+                value.into()
+            }
+        };
+        let range = ZsetRange {
+            by: self.range_by,
+            min: bound(&self.min_state, default_min, cx),
+            max: bound(&self.max_state, default_max, cx),
+        };
+        self.server_state.update(cx, |state, cx| {
+            state.set_zset_range(Some(range), cx);
+        });
+    }
+
+    /// Browses all members again.
+    fn clear_range(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        for state in [&self.min_state, &self.max_state] {
+            state.update(cx, |input, cx| {
+                input.set_value(SharedString::default(), window, cx);
+            });
+        }
+        self.server_state.update(cx, |state, cx| {
+            state.set_zset_range(None, cx);
+        });
+    }
+
+    /// Opens a dialog to increment the score of a member (ZINCRBY).
+    fn handle_incr_score(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            if values.len() != 2 || values[0].is_empty() {
+                return false;
+            }
+            let Ok(increment) = values[1].parse::<f64>() else {
+                return false;
+            };
+            server_state.update(cx, |this, cx| {
+                this.incr_zset_value(values[0].clone(), increment, cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+
+        let fields = vec![
+            FormField::new(i18n_common(cx, "value"))
+                .with_placeholder(i18n_common(cx, "value_placeholder"))
+                .with_focus(),
+            FormField::new(i18n_zset_editor(cx, "increment"))
+                .with_placeholder(i18n_zset_editor(cx, "increment_placeholder"))
+                .with_focus(),
+        ];
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_zset_editor(cx, "incr_score_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    /// Asks for confirmation, then removes all members of the browsed range.
+    fn handle_remove_range(&mut self, count: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            let message = t!("zset_editor.remove_range_prompt", count = count, locale = locale).to_string();
+            let server_state = server_state.clone();
+            dialog.confirm().child(message).on_ok(move |_, window, cx| {
+                server_state.update(cx, |state, cx| {
+                    state.remove_zset_range(cx);
+                });
+                window.close_dialog(cx);
+                true
+            })
+        });
+    }
+
+    /// Renders the range toolbar: kind of bounds, min/max inputs and range actions.
+    fn render_range_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let zset = self.server_state.read(cx).value().and_then(|value| value.zset_value());
+        let range = zset.and_then(|zset| zset.range.clone());
+        let range_count = zset.filter(|_| range.is_some()).map(|zset| zset.size);
+        let readonly = self.server_state.read(cx).is_readonly();
+
+        let range_by_button = |id: &'static str, range_by: ZsetRangeBy, label: &'static str, cx: &mut Context<Self>| {
+            Button::new(id)
+                .outline()
+                .small()
+                .label(i18n_zset_editor(cx, label))
+                .selected(self.range_by == range_by)
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.set_range_by(range_by, window, cx);
+                }))
+        };
+
+        let mut bar = h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(range_by_button(
+                "zedis-zset-range-by-rank",
+                ZsetRangeBy::Rank,
+                "range_by_rank",
+                cx,
+            ))
+            .child(range_by_button(
+                "zedis-zset-range-by-score",
+                ZsetRangeBy::Score,
+                "range_by_score",
+                cx,
+            ))
+            .child(range_by_button(
+                "zedis-zset-range-by-lex",
+                ZsetRangeBy::Lex,
+                "range_by_lex",
+                cx,
+            ))
+            .child(Input::new(&self.min_state).small().w(px(RANGE_INPUT_WIDTH)))
+            .child(Input::new(&self.max_state).small().w(px(RANGE_INPUT_WIDTH)))
+            .child(
+                Button::new("zedis-zset-range-apply")
+                    .outline()
+                    .small()
+                    .label(i18n_zset_editor(cx, "apply_range"))
+                    .tooltip(i18n_zset_editor(cx, "apply_range_tooltip"))
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.apply_range(cx);
+                    })),
+            )
+            .child(
+                Button::new("zedis-zset-range-clear")
+                    .outline()
+                    .small()
+                    .label(i18n_zset_editor(cx, "clear_range"))
+                    .disabled(range.is_none())
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.clear_range(window, cx);
+                    })),
+            );
+        if let Some(count) = range_count {
+            bar = bar.child(
+                Label::new(format!("{} {count}", i18n_zset_editor(cx, "range_count")))
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground),
+            );
+        }
+        bar.child(h_flex().flex_1())
+            .child(
+                Button::new("zedis-zset-incr-score")
+                    .outline()
+                    .small()
+                    .label(i18n_zset_editor(cx, "incr_score"))
+                    .tooltip(i18n_zset_editor(cx, "incr_score_tooltip"))
+                    .disabled(readonly)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.handle_incr_score(window, cx);
+                    })),
+            )
+            .child(
+                Button::new("zedis-zset-remove-range")
+                    .outline()
+                    .small()
+                    .danger()
+                    .label(i18n_zset_editor(cx, "remove_range"))
+                    .tooltip(i18n_zset_editor(cx, "remove_range_tooltip"))
+                    .disabled(readonly || range_count.is_none_or(|count| count == 0))
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.handle_remove_range(range_count.unwrap_or_default(), window, cx);
+                    })),
+            )
     }
 }
 
 impl Render for ZedisZsetEditor {
-    /// Renders the ZSET editor as a full-size container with the table.
-    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
-        div().size_full().child(self.table_state.clone()).into_any_element()
+    /// Renders the ZSET editor as a full-size container with the range toolbar and the table.
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Reset the range inputs when key changes
+        if self.key_changed {
+            self.key_changed = false;
+            self.set_range_by(ZsetRangeBy::default(), window, cx);
+        }
+        v_flex()
+            .size_full()
+            .child(self.render_range_bar(cx))
+            .child(
+                div()
+                    .flex_1()
+                    .w_full()
+                    .overflow_hidden()
+                    .child(self.table_state.clone()),
+            )
+            .into_any_element()
     }
 }